@@ -5,16 +5,25 @@ use crate::config::Config;
 use crate::errors::GitzError;
 use crate::git::Repository;
 use crate::ui::views::repo_view::RepoView;
+use crate::ui::views::branches_view::BranchesView;
+use crate::ui::views::tags_view::TagsView;
+use crate::ui::views::commits_view::CommitsView;
+use crate::ui::views::reflog_view::ReflogView;
+use crate::ui::views::stashes_view::StashesView;
+use crate::ui::views::remotes_view::RemotesView;
+use crate::ui::views::submodules_view::SubmodulesView;
+use crate::ui::views::rebase_view::RebaseView;
+use crate::ui::views::merge_view::MergeView;
+use crate::ui::views::bisect_view::BisectView;
+use crate::ui::views::blame_view::BlameView;
+use crate::ui::views::file_history_view::FileHistoryView;
 use crate::ui::views::worktrees_view::WorktreesView;
 use crate::ui::views::workflow_view::WorkflowView;
-use crate::event::AppEvent;
+use crate::ui::views::doctor_view::DoctorView;
+use crate::event::{AppEvent, TaskOrigin};
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::{Color, Style, Modifier};
-use ratatui::widgets::{Block, Borders, Paragraph, Tabs};
-use ratatui::text::Span;
-use crossterm::event::{self, Event as CEvent, KeyCode};
+use crossterm::event::{self, Event as CEvent, KeyCode, KeyModifiers};
 use std::time::Duration;
 use tokio::sync::mpsc::{self, Sender, Receiver};
 
@@ -23,11 +32,21 @@ use tokio::sync::mpsc::{self, Sender, Receiver};
 pub enum View {
     Files,
     Branches,
+    Tags,
     Commits,
+    Reflog,
     Stashes,
     Remotes,
+    Submodules,
+    Rebase,
+    Merge,
+    Bisect,
     Worktrees,
     Workflows,
+    Doctor,
+    /// Opened on a file from another view; not tabs.
+    Blame,
+    FileHistory,
 }
 
 impl View {
@@ -36,31 +55,58 @@ impl View {
         match self {
             View::Files => "Files",
             View::Branches => "Branches",
+            View::Tags => "Tags",
             View::Commits => "Commits",
+            View::Reflog => "Reflog",
             View::Stashes => "Stashes",
             View::Remotes => "Remotes",
+            View::Submodules => "Submodules",
+            View::Rebase => "Rebase",
+            View::Merge => "Merge",
+            View::Bisect => "Bisect",
             View::Worktrees => "Worktrees",
             View::Workflows => "Workflows",
+            View::Doctor => "Doctor",
+            View::Blame => "Blame",
+            View::FileHistory => "History",
         }
     }
 
     /// Get all views for tab iteration.
     pub fn all() -> &'static [View] {
-        &[View::Files, View::Branches, View::Commits, View::Stashes, View::Remotes, View::Worktrees, View::Workflows]
+        &[View::Files, View::Branches, View::Tags, View::Commits, View::Reflog, View::Stashes, View::Remotes, View::Submodules, View::Rebase, View::Merge, View::Bisect, View::Worktrees, View::Workflows, View::Doctor]
+    }
+
+    /// Whether the tab shows or changes the work tree, so is hidden for
+    /// bare repositories.
+    pub fn needs_work_tree(&self) -> bool {
+        matches!(
+            self,
+            View::Files | View::Stashes | View::Submodules | View::Rebase | View::Merge | View::Bisect | View::Workflows
+        )
+    }
+
+    /// The tabs available for a repository: all of them, or the browsing
+    /// ones when it is bare.
+    pub fn tabs(bare: bool) -> Vec<View> {
+        View::all().iter().copied().filter(|v| !(bare && v.needs_work_tree())).collect()
+    }
+
+    /// The tab a repository opens on.
+    pub fn home(bare: bool) -> View {
+        if bare { View::Commits } else { View::Files }
     }
 
     /// Switch to next view.
-    pub fn next(&self) -> View {
-        let all = View::all();
-        let current_idx = all.iter().position(|&v| v == *self).unwrap_or(0);
-        all[(current_idx + 1) % all.len()]
+    pub fn next(&self, tabs: &[View]) -> View {
+        let current_idx = tabs.iter().position(|v| v == self).unwrap_or(0);
+        tabs[(current_idx + 1) % tabs.len()]
     }
 
     /// Switch to previous view.
-    pub fn prev(&self) -> View {
-        let all = View::all();
-        let current_idx = all.iter().position(|&v| v == *self).unwrap_or(0);
-        all[(current_idx + all.len() - 1) % all.len()]
+    pub fn prev(&self, tabs: &[View]) -> View {
+        let current_idx = tabs.iter().position(|v| v == self).unwrap_or(0);
+        tabs[(current_idx + tabs.len() - 1) % tabs.len()]
     }
 }
 
@@ -72,21 +118,36 @@ pub struct App {
     event_tx: Sender<AppEvent>,
     event_rx: Receiver<AppEvent>,
     current_view: View,
+    /// Views to return to when a blame or history view is closed.
+    view_stack: Vec<View>,
+    /// Repositories a submodule was opened from, innermost last.
+    parent_repos: Vec<Repository>,
+    views: Views,
+}
+
+/// The per-tab views, kept apart from the terminal so they can be borrowed while drawing.
+struct Views {
     repo_view: RepoView,
+    branches_view: BranchesView,
+    tags_view: TagsView,
+    commits_view: CommitsView,
+    reflog_view: ReflogView,
+    stashes_view: StashesView,
+    remotes_view: RemotesView,
+    submodules_view: SubmodulesView,
+    rebase_view: RebaseView,
+    merge_view: MergeView,
+    bisect_view: BisectView,
+    blame_view: BlameView,
+    file_history_view: FileHistoryView,
     worktrees_view: WorktreesView,
     workflow_view: WorkflowView,
+    doctor_view: DoctorView,
 }
 
 impl App {
-    /// Initialise the application.
-    pub async fn new<P: AsRef<std::path::Path>>(repo_path: P, config: Config) -> Result<Self, GitzError> {
-        // Open or initialise repository.
-        let repo = if repo_path.as_ref().join(".git").exists() {
-            Repository::open(repo_path)?
-        } else {
-            Repository::init(repo_path)?
-        };
-
+    /// Initialise the application on an opened repository.
+    pub async fn new(repo: Repository, config: Config) -> Result<Self, GitzError> {
         // Terminal setup.
         let stdout = std::io::stdout();
         let backend = CrosstermBackend::new(stdout);
@@ -96,21 +157,53 @@ impl App {
         let (tx, rx) = mpsc::channel(100);
 
         // Initialise UI views.
-        let repo_view = RepoView::new();
+        let repo_view = RepoView::new(tx.clone());
+        let branches_view = BranchesView::new(tx.clone());
+        let tags_view = TagsView::new(tx.clone());
+        let commits_view = CommitsView::new(tx.clone());
+        let reflog_view = ReflogView::new();
+        let stashes_view = StashesView::new();
+        let remotes_view = RemotesView::new(tx.clone());
+        let submodules_view = SubmodulesView::new(tx.clone());
+        let rebase_view = RebaseView::new();
+        let merge_view = MergeView::new();
+        let bisect_view = BisectView::new(tx.clone());
+        let blame_view = BlameView::new(tx.clone());
+        let file_history_view = FileHistoryView::new(tx.clone());
         let worktrees_view = WorktreesView::new();
         let workflow_view = WorkflowView::new();
+        let doctor_view = DoctorView::new(tx.clone());
 
-        Ok(Self {
+        let mut app = Self {
+            current_view: View::home(repo.is_bare()),
             repo,
             config,
             terminal,
             event_tx: tx,
             event_rx: rx,
-            current_view: View::Files,
-            repo_view,
-            worktrees_view,
-            workflow_view,
-        })
+            view_stack: Vec::new(),
+            parent_repos: Vec::new(),
+            views: Views {
+                repo_view,
+                branches_view,
+                tags_view,
+                commits_view,
+                reflog_view,
+                stashes_view,
+                remotes_view,
+                submodules_view,
+                rebase_view,
+                merge_view,
+                bisect_view,
+                blame_view,
+                file_history_view,
+                worktrees_view,
+                workflow_view,
+                doctor_view,
+            },
+        };
+        app.refresh_current_view()?;
+        Ok(app)
     }
 
     /// Main event loop.
@@ -135,12 +228,14 @@ impl App {
         while let Some(event) = self.event_rx.recv().await {
             match event {
                 AppEvent::Key(key) => {
-                    if key.code == KeyCode::Char('q') {
+                    // While a view is reading text, every key belongs to it
+                    let capturing = self.view_captures_input();
+                    if !capturing && key.code == KeyCode::Char('q') {
                         break;
                     }
 
                     // Handle global key bindings first
-                    if !self.handle_global_key(key)? {
+                    if capturing || !self.handle_global_key(key)? {
                         // If not a global key, handle in current view
                         self.handle_view_key(key)?;
                     }
@@ -151,6 +246,94 @@ impl App {
                 AppEvent::Refresh => {
                     self.draw()?;
                 }
+                AppEvent::Progress(line) => {
+                    self.views.remotes_view.set_progress(line);
+                    self.draw()?;
+                }
+                AppEvent::TaskDone { origin, result } => {
+                    let origin_view = match origin {
+                        TaskOrigin::Remotes => {
+                            self.views.remotes_view.task_finished(&self.repo, result)?;
+                            View::Remotes
+                        }
+                        TaskOrigin::Tags => {
+                            self.views.tags_view.task_finished(&self.repo, result)?;
+                            View::Tags
+                        }
+                        TaskOrigin::Submodules => {
+                            self.views.submodules_view.task_finished(&self.repo, result)?;
+                            View::Submodules
+                        }
+                        TaskOrigin::Bisect => {
+                            self.views.bisect_view.task_finished(&self.repo, &self.config, result)?;
+                            View::Bisect
+                        }
+                    };
+                    if self.current_view != origin_view {
+                        self.refresh_current_view()?;
+                    }
+                    self.draw()?;
+                }
+                AppEvent::DoctorDone(result) => {
+                    self.views.doctor_view.task_finished(result);
+                    self.draw()?;
+                }
+                AppEvent::OpenRebase(oid) if !self.repo.is_bare() => {
+                    self.views.rebase_view.plan(&self.repo, oid)?;
+                    self.current_view = View::Rebase;
+                    self.draw()?;
+                }
+                AppEvent::OpenConflicts if !self.repo.is_bare() => {
+                    self.current_view = View::Merge;
+                    self.refresh_current_view()?;
+                    self.draw()?;
+                }
+                AppEvent::OpenBisect if !self.repo.is_bare() => {
+                    self.current_view = View::Bisect;
+                    self.refresh_current_view()?;
+                    self.draw()?;
+                }
+                // Work-tree views are hidden in bare repositories.
+                AppEvent::OpenRebase(_) | AppEvent::OpenConflicts | AppEvent::OpenBisect => {}
+                AppEvent::OpenReflog(refname) => {
+                    self.views.reflog_view.show(&self.repo, &refname)?;
+                    self.current_view = View::Reflog;
+                    self.draw()?;
+                }
+                AppEvent::OpenRepository(path) => {
+                    match Repository::open(&path) {
+                        Ok(repo) => {
+                            self.parent_repos.push(std::mem::replace(&mut self.repo, repo));
+                            self.switch_repository()?;
+                        }
+                        Err(e) => self.views.submodules_view.set_error(&e),
+                    }
+                    self.draw()?;
+                }
+                AppEvent::CloseRepository => {
+                    if let Some(parent) = self.parent_repos.pop() {
+                        self.repo = parent;
+                        self.switch_repository()?;
+                        self.draw()?;
+                    }
+                }
+                AppEvent::OpenBlame { path, at } => {
+                    self.views.blame_view.open(&self.repo, &self.config, &path, at)?;
+                    self.push_view(View::Blame);
+                    self.draw()?;
+                }
+                AppEvent::OpenFileHistory(path) => {
+                    self.views.file_history_view.open(&self.repo, &self.config, &path)?;
+                    self.push_view(View::FileHistory);
+                    self.draw()?;
+                }
+                AppEvent::Back => {
+                    self.current_view = self.view_stack.pop().unwrap_or(View::home(self.repo.is_bare()));
+                    if !matches!(self.current_view, View::Blame | View::FileHistory) {
+                        self.refresh_current_view()?;
+                    }
+                    self.draw()?;
+                }
                 AppEvent::Quit => {
                     break;
                 }
@@ -160,46 +343,76 @@ impl App {
         Ok(())
     }
 
+    /// Start over on the home tab after `self.repo` was replaced.
+    fn switch_repository(&mut self) -> Result<(), GitzError> {
+        self.current_view = View::home(self.repo.is_bare());
+        self.view_stack.clear();
+        self.refresh_current_view()
+    }
+
+    /// Show `view` on top of the current one, which `Back` returns to.
+    fn push_view(&mut self, view: View) {
+        if self.current_view != view {
+            self.view_stack.push(self.current_view);
+        }
+        self.current_view = view;
+    }
+
     /// Handle global key bindings that work across all views.
     fn handle_global_key(&mut self, key: crossterm::event::KeyEvent) -> Result<bool, GitzError> {
-        match key.code {
-            KeyCode::Tab => {
-                self.current_view = self.current_view.next();
-                Ok(true)
-            }
-            KeyCode::BackTab => {
-                self.current_view = self.current_view.prev();
-                Ok(true)
-            }
-            KeyCode::Char('1') => {
-                self.current_view = View::Files;
-                Ok(true)
-            }
-            KeyCode::Char('2') => {
-                self.current_view = View::Branches;
-                Ok(true)
-            }
-            KeyCode::Char('3') => {
-                self.current_view = View::Commits;
-                Ok(true)
-            }
-            KeyCode::Char('4') => {
-                self.current_view = View::Stashes;
-                Ok(true)
-            }
-            KeyCode::Char('5') => {
-                self.current_view = View::Remotes;
-                Ok(true)
-            }
-            KeyCode::Char('6') => {
-                self.current_view = View::Worktrees;
-                Ok(true)
-            }
-            KeyCode::Char('7') => {
-                self.current_view = View::Workflows;
-                Ok(true)
+        let tabs = View::tabs(self.repo.is_bare());
+        let target = match key.code {
+            KeyCode::Tab => self.current_view.next(&tabs),
+            KeyCode::BackTab => self.current_view.prev(&tabs),
+            // 1-9 pick the first tabs, 0 the tenth; with Alt, the ten after.
+            KeyCode::Char(c @ '0'..='9') => {
+                let page = if key.modifiers.contains(KeyModifiers::ALT) { 10 } else { 0 };
+                match tabs.get(page + (c as usize + 9 - '1' as usize) % 10) {
+                    Some(view) => *view,
+                    None => return Ok(false),
+                }
             }
-            _ => Ok(false),
+            _ => return Ok(false),
+        };
+        self.current_view = target;
+        self.view_stack.clear();
+        self.refresh_current_view()?;
+        Ok(true)
+    }
+
+    /// Whether the current view is reading text input (prompt open).
+    fn view_captures_input(&self) -> bool {
+        match self.current_view {
+            View::Files => self.views.repo_view.captures_input(),
+            View::Branches => self.views.branches_view.captures_input(),
+            View::Tags => self.views.tags_view.captures_input(),
+            View::Commits => self.views.commits_view.captures_input(),
+            View::Reflog => self.views.reflog_view.captures_input(),
+            View::Stashes => self.views.stashes_view.captures_input(),
+            View::Remotes => self.views.remotes_view.captures_input(),
+            View::Rebase => self.views.rebase_view.captures_input(),
+            View::Bisect => self.views.bisect_view.captures_input(),
+            _ => false,
+        }
+    }
+
+    /// Reload the data of the view that just became active.
+    fn refresh_current_view(&mut self) -> Result<(), GitzError> {
+        match self.current_view {
+            View::Files => self.views.repo_view.refresh(&self.repo, &self.config),
+            View::Branches => self.views.branches_view.refresh(&self.repo),
+            View::Tags => self.views.tags_view.refresh(&self.repo),
+            View::Commits => self.views.commits_view.refresh(&self.repo, &self.config),
+            View::Reflog => self.views.reflog_view.refresh(&self.repo),
+            View::Stashes => self.views.stashes_view.refresh(&self.repo, &self.config),
+            View::Remotes => self.views.remotes_view.refresh(&self.repo),
+            View::Submodules => self.views.submodules_view.refresh(&self.repo),
+            View::Rebase => self.views.rebase_view.refresh(&self.repo),
+            View::Merge => self.views.merge_view.refresh(&self.repo),
+            View::Bisect => self.views.bisect_view.refresh(&self.repo, &self.config),
+            View::Worktrees => self.views.worktrees_view.refresh(&self.repo),
+            View::Doctor => self.views.doctor_view.refresh(&self.repo),
+            _ => Ok(()),
         }
     }
 
@@ -207,21 +420,52 @@ impl App {
     fn handle_view_key(&mut self, key: crossterm::event::KeyEvent) -> Result<(), GitzError> {
         match self.current_view {
             View::Files => {
-                self.repo_view.handle_key(key, &self.repo, &self.config)?;
+                self.views.repo_view.handle_key(key, &self.repo, &self.config)?;
             }
             View::Branches => {
+                self.views.branches_view.handle_key(key, &self.repo, &self.config)?;
+            }
+            View::Tags => {
+                self.views.tags_view.handle_key(key, &self.repo, &self.config)?;
             }
             View::Commits => {
+                self.views.commits_view.handle_key(key, &mut self.repo, &self.config)?;
+            }
+            View::Reflog => {
+                self.views.reflog_view.handle_key(key, &mut self.repo, &self.config)?;
             }
             View::Stashes => {
+                self.views.stashes_view.handle_key(key, &mut self.repo, &self.config)?;
             }
             View::Remotes => {
+                self.views.remotes_view.handle_key(key, &self.repo, &self.config)?;
+            }
+            View::Submodules => {
+                self.views.submodules_view.handle_key(key, &self.repo, &self.config)?;
+            }
+            View::Rebase => {
+                self.views.rebase_view.handle_key(key, &self.repo, &self.config)?;
+            }
+            View::Merge => {
+                self.views.merge_view.handle_key(key, &self.repo, &self.config)?;
+            }
+            View::Bisect => {
+                self.views.bisect_view.handle_key(key, &self.repo, &self.config)?;
             }
             View::Worktrees => {
-                self.worktrees_view.handle_key(key, &self.repo, &self.config)?;
+                self.views.worktrees_view.handle_key(key, &self.repo, &self.config)?;
             }
             View::Workflows => {
-                self.workflow_view.handle_key(key, &self.repo, &self.config)?;
+                self.views.workflow_view.handle_key(key, &self.repo, &self.config)?;
+            }
+            View::Doctor => {
+                self.views.doctor_view.handle_key(key, &self.repo, &self.config)?;
+            }
+            View::Blame => {
+                self.views.blame_view.handle_key(key, &self.repo, &self.config)?;
+            }
+            View::FileHistory => {
+                self.views.file_history_view.handle_key(key, &self.repo, &self.config)?;
             }
         }
         Ok(())
@@ -231,85 +475,65 @@ impl App {
     fn draw(&mut self) -> Result<(), GitzError> {
         let current_view = self.current_view;
         let repo = &self.repo;
-        let repo_view = &self.repo_view;
-        let worktrees_view = &self.worktrees_view;
-        let workflow_view = &mut self.workflow_view;
+        let views = &mut self.views;
         self.terminal.draw(move |f| {
-            let _ = Self::draw_ui_static(f, current_view, repo, repo_view, worktrees_view, workflow_view);
+            let _ = Self::draw_ui_static(f, current_view, repo, views);
         })?;
         Ok(())
     }
 
     /// Draw the UI for the current view.
-    fn draw_ui_static(f: &mut ratatui::Frame, current_view: View, repo: &Repository, repo_view: &RepoView, worktrees_view: &WorktreesView, workflow_view: &mut WorkflowView) -> Result<(), GitzError> {
+    fn draw_ui_static(f: &mut ratatui::Frame, current_view: View, repo: &Repository, views: &mut Views) -> Result<(), GitzError> {
         match current_view {
             View::Files => {
-                repo_view.draw(f, repo)?;
+                views.repo_view.draw(f, repo)?;
             }
             View::Branches => {
-                Self::draw_placeholder_view_static(f, "Branches", current_view);
+                views.branches_view.draw(f, repo)?;
+            }
+            View::Tags => {
+                views.tags_view.draw(f, repo)?;
             }
             View::Commits => {
-                Self::draw_placeholder_view_static(f, "Commits", current_view);
+                views.commits_view.draw(f, repo)?;
+            }
+            View::Reflog => {
+                views.reflog_view.draw(f, repo)?;
             }
             View::Stashes => {
-                Self::draw_placeholder_view_static(f, "Stashes", current_view);
+                views.stashes_view.draw(f, repo)?;
             }
             View::Remotes => {
-                Self::draw_placeholder_view_static(f, "Remotes", current_view);
+                views.remotes_view.draw(f, repo)?;
+            }
+            View::Submodules => {
+                views.submodules_view.draw(f, repo)?;
+            }
+            View::Rebase => {
+                views.rebase_view.draw(f, repo)?;
+            }
+            View::Merge => {
+                views.merge_view.draw(f, repo)?;
+            }
+            View::Bisect => {
+                views.bisect_view.draw(f, repo)?;
             }
             View::Worktrees => {
-                worktrees_view.draw(f, repo)?;
+                views.worktrees_view.draw(f, repo)?;
             }
             View::Workflows => {
-                workflow_view.draw(f, repo)?;
+                views.workflow_view.draw(f, repo)?;
+            }
+            View::Doctor => {
+                views.doctor_view.draw(f, repo)?;
+            }
+            View::Blame => {
+                views.blame_view.draw(f, repo)?;
+            }
+            View::FileHistory => {
+                views.file_history_view.draw(f, repo)?;
             }
         }
         Ok(())
     }
-
-    /// Draw a placeholder view for unimplemented tabs.
-    fn draw_placeholder_view_static(f: &mut ratatui::Frame, title: &str, current_view: View) {
-
-        let size = f.area();
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // top bar
-                Constraint::Min(0),    // main area
-                Constraint::Length(3), // status bar
-            ])
-            .split(size);
-
-        // Top bar with tabs
-        Self::draw_tab_bar_static(f, chunks[0], current_view);
-
-        // Placeholder content
-        let placeholder = Paragraph::new(format!("{} view - Coming soon!", title))
-            .style(Style::default().fg(Color::Yellow))
-            .block(Block::default().borders(Borders::ALL).title(title));
-        f.render_widget(placeholder, chunks[1]);
-
-        // Status bar
-        let status = Paragraph::new("Press Tab to switch views | q to quit")
-            .style(Style::default().fg(Color::White))
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(status, chunks[2]);
-    }
-
-    /// Draw the tab bar at the top.
-    fn draw_tab_bar_static(f: &mut ratatui::Frame, area: ratatui::layout::Rect, current_view: View) {
-        let titles = View::all()
-            .iter()
-            .map(|v| Span::raw(v.name()))
-            .collect::<Vec<_>>();
-
-        let tabs = Tabs::new(titles)
-            .block(Block::default().borders(Borders::ALL).title("gitpulse"))
-            .select(View::all().iter().position(|&v| v == current_view).unwrap_or(0))
-            .style(Style::default().fg(Color::Cyan))
-            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
-
-        f.render_widget(tabs, area);
-    }
 }
@@ -1,15 +1,106 @@
 #[allow(dead_code)]
 use crate::errors::GitzError;
 use crate::git::Repository;
+use std::path::{Component, Path, PathBuf};
 
 /// Stage all changes in the repository.
 pub fn stage_all(repo: &Repository) -> Result<(), GitzError> {
     repo.add_all()
 }
 
-/// Stage a specific file.
-pub fn stage_file(repo: &Repository, _path: &str) -> Result<(), GitzError> {
-    // TODO: Implementiere einzelne Datei staging
-    // Für jetzt: stage alles
-    repo.add_all()
+/// Stage the given paths, relative to the work tree root; `.` stages
+/// everything and a directory everything in it.
+pub fn stage_paths(repo: &Repository, paths: &[String]) -> Result<(), GitzError> {
+    if paths.iter().any(|p| p == ".") {
+        return repo.add_all();
+    }
+    let specs: Vec<&str> = paths.iter().map(|p| p.trim_end_matches('/')).collect();
+    repo.add_pathspecs(&specs)
+}
+
+/// Turn `path`, given relative to `cwd`, into a path relative to the work
+/// tree root (`.` for the root itself), as `git add` does when run from a
+/// subdirectory. Both directories must be absolute.
+pub fn workdir_relative(workdir: &Path, cwd: &Path, path: &str) -> Result<String, GitzError> {
+    let mut full = PathBuf::new();
+    for component in cwd.join(path).components() {
+        match component {
+            Component::ParentDir => {
+                full.pop();
+            }
+            Component::CurDir => {}
+            other => full.push(other),
+        }
+    }
+    let relative = full
+        .strip_prefix(workdir)
+        .map_err(|_| GitzError::InvalidInput(format!("{} is outside the work tree {}", path, workdir.display())))?;
+    Ok(match relative.to_string_lossy().into_owned() {
+        s if s.is_empty() => ".".to_string(),
+        s => s,
+    })
+}
+
+/// Stage a specific file, including its deletion.
+pub fn stage_file(repo: &Repository, path: &str) -> Result<(), GitzError> {
+    repo.stage_path(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_stage_file_only_touches_that_path() {
+        let t = TestRepo::new();
+        t.write("a.txt", "a\n");
+        t.write("b.txt", "b\n");
+        t.commit_all("initial");
+        t.write("a.txt", "a2\n");
+        t.write("b.txt", "b2\n");
+
+        stage_file(&t.repo, "a.txt").unwrap();
+        assert_eq!(t.index_contents("a.txt").unwrap(), "a2\n");
+        assert_eq!(t.index_contents("b.txt").unwrap(), "b\n");
+    }
+
+    #[test]
+    fn test_stage_paths_by_directory() {
+        let t = TestRepo::new();
+        t.write("src/a.txt", "a\n");
+        t.write("src/gone.txt", "x\n");
+        t.write("b.txt", "b\n");
+        t.commit_all("initial");
+        t.write("src/a.txt", "a2\n");
+        t.write("b.txt", "b2\n");
+        std::fs::remove_file(t.path("src/gone.txt")).unwrap();
+
+        stage_paths(&t.repo, &["src/".to_string()]).unwrap();
+        assert_eq!(t.index_contents("src/a.txt").unwrap(), "a2\n");
+        assert_eq!(t.index_contents("src/gone.txt"), None);
+        assert_eq!(t.index_contents("b.txt").unwrap(), "b\n");
+    }
+
+    #[test]
+    fn test_workdir_relative_from_subdirectory() {
+        let root = Path::new("/work/repo");
+        let sub = root.join("src/ui");
+        assert_eq!(workdir_relative(root, &sub, "main.rs").unwrap(), "src/ui/main.rs");
+        assert_eq!(workdir_relative(root, &sub, "../lib.rs").unwrap(), "src/lib.rs");
+        assert_eq!(workdir_relative(root, &sub, "../..").unwrap(), ".");
+        assert_eq!(workdir_relative(root, root, "./a/").unwrap(), "a");
+        assert!(workdir_relative(root, root, "../other").is_err());
+    }
+
+    #[test]
+    fn test_stage_file_records_deletion() {
+        let t = TestRepo::new();
+        t.write("gone.txt", "bye\n");
+        t.commit_all("initial");
+        std::fs::remove_file(t.path("gone.txt")).unwrap();
+
+        stage_file(&t.repo, "gone.txt").unwrap();
+        assert_eq!(t.index_contents("gone.txt"), None);
+    }
 }
@@ -0,0 +1,85 @@
+#[allow(dead_code)]
+use crate::commands::format::{write_json, Format};
+use crate::errors::GitzError;
+use crate::git::{BranchKind, Repository};
+use std::io::Write;
+
+/// Print local then remote-tracking branches in the style of
+/// `git branch -avv`, or as a JSON array of `BranchInfo`.
+pub fn branches(repo: &Repository, format: Format, out: &mut dyn Write) -> Result<(), GitzError> {
+    let branches = repo.branches()?;
+    if format == Format::Json {
+        return write_json(out, &branches);
+    }
+    let names: Vec<String> = branches
+        .iter()
+        .map(|b| match b.kind {
+            BranchKind::Local => b.name.clone(),
+            BranchKind::Remote => format!("remotes/{}", b.name),
+        })
+        .collect();
+    let width = names.iter().map(String::len).max().unwrap_or(0);
+    for (branch, name) in branches.iter().zip(names) {
+        let tracking = match &branch.upstream {
+            Some(upstream) => {
+                let mut counts = Vec::new();
+                if branch.ahead > 0 {
+                    counts.push(format!("ahead {}", branch.ahead));
+                }
+                if branch.behind > 0 {
+                    counts.push(format!("behind {}", branch.behind));
+                }
+                if counts.is_empty() {
+                    format!("[{}] ", upstream)
+                } else {
+                    format!("[{}: {}] ", upstream, counts.join(", "))
+                }
+            }
+            None => String::new(),
+        };
+        writeln!(
+            out,
+            "{} {:<width$} {} {}{}",
+            if branch.is_head { '*' } else { ' ' },
+            name,
+            &branch.target.to_string()[..7],
+            tracking,
+            branch.summary,
+            width = width
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_branches_text_and_json() {
+        let t = TestRepo::new();
+        t.write("f.txt", "x\n");
+        let head = t.commit_all("initial");
+        let current = t.repo.current_branch().unwrap();
+        t.repo.create_branch("topic", head).unwrap();
+
+        let mut out = Vec::new();
+        branches(&t.repo, Format::Text, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let current_line = text.lines().find(|l| l.starts_with('*')).unwrap();
+        assert!(current_line.contains(&current) && current_line.ends_with(" initial"));
+        assert!(text.lines().any(|l| l.starts_with("  topic")));
+
+        let mut out = Vec::new();
+        branches(&t.repo, Format::Json, &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let topic = json.as_array().unwrap().iter().find(|b| b["name"] == "topic").unwrap();
+        assert_eq!(topic["kind"], "local");
+        assert_eq!(topic["is_head"], false);
+        assert_eq!(topic["upstream"], serde_json::Value::Null);
+        assert_eq!((topic["ahead"].as_u64(), topic["behind"].as_u64()), (Some(0), Some(0)));
+        assert_eq!(topic["target"], head.to_string());
+        assert!(chrono::DateTime::parse_from_rfc3339(topic["time"].as_str().unwrap()).is_ok());
+    }
+}
@@ -0,0 +1,134 @@
+#[allow(dead_code)]
+use crate::commands;
+use crate::commands::format::Format;
+use crate::errors::GitzError;
+use crate::git::{RemoteProgress, Repository, DEFAULT_LARGE_BLOB_BYTES};
+use clap::Subcommand;
+use std::io::Write;
+
+/// Non-interactive subcommands; they run the same code as the TUI.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create an empty repository.
+    Init {
+        #[arg(default_value = ".")]
+        path: String,
+    },
+    /// Show the branch and changed files, like `git status --short --branch`.
+    Status {
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Stage files (`.` for everything, a directory for everything in it).
+    Add {
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Commit the staged changes.
+    Commit {
+        /// Commit message; several are joined as paragraphs.
+        #[arg(short, long, required = true)]
+        message: Vec<String>,
+        /// Replace the last commit instead of adding one.
+        #[arg(long)]
+        amend: bool,
+    },
+    /// Push the current branch.
+    Push {
+        /// Remote to push to (default: the upstream's remote, else origin).
+        remote: Option<String>,
+    },
+    /// Show the commit history.
+    Log {
+        /// Revision to start from (default: HEAD).
+        revision: Option<String>,
+        /// Number of commits to show.
+        #[arg(short = 'n', long, default_value_t = 20)]
+        max_count: usize,
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// List local and remote-tracking branches.
+    Branches {
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// List the main and linked work trees.
+    Worktrees {
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Check the repository's health.
+    Doctor {
+        /// Report blobs in the history of at least this many KiB.
+        #[arg(long, value_name = "KIB", default_value_t = DEFAULT_LARGE_BLOB_BYTES / 1024)]
+        large_blob_kib: u64,
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+}
+
+/// Find the repository containing the current directory.
+fn open_repo() -> Result<Repository, GitzError> {
+    Repository::discover(".")
+}
+
+/// Stage `paths`, given relative to the current directory.
+fn add(paths: &[String]) -> Result<(), GitzError> {
+    let repo = open_repo()?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitzError::InvalidInput("Cannot stage files in a bare repository".into()))?
+        .canonicalize()?;
+    let cwd = std::env::current_dir()?.canonicalize()?;
+    let paths = paths
+        .iter()
+        .map(|p| commands::add::workdir_relative(&workdir, &cwd, p))
+        .collect::<Result<Vec<_>, _>>()?;
+    commands::add::stage_paths(&repo, &paths)
+}
+
+impl Command {
+    /// Run the command, writing its output to `out`.
+    pub fn run(self, out: &mut dyn Write) -> Result<(), GitzError> {
+        match self {
+            Command::Init { path } => {
+                let repo = commands::init::init(&path)?;
+                writeln!(out, "Initialized empty Git repository in {}", repo.git_dir().display())?;
+            }
+            Command::Status { format } => commands::status::status(&open_repo()?, format, out)?,
+            Command::Add { paths } => add(&paths)?,
+            Command::Commit { message, amend } => {
+                let repo = open_repo()?;
+                let message = message.join("\n\n");
+                let oid = if amend {
+                    commands::commit::amend(&repo, &message)?
+                } else {
+                    commands::commit::commit(&repo, &message)?
+                };
+                let summary = message.lines().next().unwrap_or_default();
+                writeln!(out, "[{} {}] {}", repo.current_branch()?, &oid.to_string()[..7], summary)?;
+            }
+            Command::Push { remote } => {
+                let repo = open_repo()?;
+                let branch = repo.current_branch()?;
+                let mut progress = |p: RemoteProgress| {
+                    if let RemoteProgress::Message(text) = p {
+                        eprintln!("remote: {}", text.trim());
+                    }
+                };
+                let remote = commands::push::push(&repo, remote.as_deref(), &mut progress)?;
+                writeln!(out, "Pushed {} to {}", branch, remote)?;
+            }
+            Command::Log { revision, max_count, format } => {
+                commands::log::log(&open_repo()?, revision.as_deref(), max_count, format, out)?;
+            }
+            Command::Branches { format } => commands::branches::branches(&open_repo()?, format, out)?,
+            Command::Worktrees { format } => commands::worktrees::worktrees(&open_repo()?, format, out)?,
+            Command::Doctor { large_blob_kib, format } => {
+                commands::doctor::doctor(&open_repo()?, large_blob_kib * 1024, format, out)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -9,4 +9,64 @@ pub fn commit(repo: &Repository, message: &str) -> Result<Oid, GitzError> {
         return Err(GitzError::InvalidInput("Commit message cannot be empty".into()));
     }
     repo.commit(message)
-}
\ No newline at end of file
+}
+
+/// Amend HEAD with the staged changes and a new message.
+pub fn amend(repo: &Repository, message: &str) -> Result<Oid, GitzError> {
+    if message.trim().is_empty() {
+        return Err(GitzError::InvalidInput("Commit message cannot be empty".into()));
+    }
+    repo.amend(Some(message), true)
+}
+
+/// Give an earlier commit a new message.
+pub fn reword(repo: &Repository, target: Oid, message: &str) -> Result<Oid, GitzError> {
+    if message.trim().is_empty() {
+        return Err(GitzError::InvalidInput("Commit message cannot be empty".into()));
+    }
+    repo.reword(target, message)
+}
+
+/// Clean up a message written in the editor the way `git commit` does:
+/// drop `#` comment lines and trailing whitespace, collapse runs of blank
+/// lines and strip blank lines at either end.
+pub fn cleanup_message(raw: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in raw.lines().filter(|l| !l.starts_with('#')).map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    let mut message = lines.join("\n");
+    if !message.is_empty() {
+        message.push('\n');
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_cleanup_message() {
+        let raw = "\n# Please enter a message\nSubject  \n\n\n\nBody line\n# comment\n\n";
+        assert_eq!(cleanup_message(raw), "Subject\n\nBody line\n");
+        assert_eq!(cleanup_message("# only a template\n\n"), "");
+    }
+
+    #[test]
+    fn test_commit_rejects_empty_message() {
+        let t = TestRepo::new();
+        t.write("f.txt", "x\n");
+        t.repo.add_all().unwrap();
+        let message = cleanup_message("# Summary of the change\n");
+        assert!(matches!(commit(&t.repo, &message), Err(GitzError::InvalidInput(_))));
+        assert!(commit(&t.repo, "real message").is_ok());
+    }
+}
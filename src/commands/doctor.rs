@@ -0,0 +1,168 @@
+#[allow(dead_code)]
+use crate::commands::format::{write_json, Format};
+use crate::errors::GitzError;
+use crate::git::{DoctorReport, Repository};
+use crate::utils::{format_bytes, format_duration};
+use std::io::Write;
+
+/// Dangling objects listed before the rest are summarised.
+const MAX_DANGLING_LINES: usize = 20;
+
+/// One check of a report as text: a heading, and a line per finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub title: String,
+    /// Shown after the title, e.g. `none` or a count.
+    pub summary: String,
+    /// Whether nothing needs fixing.
+    pub ok: bool,
+    pub lines: Vec<String>,
+}
+
+impl Section {
+    fn new(title: impl Into<String>, ok: bool, summary: impl Into<String>, lines: Vec<String>) -> Self {
+        Self { title: title.into(), summary: summary.into(), ok, lines }
+    }
+}
+
+fn count_or_none(n: usize) -> String {
+    if n == 0 { "none".to_string() } else { n.to_string() }
+}
+
+/// The report as text sections, shared by `gitz doctor` and the doctor view.
+pub fn sections(report: &DoctorReport, large_blob_bytes: u64) -> Vec<Section> {
+    let objects = &report.objects;
+    let mut dangling: Vec<String> = report
+        .dangling
+        .iter()
+        .take(MAX_DANGLING_LINES)
+        .map(|d| format!("dangling {} {}", d.kind, d.oid))
+        .collect();
+    if report.dangling.len() > MAX_DANGLING_LINES {
+        dangling.push(format!("... and {} more; `git gc --prune=now` removes them", report.dangling.len() - MAX_DANGLING_LINES));
+    }
+
+    vec![
+        Section::new(
+            "Objects",
+            true,
+            format!(
+                "{} loose ({}), {} packs with {} objects ({})",
+                objects.loose,
+                format_bytes(objects.loose_bytes as usize),
+                objects.packs,
+                objects.packed,
+                format_bytes(objects.pack_bytes as usize)
+            ),
+            Vec::new(),
+        ),
+        Section::new("Dangling objects", true, count_or_none(report.dangling.len()), dangling),
+        Section::new(
+            "Lock files",
+            report.lock_files.is_empty(),
+            count_or_none(report.lock_files.len()),
+            report
+                .lock_files
+                .iter()
+                .map(|lock| {
+                    format!(
+                        "{} (left {} ago); remove it if no git process is running",
+                        lock.path.display(),
+                        format_duration(lock.age_secs)
+                    )
+                })
+                .collect(),
+        ),
+        Section::new(
+            "Broken refs",
+            report.broken_refs.is_empty(),
+            count_or_none(report.broken_refs.len()),
+            report.broken_refs.iter().map(|r| format!("{}: {}", r.name, r.problem)).collect(),
+        ),
+        Section::new(
+            format!("Large blobs ({} or more)", format_bytes(large_blob_bytes as usize)),
+            true,
+            count_or_none(report.large_blobs.len()),
+            report
+                .large_blobs
+                .iter()
+                .map(|b| format!("{:>10}  {}  {}", format_bytes(b.size as usize), &b.oid.to_string()[..7], b.path))
+                .collect(),
+        ),
+        Section::new(
+            "Identity",
+            report.missing_config.is_empty(),
+            if report.missing_config.is_empty() { "ok".to_string() } else { report.missing_config.join(", ") + " not set" },
+            report
+                .missing_config
+                .iter()
+                .map(|key| format!("set it with `git config --global {} <value>`", key))
+                .collect(),
+        ),
+        Section::new(
+            "Upstreams",
+            report.upstreams.is_empty(),
+            if report.upstreams.is_empty() { "ok".to_string() } else { report.upstreams.len().to_string() },
+            report.upstreams.iter().map(|u| format!("{}: {}", u.branch, u.problem)).collect(),
+        ),
+    ]
+}
+
+/// One-line verdict for a report.
+pub fn verdict(report: &DoctorReport) -> String {
+    match report.problems() {
+        0 => "No problems found".to_string(),
+        1 => "1 problem found".to_string(),
+        n => format!("{} problems found", n),
+    }
+}
+
+/// Check the repository and print the report, or the `DoctorReport` as JSON.
+pub fn doctor(repo: &Repository, large_blob_bytes: u64, format: Format, out: &mut dyn Write) -> Result<(), GitzError> {
+    let report = repo.doctor(large_blob_bytes)?;
+    if format == Format::Json {
+        return write_json(out, &report);
+    }
+    for section in sections(&report, large_blob_bytes) {
+        writeln!(out, "{}: {}", section.title, section.summary)?;
+        for line in &section.lines {
+            writeln!(out, "  {}", line)?;
+        }
+    }
+    writeln!(out, "{}", verdict(&report))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_doctor_text_and_json() {
+        let t = TestRepo::new();
+        t.write("a.txt", "a\n");
+        t.commit_all("initial");
+        std::fs::write(t.path(".git/index.lock"), "").unwrap();
+
+        let mut out = Vec::new();
+        doctor(&t.repo, 1024, Format::Text, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("Objects: 3 loose ("));
+        assert!(text.contains("Lock files: 1\n  "));
+        assert!(text.contains("Large blobs (1.0 KiB or more): none\n"));
+        assert!(text.ends_with("Upstreams: ok\n1 problem found\n"));
+
+        let mut out = Vec::new();
+        doctor(&t.repo, 1024, Format::Json, &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["objects"]["loose"], 3);
+        assert_eq!(json["objects"]["packs"], 0);
+        assert_eq!(json["dangling"], serde_json::json!([]));
+        assert!(json["lock_files"][0]["path"].as_str().unwrap().ends_with("index.lock"));
+        assert_eq!(json["lock_files"][0]["age_secs"], 0);
+        assert_eq!(json["missing_config"], serde_json::json!([]));
+        let keys: Vec<_> = json.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(keys, ["broken_refs", "dangling", "large_blobs", "lock_files", "missing_config", "objects", "upstreams"]);
+    }
+}
@@ -0,0 +1,22 @@
+#[allow(dead_code)]
+use crate::errors::GitzError;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::Write;
+
+/// How a command prints its result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Human-readable, in the style of the matching git command.
+    #[default]
+    Text,
+    /// JSON, in the schema documented in the README.
+    Json,
+}
+
+/// Print `value` as pretty-printed JSON followed by a newline.
+pub fn write_json<T: Serialize + ?Sized>(out: &mut dyn Write, value: &T) -> Result<(), GitzError> {
+    serde_json::to_writer_pretty(&mut *out, value).map_err(|e| GitzError::GitOperationFailed(e.to_string()))?;
+    writeln!(out)?;
+    Ok(())
+}
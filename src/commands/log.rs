@@ -0,0 +1,73 @@
+#[allow(dead_code)]
+use crate::commands::format::{write_json, Format};
+use crate::errors::GitzError;
+use crate::git::Repository;
+use chrono::DateTime;
+use std::io::Write;
+
+/// Print up to `limit` commits reachable from `start` (HEAD when `None`),
+/// newest first: short id, date, author and summary, or a JSON array of
+/// `CommitInfo`.
+pub fn log(repo: &Repository, start: Option<&str>, limit: usize, format: Format, out: &mut dyn Write) -> Result<(), GitzError> {
+    let mut cursor = repo.log_cursor(start)?;
+    let commits = repo.log_next(&mut cursor, limit)?;
+    if format == Format::Json {
+        return write_json(out, &commits);
+    }
+    for commit in commits {
+        let date = DateTime::from_timestamp(commit.time, 0)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        writeln!(out, "{} {} {}: {}", commit.short_id(), date, commit.author, commit.summary())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_log_lists_newest_first() {
+        let t = TestRepo::new();
+        t.write("f.txt", "1\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "2\n");
+        t.commit_all("second");
+
+        let mut out = Vec::new();
+        log(&t.repo, None, 10, Format::Text, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(": second"));
+        assert!(lines[1].starts_with(&first.to_string()[..7]));
+
+        let mut out = Vec::new();
+        log(&t.repo, Some(&first.to_string()), 1, Format::Text, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().ends_with(": first\n"));
+    }
+
+    #[test]
+    fn test_json_log_schema() {
+        let t = TestRepo::new();
+        t.write("f.txt", "1\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "2\n");
+        let second = t.commit_all("second\n\nbody");
+
+        let mut out = Vec::new();
+        log(&t.repo, None, 10, Format::Json, &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let commit = &json[0];
+        let keys: Vec<&str> = commit.as_object().unwrap().keys().map(String::as_str).collect();
+        assert_eq!(keys, ["author", "email", "message", "oid", "parents", "time"]);
+        assert_eq!(commit["oid"], second.to_string());
+        assert_eq!(commit["parents"], serde_json::json!([first.to_string()]));
+        assert_eq!(commit["message"], "second\n\nbody");
+        let time = commit["time"].as_str().unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(time).is_ok() && time.ends_with('Z'));
+        assert_eq!(json[1]["parents"], serde_json::json!([]));
+    }
+}
@@ -1,4 +1,13 @@
 #[allow(dead_code)]
 pub mod init;
 pub mod add;
-pub mod commit;
\ No newline at end of file
+pub mod commit;
+pub mod restore;
+pub mod status;
+pub mod log;
+pub mod push;
+pub mod branches;
+pub mod worktrees;
+pub mod doctor;
+pub mod format;
+pub mod cli;
@@ -0,0 +1,38 @@
+#[allow(dead_code)]
+use crate::errors::GitzError;
+use crate::git::{RemoteProgress, Repository};
+
+/// Push the current branch to `remote`, or to its upstream remote (else
+/// `origin`) when `None`. Returns the remote pushed to.
+pub fn push(repo: &Repository, remote: Option<&str>, progress: &mut dyn FnMut(RemoteProgress)) -> Result<String, GitzError> {
+    let remote = remote.map_or_else(|| repo.default_push_remote(), str::to_string);
+    repo.push(&remote, progress)?;
+    Ok(remote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_push_defaults_to_upstream_remote() {
+        let dir = TempDir::new().unwrap();
+        let bare = git2::Repository::init_bare(dir.path()).unwrap();
+        let t = TestRepo::new();
+        t.write("f.txt", "x\n");
+        t.commit_all("initial");
+        let branch = t.repo.current_branch().unwrap();
+        t.git().remote("upstream", &format!("file://{}", dir.path().display())).unwrap();
+        assert!(push(&t.repo, None, &mut |_| {}).is_err());
+
+        assert_eq!(push(&t.repo, Some("upstream"), &mut |_| {}).unwrap(), "upstream");
+        assert_eq!(t.repo.default_push_remote(), "upstream");
+        t.write("f.txt", "y\n");
+        let next = t.commit_all("next");
+        assert_eq!(push(&t.repo, None, &mut |_| {}).unwrap(), "upstream");
+        let head = bare.find_reference(&format!("refs/heads/{}", branch)).unwrap().target();
+        assert_eq!(head, Some(next));
+    }
+}
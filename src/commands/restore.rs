@@ -0,0 +1,70 @@
+#[allow(dead_code)]
+use crate::errors::GitzError;
+use crate::git::Repository;
+
+/// Remove a file's staged changes, keeping the work tree as it is.
+pub fn unstage_file(repo: &Repository, path: &str) -> Result<(), GitzError> {
+    repo.unstage_path(path)
+}
+
+/// Throw away a file's unstaged changes (deletes untracked files).
+pub fn discard_file(repo: &Repository, path: &str) -> Result<(), GitzError> {
+    repo.discard_path(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_unstage_file_resets_to_head() {
+        let t = TestRepo::new();
+        t.write("a.txt", "one\n");
+        t.commit_all("initial");
+        t.write("a.txt", "two\n");
+        t.repo.stage_path("a.txt").unwrap();
+
+        unstage_file(&t.repo, "a.txt").unwrap();
+        assert_eq!(t.index_contents("a.txt").unwrap(), "one\n");
+        assert_eq!(t.read("a.txt"), "two\n");
+    }
+
+    #[test]
+    fn test_unstage_file_on_unborn_head_removes_entry() {
+        let t = TestRepo::new();
+        t.write("a.txt", "one\n");
+        t.repo.stage_path("a.txt").unwrap();
+
+        unstage_file(&t.repo, "a.txt").unwrap();
+        assert_eq!(t.index_contents("a.txt"), None);
+        assert!(t.path("a.txt").exists());
+    }
+
+    #[test]
+    fn test_discard_file_restores_index_version() {
+        let t = TestRepo::new();
+        t.write("a.txt", "one\n");
+        t.write("b.txt", "keep\n");
+        t.commit_all("initial");
+        t.write("a.txt", "two\n");
+        t.repo.stage_path("a.txt").unwrap();
+        t.write("a.txt", "three\n");
+        t.write("b.txt", "changed\n");
+
+        discard_file(&t.repo, "a.txt").unwrap();
+        assert_eq!(t.read("a.txt"), "two\n");
+        assert_eq!(t.read("b.txt"), "changed\n");
+    }
+
+    #[test]
+    fn test_discard_file_deletes_untracked() {
+        let t = TestRepo::new();
+        t.write("base.txt", "x\n");
+        t.commit_all("initial");
+        t.write("scratch.txt", "tmp\n");
+
+        discard_file(&t.repo, "scratch.txt").unwrap();
+        assert!(!t.path("scratch.txt").exists());
+    }
+}
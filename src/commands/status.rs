@@ -0,0 +1,132 @@
+#[allow(dead_code)]
+use crate::commands::format::{write_json, Format};
+use crate::errors::GitzError;
+use crate::git::{ChangeKind, FileChange, FileStatus, Repository};
+use std::io::Write;
+
+fn letter(change: Option<&FileChange>) -> char {
+    match change.map(|c| c.kind) {
+        None => ' ',
+        Some(ChangeKind::Added) => 'A',
+        Some(ChangeKind::Modified) => 'M',
+        Some(ChangeKind::Deleted) => 'D',
+        Some(ChangeKind::Renamed) => 'R',
+        Some(ChangeKind::Copied) => 'C',
+        Some(ChangeKind::Typechange) => 'T',
+        Some(ChangeKind::Untracked) => '?',
+        Some(ChangeKind::Unreadable) => 'X',
+    }
+}
+
+/// The two-letter code of `git status --short`: index, then work tree.
+pub fn short_code(file: &FileStatus) -> String {
+    if let Some(conflict) = file.conflict {
+        return match (conflict.ancestor, conflict.ours, conflict.theirs) {
+            (true, false, false) => "DD",
+            (false, true, false) => "AU",
+            (true, true, false) => "UD",
+            (false, false, true) => "UA",
+            (true, false, true) => "DU",
+            (false, true, true) => "AA",
+            _ => "UU",
+        }
+        .to_string();
+    }
+    if file.worktree.as_ref().is_some_and(|c| c.kind == ChangeKind::Untracked) {
+        return "??".to_string();
+    }
+    format!("{}{}", letter(file.index.as_ref()), letter(file.worktree.as_ref()))
+}
+
+/// Header line of `git status --short --branch`: the branch, its upstream
+/// and how far apart they are.
+pub fn branch_line(repo: &Repository) -> Result<String, GitzError> {
+    let Some(head) = repo.branches()?.into_iter().find(|b| b.is_head) else {
+        return Ok(match repo.current_branch() {
+            Ok(name) if name.starts_with("HEAD") => "## HEAD (no branch)".to_string(),
+            // HEAD names a branch without commits.
+            _ => "## No commits yet".to_string(),
+        });
+    };
+    let mut line = format!("## {}", head.name);
+    if let Some(upstream) = &head.upstream {
+        line.push_str(&format!("...{}", upstream));
+        let mut counts = Vec::new();
+        if head.ahead > 0 {
+            counts.push(format!("ahead {}", head.ahead));
+        }
+        if head.behind > 0 {
+            counts.push(format!("behind {}", head.behind));
+        }
+        if !counts.is_empty() {
+            line.push_str(&format!(" [{}]", counts.join(", ")));
+        }
+    }
+    Ok(line)
+}
+
+/// Print the branch and changed files in the format of
+/// `git status --short --branch`, or the `RepoStatus` as JSON.
+pub fn status(repo: &Repository, format: Format, out: &mut dyn Write) -> Result<(), GitzError> {
+    let status = repo.status()?;
+    if format == Format::Json {
+        return write_json(out, &status);
+    }
+    writeln!(out, "{}", branch_line(repo)?)?;
+    for file in status.files {
+        let path = match file.index.as_ref().and_then(|c| c.renamed_from.as_ref()) {
+            Some(from) => format!("{} -> {}", from, file.path),
+            None => file.path.clone(),
+        };
+        writeln!(out, "{} {}", short_code(&file), path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_short_status_output() {
+        let t = TestRepo::new();
+        t.write("kept.txt", "a\n");
+        t.write("edited.txt", "a\n");
+        t.commit_all("initial");
+        let branch = t.repo.current_branch().unwrap();
+        t.write("edited.txt", "b\n");
+        t.repo.stage_path("edited.txt").unwrap();
+        t.write("edited.txt", "c\n");
+        t.write("new.txt", "n\n");
+        std::fs::remove_file(t.path("kept.txt")).unwrap();
+
+        let mut out = Vec::new();
+        status(&t.repo, Format::Text, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, format!("## {}\nMM edited.txt\n D kept.txt\n?? new.txt\n", branch));
+    }
+
+    #[test]
+    fn test_json_status_schema() {
+        let t = TestRepo::new();
+        t.write("a.txt", "a\n");
+        t.commit_all("initial");
+        t.write("a.txt", "b\n");
+        t.repo.stage_path("a.txt").unwrap();
+        std::fs::rename(t.path("a.txt"), t.path("b.txt")).unwrap();
+
+        let mut out = Vec::new();
+        status(&t.repo, Format::Json, &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json, serde_json::json!({
+            "files": [{
+                "path": "b.txt",
+                "index": { "kind": "modified", "renamed_from": null },
+                "worktree": { "kind": "renamed", "renamed_from": "a.txt" },
+                "conflict": null,
+                "submodule": false,
+            }],
+        }));
+    }
+}
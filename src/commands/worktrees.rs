@@ -0,0 +1,68 @@
+#[allow(dead_code)]
+use crate::commands::format::{write_json, Format};
+use crate::errors::GitzError;
+use crate::git::Repository;
+use std::io::Write;
+
+/// Print the work trees in the style of `git worktree list`, or as a JSON
+/// array of `WorktreeInfo`.
+pub fn worktrees(repo: &Repository, format: Format, out: &mut dyn Write) -> Result<(), GitzError> {
+    let worktrees = repo.worktrees()?;
+    if format == Format::Json {
+        return write_json(out, &worktrees);
+    }
+    let width = worktrees.iter().map(|w| w.path.display().to_string().len()).max().unwrap_or(0);
+    for worktree in &worktrees {
+        let head = worktree.head.map_or("0000000".to_string(), |oid| oid.to_string()[..7].to_string());
+        let mut line = format!(
+            "{:<width$} {} {}",
+            worktree.path.display().to_string(),
+            head,
+            worktree.branch.as_ref().map_or("(detached HEAD)".to_string(), |b| format!("[{}]", b)),
+            width = width
+        );
+        if worktree.locked {
+            line.push_str(" locked");
+        }
+        if worktree.prunable {
+            line.push_str(" prunable");
+        }
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_worktrees_json_schema() {
+        let t = TestRepo::new();
+        t.write("f.txt", "x\n");
+        let head = t.commit_all("initial");
+        t.repo.create_branch("topic", head).unwrap();
+        let dir = TempDir::new().unwrap();
+        t.repo.create_worktree(dir.path().join("topic"), "topic").unwrap();
+
+        let mut out = Vec::new();
+        worktrees(&t.repo, Format::Json, &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json[0]["name"], serde_json::Value::Null);
+        assert_eq!(json[1], serde_json::json!({
+            "name": "topic",
+            "path": json[1]["path"],
+            "branch": "topic",
+            "head": head.to_string(),
+            "locked": false,
+            "prunable": false,
+        }));
+        assert!(json[1]["path"].as_str().unwrap().contains("topic"));
+
+        let mut out = Vec::new();
+        worktrees(&t.repo, Format::Text, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().lines().nth(1).unwrap().ends_with("[topic]"));
+    }
+}
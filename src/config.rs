@@ -18,6 +18,9 @@ pub struct GitConfig {
     pub auto_fetch_interval: u64,
     pub sign_commits: bool,
     pub gpg_key: Option<String>,
+    /// Shell command that tests each bisect step: exit 0 for good, 125 to
+    /// skip, anything else up to 127 for bad.
+    pub bisect_command: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
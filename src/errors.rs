@@ -21,6 +21,9 @@ pub enum GitzError {
     #[error("Invalid branch name: {0}")]
     InvalidBranchName(String),
 
+    #[error("Branch `{0}` is not fully merged")]
+    UnmergedBranch(String),
+
     #[error("Authentication failed")]
     AuthFailed,
 
@@ -36,3 +39,16 @@ pub enum GitzError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+impl GitzError {
+    /// Process exit code for a failed command-line invocation: 2 for bad
+    /// input (as for usage errors), 128 when there is no repository (as
+    /// git does), 1 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            GitzError::InvalidInput(_) | GitzError::InvalidBranchName(_) => 2,
+            GitzError::RepoNotFound(_) => 128,
+            _ => 1,
+        }
+    }
+}
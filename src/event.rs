@@ -10,7 +10,52 @@ pub enum AppEvent {
     
     /// Request a UI refresh.
     Refresh,
+
+    /// Progress line from a running background git operation.
+    Progress(String),
+
+    /// A background git operation finished, with its summary or error message.
+    TaskDone { origin: TaskOrigin, result: Result<String, String> },
     
+    /// The background repository health check finished.
+    DoctorDone(Result<crate::git::DoctorReport, String>),
+
+    /// Open the rebase planner for the given commit and everything above it.
+    OpenRebase(git2::Oid),
+
+    /// A merge stopped on conflicts; show the conflicts view.
+    OpenConflicts,
+
+    /// A bisect has a commit to test; show the bisect view.
+    OpenBisect,
+
+    /// Show the reflog of a ref (`HEAD` or a full ref name).
+    OpenReflog(String),
+
+    /// Browse another repository (a submodule) until `CloseRepository`.
+    OpenRepository(std::path::PathBuf),
+
+    /// Return to the repository the current one was opened from.
+    CloseRepository,
+
+    /// Show the blame of a file, at a commit or at HEAD.
+    OpenBlame { path: String, at: Option<git2::Oid> },
+
+    /// Show every commit that touched a file.
+    OpenFileHistory(String),
+
+    /// Leave a view opened on top of another (blame, file history) for the one it was opened from.
+    Back,
+
     /// Application should quit.
     Quit,
 }
+
+/// The view that started a background operation and reports its outcome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskOrigin {
+    Remotes,
+    Tags,
+    Submodules,
+    Bisect,
+}
@@ -0,0 +1,484 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::{BranchKind, Repository};
+use git2::{BranchType, Oid};
+use std::collections::HashMap;
+
+/// How a tested commit behaves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectMark {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectMark {
+    /// The word `git bisect` uses for the mark.
+    pub fn label(&self) -> &'static str {
+        match self {
+            BisectMark::Good => "good",
+            BisectMark::Bad => "bad",
+            BisectMark::Skip => "skip",
+        }
+    }
+
+    /// Verdict of a `bisect run` test command from its exit code, like
+    /// `git bisect run`: 0 is good, 125 skips, 1-127 is bad. `None` (other
+    /// codes, or killed by a signal) stops the run.
+    pub fn from_exit_code(code: Option<i32>) -> Option<Self> {
+        match code? {
+            0 => Some(BisectMark::Good),
+            125 => Some(BisectMark::Skip),
+            1..=127 => Some(BisectMark::Bad),
+            _ => None,
+        }
+    }
+}
+
+/// A bisect in progress, as `git bisect` keeps it in the git directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BisectState {
+    /// Branch (or commit, when HEAD was detached) checked out before the
+    /// bisect started; resetting goes back to it.
+    pub start: String,
+    pub bad: Option<Oid>,
+    pub good: Vec<Oid>,
+    pub skipped: Vec<Oid>,
+}
+
+/// Where a bisect stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BisectStep {
+    /// Waiting for a bad commit and at least one good one.
+    NeedMarks,
+    /// `commit` is to be tested; `remaining` commits may still be the first
+    /// bad one.
+    Testing { commit: Oid, remaining: usize },
+    /// The first bad commit.
+    Found(Oid),
+    /// Only skipped commits are left; the first bad commit is one of these.
+    OnlySkipped(Vec<Oid>),
+}
+
+impl BisectStep {
+    /// Roughly how many more commits need testing, counting the current one.
+    pub fn steps_left(&self) -> u32 {
+        match self {
+            BisectStep::Testing { remaining, .. } => usize::BITS - remaining.saturating_sub(1).leading_zeros(),
+            _ => 0,
+        }
+    }
+
+    /// One-line summary for the status bar.
+    pub fn describe(&self) -> String {
+        match self {
+            BisectStep::NeedMarks => "Bisecting: mark a bad and a good commit".to_string(),
+            BisectStep::Testing { commit, remaining } => format!(
+                "Bisecting: testing {}, {} commits left (roughly {} steps)",
+                &commit.to_string()[..7],
+                remaining,
+                self.steps_left()
+            ),
+            BisectStep::Found(commit) => format!("{} is the first bad commit", &commit.to_string()[..7]),
+            BisectStep::OnlySkipped(commits) => {
+                format!("Only skipped commits left; the first bad commit is one of {}", commits.len())
+            }
+        }
+    }
+}
+
+impl Repository {
+    /// Files `git bisect` keeps in the git directory while bisecting.
+    const BISECT_FILES: [&'static str; 8] = [
+        "BISECT_START",
+        "BISECT_TERMS",
+        "BISECT_NAMES",
+        "BISECT_LOG",
+        "BISECT_EXPECTED_REV",
+        "BISECT_ANCESTORS_OK",
+        "BISECT_RUN",
+        "BISECT_FIRST_PARENT",
+    ];
+
+    /// Commits under `refs/bisect/` whose names start with `prefix`.
+    fn bisect_refs(&self, prefix: &str) -> Result<Vec<Oid>, GitzError> {
+        let mut oids = Vec::new();
+        for reference in self.inner.references_glob(&format!("refs/bisect/{}*", prefix))? {
+            if let Some(oid) = reference?.target() {
+                oids.push(oid);
+            }
+        }
+        Ok(oids)
+    }
+
+    /// The bisect in progress, if any; also one started with `git bisect`.
+    pub fn bisect_state(&self) -> Result<Option<BisectState>, GitzError> {
+        let start = match std::fs::read_to_string(self.inner.path().join("BISECT_START")) {
+            Ok(start) => start.trim().to_string(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if let Ok(terms) = std::fs::read_to_string(self.inner.path().join("BISECT_TERMS")) {
+            if terms.split_whitespace().ne(["bad", "good"]) {
+                return Err(GitzError::InvalidInput(format!(
+                    "bisecting with custom terms ({}) is not supported",
+                    terms.split_whitespace().collect::<Vec<_>>().join("/")
+                )));
+            }
+        }
+        let bad = self.inner.find_reference("refs/bisect/bad").ok().and_then(|r| r.target());
+        Ok(Some(BisectState { start, bad, good: self.bisect_refs("good-")?, skipped: self.bisect_refs("skip-")? }))
+    }
+
+    fn require_bisect(&self) -> Result<BisectState, GitzError> {
+        self.bisect_state()?
+            .ok_or_else(|| GitzError::InvalidInput("No bisect in progress".into()))
+    }
+
+    fn append_bisect_log(&self, text: &str) -> Result<(), GitzError> {
+        use std::io::Write;
+        let mut log = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.inner.path().join("BISECT_LOG"))?;
+        log.write_all(text.as_bytes())?;
+        Ok(())
+    }
+
+    /// Start bisecting from the current checkout, which `bisect_reset`
+    /// returns to.
+    pub fn bisect_start(&self) -> Result<(), GitzError> {
+        self.ensure_idle()?;
+        if self.has_tracked_changes()? {
+            return Err(GitzError::InvalidInput("Commit or stash your changes before bisecting".into()));
+        }
+        let start = match self.head_branch_name() {
+            Some(branch) => branch,
+            None => self
+                .head_oid()
+                .ok_or_else(|| GitzError::InvalidInput("Nothing to bisect: no commits yet".into()))?
+                .to_string(),
+        };
+        let gitdir = self.inner.path();
+        std::fs::write(gitdir.join("BISECT_START"), format!("{}\n", start))?;
+        std::fs::write(gitdir.join("BISECT_TERMS"), "bad\ngood\n")?;
+        std::fs::write(gitdir.join("BISECT_NAMES"), "\n")?;
+        std::fs::write(gitdir.join("BISECT_LOG"), "git bisect start\n")?;
+        Ok(())
+    }
+
+    /// Record how `commit` behaves and check out the next commit to test.
+    pub fn bisect_mark(&self, commit: Oid, mark: BisectMark) -> Result<BisectStep, GitzError> {
+        self.require_bisect()?;
+        let refname = match mark {
+            BisectMark::Bad => "refs/bisect/bad".to_string(),
+            _ => format!("refs/bisect/{}-{}", mark.label(), commit),
+        };
+        let summary = self.inner.find_commit(commit)?.summary().unwrap_or_default().to_string();
+        self.inner.reference(&refname, commit, true, &format!("bisect: {}", mark.label()))?;
+        self.append_bisect_log(&format!(
+            "# {}: [{}] {}\ngit bisect {} {}\n",
+            mark.label(),
+            commit,
+            summary,
+            mark.label(),
+            commit
+        ))?;
+        self.bisect_next()
+    }
+
+    /// Check out the commit to test next, or record the first bad commit
+    /// once it is known.
+    pub fn bisect_next(&self) -> Result<BisectStep, GitzError> {
+        let state = self.require_bisect()?;
+        let step = self.bisect_step(&state)?;
+        match &step {
+            BisectStep::Testing { commit, .. } => {
+                if self.head_oid() != Some(*commit) {
+                    self.checkout_detached(*commit)?;
+                }
+                std::fs::write(self.inner.path().join("BISECT_EXPECTED_REV"), format!("{}\n", commit))?;
+            }
+            BisectStep::Found(commit) => {
+                let summary = self.inner.find_commit(*commit)?.summary().unwrap_or_default().to_string();
+                self.append_bisect_log(&format!("# first bad commit: [{}] {}\n", commit, summary))?;
+            }
+            BisectStep::NeedMarks | BisectStep::OnlySkipped(_) => {}
+        }
+        Ok(step)
+    }
+
+    /// Where `state` stands: the commit that best halves the commits that
+    /// may still be the first bad one (reachable from the bad commit but
+    /// from no good one), or the result.
+    pub fn bisect_step(&self, state: &BisectState) -> Result<BisectStep, GitzError> {
+        let Some(bad) = state.bad else {
+            return Ok(BisectStep::NeedMarks);
+        };
+        if state.good.is_empty() {
+            return Ok(BisectStep::NeedMarks);
+        }
+        let mut walk = self.inner.revwalk()?;
+        walk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+        walk.push(bad)?;
+        for good in &state.good {
+            walk.hide(*good)?;
+        }
+        let candidates = walk.collect::<Result<Vec<Oid>, _>>()?;
+        if candidates.len() <= 1 {
+            return Ok(BisectStep::Found(bad));
+        }
+
+        // Weigh each candidate by how many candidates it reaches, itself
+        // included, parents before children. As in `git bisect`, a commit
+        // with a single candidate parent weighs one more than that parent;
+        // only merges need a walk of their own.
+        let total = candidates.len();
+        let index: HashMap<Oid, usize> = candidates.iter().enumerate().map(|(i, oid)| (*oid, i)).collect();
+        let mut parents = Vec::with_capacity(total);
+        for oid in &candidates {
+            let commit = self.inner.find_commit(*oid)?;
+            parents.push(commit.parent_ids().filter_map(|p| index.get(&p).copied()).collect::<Vec<usize>>());
+        }
+        let mut weight = vec![0; total];
+        // Walk marks: a candidate was seen by the merge walk with this stamp.
+        let mut seen = vec![0u32; total];
+        let mut stamp = 0;
+        let mut best: Option<(usize, usize)> = None;
+        for i in (0..total).rev() {
+            weight[i] = match parents[i].as_slice() {
+                [] => 1,
+                [p] => weight[*p] + 1,
+                _ => {
+                    stamp += 1;
+                    let mut stack = vec![i];
+                    seen[i] = stamp;
+                    let mut count = 0;
+                    while let Some(c) = stack.pop() {
+                        count += 1;
+                        for &p in &parents[c] {
+                            if seen[p] != stamp {
+                                seen[p] = stamp;
+                                stack.push(p);
+                            }
+                        }
+                    }
+                    count
+                }
+            };
+            if candidates[i] == bad || state.skipped.contains(&candidates[i]) {
+                continue;
+            }
+            let score = weight[i].min(total - weight[i]);
+            // Ties go to the newer candidate, like `git bisect`.
+            if best.is_none_or(|(_, b)| score >= b) {
+                best = Some((i, score));
+            }
+            // Nothing can beat splitting the candidates in half.
+            if score == total / 2 {
+                break;
+            }
+        }
+        let best = best.map(|(i, _)| (i, &candidates[i]));
+        Ok(match best {
+            Some((_, commit)) => BisectStep::Testing { commit: *commit, remaining: total },
+            None => BisectStep::OnlySkipped(candidates),
+        })
+    }
+
+    /// Test each commit with the shell `command` and mark it by its exit
+    /// code, like `git bisect run`, until the bisect is over. `tested` is
+    /// told every verdict.
+    pub fn bisect_run(&self, command: &str, tested: &mut dyn FnMut(Oid, BisectMark)) -> Result<BisectStep, GitzError> {
+        let workdir = self
+            .inner
+            .workdir()
+            .ok_or_else(|| GitzError::InvalidInput("Cannot run tests in a bare repository".into()))?
+            .to_path_buf();
+        let mut step = self.bisect_next()?;
+        while let BisectStep::Testing { commit, .. } = step {
+            let status = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .current_dir(&workdir)
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status()?;
+            let mark = BisectMark::from_exit_code(status.code()).ok_or_else(|| {
+                GitzError::GitOperationFailed(format!(
+                    "bisect run stopped at {}: `{}` exited with {}",
+                    &commit.to_string()[..7],
+                    command,
+                    status
+                ))
+            })?;
+            tested(commit, mark);
+            step = self.bisect_mark(commit, mark)?;
+        }
+        Ok(step)
+    }
+
+    /// End the bisect: check out what was checked out before it started
+    /// and remove its state.
+    pub fn bisect_reset(&self) -> Result<(), GitzError> {
+        let state = self.require_bisect()?;
+        if self.inner.find_branch(&state.start, BranchType::Local).is_ok() {
+            self.checkout_branch(&state.start, BranchKind::Local)?;
+        } else if let Ok(oid) = Oid::from_str(&state.start) {
+            self.checkout_detached(oid)?;
+        }
+        for file in Self::BISECT_FILES {
+            match std::fs::remove_file(self.inner.path().join(file)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        for reference in self.inner.references_glob("refs/bisect/*")? {
+            reference?.delete()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    /// Eight commits; the bug (`bad` in `v.txt`) arrives with the sixth.
+    fn history(t: &TestRepo) -> Vec<Oid> {
+        (1..=8)
+            .map(|i| {
+                t.write("v.txt", if i >= 6 { "bad\n" } else { "good\n" });
+                t.write("n.txt", &format!("{}\n", i));
+                t.commit_all(&format!("commit {}", i))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_steps_left_and_exit_codes() {
+        let step = |remaining| BisectStep::Testing { commit: Oid::zero(), remaining };
+        assert_eq!([2, 3, 4, 8, 9].map(|r| step(r).steps_left()), [1, 2, 2, 3, 4]);
+        assert_eq!(BisectMark::from_exit_code(Some(0)), Some(BisectMark::Good));
+        assert_eq!(BisectMark::from_exit_code(Some(125)), Some(BisectMark::Skip));
+        assert_eq!(BisectMark::from_exit_code(Some(1)), Some(BisectMark::Bad));
+        assert_eq!(BisectMark::from_exit_code(Some(128)), None);
+        assert_eq!(BisectMark::from_exit_code(None), None);
+    }
+
+    #[test]
+    fn test_manual_bisect_finds_first_bad_commit() {
+        let t = TestRepo::new();
+        let commits = history(&t);
+        let branch = t.repo.current_branch().unwrap();
+
+        t.repo.bisect_start().unwrap();
+        assert_eq!(t.repo.bisect_mark(commits[7], BisectMark::Bad).unwrap(), BisectStep::NeedMarks);
+        let mut step = t.repo.bisect_mark(commits[0], BisectMark::Good).unwrap();
+        let mut tested = 0;
+        while let BisectStep::Testing { commit, .. } = step {
+            assert_eq!(t.repo.head_oid(), Some(commit));
+            let mark = if t.read("v.txt") == "bad\n" { BisectMark::Bad } else { BisectMark::Good };
+            step = t.repo.bisect_mark(commit, mark).unwrap();
+            tested += 1;
+        }
+        assert_eq!(step, BisectStep::Found(commits[5]));
+        assert!(tested <= 3);
+
+        // The state is kept where `git bisect` looks for it.
+        let gitdir = t.repo.git_dir();
+        assert_eq!(std::fs::read_to_string(gitdir.join("BISECT_START")).unwrap().trim(), branch);
+        assert!(t.git().find_reference("refs/bisect/bad").is_ok());
+        assert!(t.git().find_reference(&format!("refs/bisect/good-{}", commits[0])).is_ok());
+        let state = t.repo.bisect_state().unwrap().unwrap();
+        assert_eq!((state.start.as_str(), state.bad), (branch.as_str(), Some(commits[5])));
+        assert!(state.good.contains(&commits[4]));
+
+        t.repo.bisect_reset().unwrap();
+        assert!(t.repo.bisect_state().unwrap().is_none());
+        assert!(!gitdir.join("BISECT_LOG").exists());
+        assert!(t.git().find_reference("refs/bisect/bad").is_err());
+        assert_eq!(t.repo.current_branch().unwrap(), branch);
+        assert_eq!(t.repo.head_oid(), Some(commits[7]));
+    }
+
+    #[test]
+    fn test_run_with_test_command() {
+        let t = TestRepo::new();
+        let commits = history(&t);
+        t.repo.bisect_start().unwrap();
+        t.repo.bisect_mark(commits[7], BisectMark::Bad).unwrap();
+        t.repo.bisect_mark(commits[0], BisectMark::Good).unwrap();
+
+        let step = t.repo.bisect_run("grep -q good v.txt", &mut |_, _| {}).unwrap();
+        assert_eq!(step, BisectStep::Found(commits[5]));
+        let log = std::fs::read_to_string(t.repo.git_dir().join("BISECT_LOG")).unwrap();
+        assert!(log.contains(&format!("# first bad commit: [{}] commit 6", commits[5])));
+    }
+
+    /// Commit `parents` with the tree of the first commit, without touching
+    /// the work tree.
+    fn raw_commit(t: &TestRepo, parents: &[Oid], message: &str) -> Oid {
+        let git = t.git();
+        let tree = git.find_commit(parents[0]).unwrap().tree().unwrap();
+        let parents: Vec<_> = parents.iter().map(|p| git.find_commit(*p).unwrap()).collect();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        git.commit(None, &sig, &sig, message, &tree, &parents.iter().collect::<Vec<_>>()).unwrap()
+    }
+
+    fn step(t: &TestRepo, bad: Oid, good: &[Oid]) -> BisectStep {
+        let state = BisectState { start: "main".into(), bad: Some(bad), good: good.to_vec(), skipped: Vec::new() };
+        t.repo.bisect_step(&state).unwrap()
+    }
+
+    #[test]
+    fn test_step_halves_long_linear_history() {
+        let t = TestRepo::new();
+        t.write("f.txt", "x\n");
+        let mut commits = vec![t.commit_all("commit 0")];
+        for i in 1..1200 {
+            let parent = commits[i - 1];
+            commits.push(raw_commit(&t, &[parent], &format!("commit {}", i)));
+        }
+        // 1199 candidates; commit 599 reaches 599 of them.
+        assert_eq!(step(&t, commits[1199], &[commits[0]]), BisectStep::Testing { commit: commits[599], remaining: 1199 });
+    }
+
+    #[test]
+    fn test_step_counts_merges_once() {
+        let t = TestRepo::new();
+        t.write("f.txt", "x\n");
+        let base = t.commit_all("base");
+        let mut a = vec![base];
+        for i in 1..=6 {
+            a.push(raw_commit(&t, &[a[i - 1]], &format!("a{}", i)));
+        }
+        let b1 = raw_commit(&t, &[base], "b1");
+        let b2 = raw_commit(&t, &[b1], "b2");
+        let merge = raw_commit(&t, &[a[6], b2], "merge");
+        let mut tip = merge;
+        for i in 1..=3 {
+            tip = raw_commit(&t, &[tip], &format!("d{}", i));
+        }
+
+        // 12 candidates: a6 reaches exactly half of them.
+        assert_eq!(step(&t, tip, &[base]), BisectStep::Testing { commit: a[6], remaining: 12 });
+        // With a6 good the merge reaches b1, b2 and itself: half of six.
+        assert_eq!(step(&t, tip, &[base, a[6]]), BisectStep::Testing { commit: merge, remaining: 6 });
+    }
+
+    #[test]
+    fn test_only_skipped_commits_left() {
+        let t = TestRepo::new();
+        let commits = history(&t);
+        t.repo.bisect_start().unwrap();
+        t.repo.bisect_mark(commits[6], BisectMark::Bad).unwrap();
+        t.repo.bisect_mark(commits[3], BisectMark::Good).unwrap();
+        t.repo.bisect_mark(commits[4], BisectMark::Skip).unwrap();
+        let step = t.repo.bisect_mark(commits[5], BisectMark::Skip).unwrap();
+        assert_eq!(step, BisectStep::OnlySkipped(vec![commits[6], commits[5], commits[4]]));
+    }
+}
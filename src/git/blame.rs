@@ -0,0 +1,144 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use git2::Oid;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One line of a blamed file and the commit that last changed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    pub oid: Oid,
+    pub author: String,
+    pub time: i64,
+    pub summary: String,
+    /// Path of the file in the blamed commit (differs after a rename).
+    pub orig_path: String,
+    /// 1-based line number in the blamed commit.
+    pub orig_line: usize,
+    pub content: String,
+}
+
+impl BlameLine {
+    pub fn short_id(&self) -> String {
+        self.oid.to_string()[..7].to_string()
+    }
+}
+
+/// A file annotated line by line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blame {
+    pub path: String,
+    /// The commit the file was blamed at; `None` for HEAD.
+    pub at: Option<Oid>,
+    pub lines: Vec<BlameLine>,
+}
+
+impl Blame {
+    /// How recent `line` is compared with the rest of the file, from 0
+    /// (oldest) to 3 (newest), for coloring.
+    pub fn recency(&self, line: &BlameLine) -> u8 {
+        let oldest = self.lines.iter().map(|l| l.time).min().unwrap_or(line.time);
+        let newest = self.lines.iter().map(|l| l.time).max().unwrap_or(line.time);
+        if newest == oldest {
+            return 3;
+        }
+        (((line.time - oldest) * 4 / (newest - oldest + 1)).clamp(0, 3)) as u8
+    }
+}
+
+impl Repository {
+    /// Annotate each line of `path` with the commit that last changed it,
+    /// as of `at` (HEAD when `None`).
+    pub fn blame(&self, path: &str, at: Option<Oid>) -> Result<Blame, GitzError> {
+        let commit = match at {
+            Some(oid) => self.inner.find_commit(oid)?,
+            None => self.inner.head()?.peel_to_commit()?,
+        };
+        let entry = commit
+            .tree()?
+            .get_path(Path::new(path))
+            .map_err(|_| GitzError::InvalidInput(format!("{} does not exist in {}", path, &commit.id().to_string()[..7])))?;
+        let blob = self.inner.find_blob(entry.id())?;
+        if blob.is_binary() {
+            return Err(GitzError::InvalidInput(format!("{} is a binary file", path)));
+        }
+        let content = String::from_utf8_lossy(blob.content()).into_owned();
+
+        let mut opts = git2::BlameOptions::new();
+        opts.newest_commit(commit.id());
+        let blame = self.inner.blame_file(Path::new(path), Some(&mut opts))?;
+        let mut summaries: HashMap<Oid, String> = HashMap::new();
+        let mut lines = Vec::new();
+        for (idx, text) in content.lines().enumerate() {
+            let Some(hunk) = blame.get_line(idx + 1) else { continue };
+            let oid = hunk.final_commit_id();
+            if let std::collections::hash_map::Entry::Vacant(entry) = summaries.entry(oid) {
+                entry.insert(self.inner.find_commit(oid)?.summary().unwrap_or("").to_string());
+            }
+            let signature = hunk.final_signature();
+            lines.push(BlameLine {
+                oid,
+                author: signature.name().unwrap_or("unknown").to_string(),
+                time: signature.when().seconds(),
+                summary: summaries[&oid].clone(),
+                orig_path: hunk.path().map_or_else(|| path.to_string(), |p| p.to_string_lossy().into_owned()),
+                orig_line: hunk.orig_start_line() + (idx + 1 - hunk.final_start_line()),
+                content: text.to_string(),
+            });
+        }
+        Ok(Blame { path: path.to_string(), at: at.map(|_| commit.id()), lines })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::GitzError;
+    use crate::git::testing::TestRepo;
+
+    fn line(time: i64) -> BlameLine {
+        BlameLine {
+            oid: Oid::zero(),
+            author: "a".into(),
+            time,
+            summary: String::new(),
+            orig_path: "f".into(),
+            orig_line: 1,
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_recency_buckets() {
+        let blame = Blame { path: "f".into(), at: None, lines: vec![line(0), line(50), line(99)] };
+        let ranks: Vec<u8> = blame.lines.iter().map(|l| blame.recency(l)).collect();
+        assert_eq!(ranks, [0, 2, 3]);
+        let single = Blame { lines: vec![line(5)], ..blame };
+        assert_eq!(single.recency(&single.lines[0]), 3);
+    }
+
+    #[test]
+    fn test_blame_and_reblame_at_parent() {
+        let t = TestRepo::new();
+        t.write("f.txt", "one\ntwo\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "one\nTWO\nthree\n");
+        let second = t.commit_all("second");
+
+        let blame = t.repo.blame("f.txt", None).unwrap();
+        let owners: Vec<(Oid, &str)> = blame.lines.iter().map(|l| (l.oid, l.content.as_str())).collect();
+        assert_eq!(owners, [(first, "one"), (second, "TWO"), (second, "three")]);
+        assert_eq!(blame.lines[1].summary, "second");
+        assert_eq!(blame.lines[1].author, "Test User");
+
+        // Digging past `second` shows the line it replaced.
+        let parent = t.repo.find_commit(blame.lines[1].oid).unwrap().parents[0];
+        let older = t.repo.blame("f.txt", Some(parent)).unwrap();
+        assert_eq!(older.at, Some(first));
+        assert_eq!(older.lines.iter().map(|l| l.content.as_str()).collect::<Vec<_>>(), ["one", "two"]);
+
+        assert!(matches!(t.repo.blame("missing.txt", None), Err(GitzError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,265 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use git2::build::CheckoutBuilder;
+use git2::{BranchType, Oid};
+use serde::Serialize;
+
+/// Whether a branch lives under `refs/heads` or `refs/remotes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BranchKind {
+    Local,
+    Remote,
+}
+
+/// A local or remote-tracking branch with its tip and tracking state.
+#[derive(Debug, Clone, Serialize)]
+pub struct BranchInfo {
+    /// Short name, e.g. `main` or `origin/main`.
+    pub name: String,
+    pub kind: BranchKind,
+    pub is_head: bool,
+    /// Short name of the upstream branch, for local branches that track one.
+    pub upstream: Option<String>,
+    /// Commits on this branch that are not on its upstream.
+    pub ahead: usize,
+    /// Commits on the upstream that are not on this branch.
+    pub behind: usize,
+    #[serde(with = "crate::git::serde_oid")]
+    pub target: Oid,
+    pub summary: String,
+    #[serde(with = "crate::git::serde_time")]
+    pub time: i64, // seconds since epoch
+}
+
+impl BranchInfo {
+    /// Ahead/behind marker such as `↑2 ↓1`, empty when in sync or untracked.
+    pub fn tracking_label(&self) -> String {
+        let mut parts = Vec::new();
+        if self.ahead > 0 {
+            parts.push(format!("↑{}", self.ahead));
+        }
+        if self.behind > 0 {
+            parts.push(format!("↓{}", self.behind));
+        }
+        parts.join(" ")
+    }
+}
+
+impl Repository {
+    /// Local branches followed by remote-tracking branches.
+    pub fn branches(&self) -> Result<Vec<BranchInfo>, GitzError> {
+        let mut result = Vec::new();
+        for kind in [BranchType::Local, BranchType::Remote] {
+            let mut batch = Vec::new();
+            for item in self.inner.branches(Some(kind))? {
+                let (branch, _) = item?;
+                let reference = branch.get();
+                // Skip symbolic refs such as `origin/HEAD`.
+                if reference.symbolic_target().is_some() {
+                    continue;
+                }
+                let Some(name) = branch.name()?.map(str::to_string) else {
+                    continue;
+                };
+                let commit = reference.peel_to_commit()?;
+                let mut info = BranchInfo {
+                    name,
+                    kind: match kind {
+                        BranchType::Local => BranchKind::Local,
+                        BranchType::Remote => BranchKind::Remote,
+                    },
+                    is_head: branch.is_head(),
+                    upstream: None,
+                    ahead: 0,
+                    behind: 0,
+                    target: commit.id(),
+                    summary: commit.summary().unwrap_or("").to_string(),
+                    time: commit.time().seconds(),
+                };
+                if let Ok(upstream) = branch.upstream() {
+                    info.upstream = upstream.name()?.map(str::to_string);
+                    if let Some(up_oid) = upstream.get().target() {
+                        let (ahead, behind) = self.inner.graph_ahead_behind(info.target, up_oid)?;
+                        info.ahead = ahead;
+                        info.behind = behind;
+                    }
+                }
+                batch.push(info);
+            }
+            batch.sort_by(|a, b| a.name.cmp(&b.name));
+            result.extend(batch);
+        }
+        Ok(result)
+    }
+
+    fn validate_branch_name(name: &str) -> Result<(), GitzError> {
+        if git2::Branch::name_is_valid(name)? {
+            Ok(())
+        } else {
+            Err(GitzError::InvalidBranchName(name.to_string()))
+        }
+    }
+
+    /// Create a local branch pointing at `target`.
+    pub fn create_branch(&self, name: &str, target: Oid) -> Result<(), GitzError> {
+        Self::validate_branch_name(name)?;
+        let commit = self.inner.find_commit(target)?;
+        self.inner.branch(name, &commit, false)?;
+        Ok(())
+    }
+
+    /// Check out a branch. A remote-tracking branch is checked out through a
+    /// local branch of the same name that tracks it, created if necessary.
+    pub fn checkout_branch(&self, name: &str, kind: BranchKind) -> Result<(), GitzError> {
+        let local_name = match kind {
+            BranchKind::Local => name.to_string(),
+            BranchKind::Remote => {
+                let remote = self.inner.find_branch(name, BranchType::Remote)?;
+                let local = name.split_once('/').map(|(_, rest)| rest).unwrap_or(name).to_string();
+                if self.inner.find_branch(&local, BranchType::Local).is_err() {
+                    let commit = remote.get().peel_to_commit()?;
+                    let mut branch = self.inner.branch(&local, &commit, false)?;
+                    branch.set_upstream(Some(name))?;
+                }
+                local
+            }
+        };
+        let branch = self.inner.find_branch(&local_name, BranchType::Local)?;
+        let refname = branch
+            .get()
+            .name()
+            .ok_or_else(|| GitzError::InvalidBranchName(local_name.clone()))?
+            .to_string();
+        let tree = branch.get().peel_to_tree()?;
+        let mut checkout = CheckoutBuilder::new();
+        checkout.safe();
+        self.inner.checkout_tree(tree.as_object(), Some(&mut checkout))?;
+        self.inner.set_head(&refname)?;
+        Ok(())
+    }
+
+    /// Rename a local branch.
+    pub fn rename_branch(&self, old: &str, new: &str) -> Result<(), GitzError> {
+        Self::validate_branch_name(new)?;
+        let mut branch = self.inner.find_branch(old, BranchType::Local)?;
+        branch.rename(new, false)?;
+        Ok(())
+    }
+
+    /// Delete a branch. Without `force`, a local branch must be merged into
+    /// its upstream or into HEAD.
+    pub fn delete_branch(&self, name: &str, kind: BranchKind, force: bool) -> Result<(), GitzError> {
+        let branch_type = match kind {
+            BranchKind::Local => BranchType::Local,
+            BranchKind::Remote => BranchType::Remote,
+        };
+        let mut branch = self.inner.find_branch(name, branch_type)?;
+        if branch.is_head() {
+            return Err(GitzError::GitOperationFailed(format!(
+                "Cannot delete the checked out branch `{}`",
+                name
+            )));
+        }
+        if !force && kind == BranchKind::Local {
+            let tip = branch.get().peel_to_commit()?.id();
+            let base = match branch.upstream() {
+                Ok(upstream) => upstream.get().target(),
+                Err(_) => self.inner.head().ok().and_then(|h| h.target()),
+            };
+            let merged = match base {
+                Some(base) => base == tip || self.inner.graph_descendant_of(base, tip)?,
+                None => false,
+            };
+            if !merged {
+                return Err(GitzError::UnmergedBranch(name.to_string()));
+            }
+        }
+        branch.delete()?;
+        Ok(())
+    }
+
+    /// Set (or with `None`, unset) the upstream of a local branch.
+    pub fn set_upstream(&self, name: &str, upstream: Option<&str>) -> Result<(), GitzError> {
+        let mut branch = self.inner.find_branch(name, BranchType::Local)?;
+        branch.set_upstream(upstream)?;
+        Ok(())
+    }
+
+    /// Detach HEAD at `oid`, keeping local changes that don't conflict.
+    pub fn checkout_detached(&self, oid: Oid) -> Result<(), GitzError> {
+        let commit = self.inner.find_commit(oid)?;
+        self.inner.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+        self.inner.set_head_detached(oid)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::GitzError;
+    use crate::git::testing::TestRepo;
+
+    fn repo_with_feature() -> (TestRepo, Oid) {
+        let t = TestRepo::new();
+        t.write("a.txt", "a\n");
+        let base = t.commit_all("base");
+        t.repo.create_branch("feature", base).unwrap();
+        t.repo.checkout_branch("feature", BranchKind::Local).unwrap();
+        t.write("a.txt", "feature\n");
+        t.commit_all("feature work");
+        (t, base)
+    }
+
+    #[test]
+    fn test_branches_list_head_and_summary() {
+        let (t, _) = repo_with_feature();
+        let branches = t.repo.branches().unwrap();
+        let feature = branches.iter().find(|b| b.name == "feature").unwrap();
+        assert!(feature.is_head);
+        assert_eq!(feature.kind, BranchKind::Local);
+        assert_eq!(feature.summary, "feature work");
+        assert_eq!(t.repo.current_branch().unwrap(), "feature");
+    }
+
+    #[test]
+    fn test_invalid_branch_name_is_rejected() {
+        let (t, base) = repo_with_feature();
+        let err = t.repo.create_branch("bad..name", base).unwrap_err();
+        assert!(matches!(err, GitzError::InvalidBranchName(n) if n == "bad..name"));
+        let err = t.repo.rename_branch("feature", "no spaces allowed").unwrap_err();
+        assert!(matches!(err, GitzError::InvalidBranchName(_)));
+    }
+
+    #[test]
+    fn test_delete_refuses_unmerged_branch() {
+        let (t, _) = repo_with_feature();
+        let default = t.repo.branches().unwrap().into_iter().find(|b| !b.is_head).unwrap().name;
+        t.repo.checkout_branch(&default, BranchKind::Local).unwrap();
+
+        let err = t.repo.delete_branch("feature", BranchKind::Local, false).unwrap_err();
+        assert!(matches!(err, GitzError::UnmergedBranch(n) if n == "feature"));
+        t.repo.delete_branch("feature", BranchKind::Local, true).unwrap();
+        assert!(t.repo.branches().unwrap().iter().all(|b| b.name != "feature"));
+    }
+
+    #[test]
+    fn test_upstream_ahead_behind() {
+        let (t, base) = repo_with_feature();
+        t.repo.create_branch("tracking", base).unwrap();
+        t.repo.set_upstream("tracking", Some("feature")).unwrap();
+
+        let branches = t.repo.branches().unwrap();
+        let tracking = branches.iter().find(|b| b.name == "tracking").unwrap();
+        assert_eq!(tracking.upstream.as_deref(), Some("feature"));
+        assert_eq!((tracking.ahead, tracking.behind), (0, 1));
+        assert_eq!(tracking.tracking_label(), "↓1");
+
+        t.repo.set_upstream("tracking", None).unwrap();
+        let branches = t.repo.branches().unwrap();
+        assert_eq!(branches.iter().find(|b| b.name == "tracking").unwrap().upstream, None);
+    }
+}
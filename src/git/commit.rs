@@ -1,11 +1,461 @@
 #[allow(dead_code)]
-use git2::Oid;
+use crate::errors::GitzError;
+use crate::git::diff::collect_file_diffs;
+use crate::git::{FileDiff, Repository};
+use git2::{DiffOptions, Oid};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
 /// Minimal commit information used by the UI.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CommitInfo {
+    #[serde(with = "crate::git::serde_oid")]
     pub oid: Oid,
     pub message: String,
     pub author: String,
+    pub email: String,
+    #[serde(with = "crate::git::serde_time")]
     pub time: i64, // seconds since epoch
+    #[serde(with = "crate::git::serde_oid::list")]
+    pub parents: Vec<Oid>,
+}
+
+impl CommitInfo {
+    pub(crate) fn from_commit(commit: &git2::Commit) -> Self {
+        let author = commit.author();
+        Self {
+            oid: commit.id(),
+            message: commit.message().unwrap_or("<no message>").to_string(),
+            author: author.name().unwrap_or("<unknown>").to_string(),
+            email: author.email().unwrap_or("").to_string(),
+            time: commit.time().seconds(),
+            parents: commit.parent_ids().collect(),
+        }
+    }
+
+    /// Abbreviated object id.
+    pub fn short_id(&self) -> String {
+        self.oid.to_string()[..7].to_string()
+    }
+
+    /// First line of the message.
+    pub fn summary(&self) -> &str {
+        self.message.lines().next().unwrap_or("")
+    }
+
+    pub fn is_merge(&self) -> bool {
+        self.parents.len() > 1
+    }
+}
+
+/// Where a paged log stopped: the commits reached but not returned yet.
+/// A sorted libgit2 revwalk walks the whole history before yielding its
+/// first commit, so the cursor does its own walk and only goes as deep as
+/// the pages asked for. Order is `git log`'s default, newest commit first.
+#[derive(Debug, Clone, Default)]
+pub struct LogCursor {
+    /// By commit time, then in the order the commits were reached.
+    queue: BinaryHeap<(i64, Reverse<usize>, Oid)>,
+    /// Every commit queued so far, so shared history is returned once.
+    seen: HashSet<Oid>,
+}
+
+impl LogCursor {
+    /// Whether every commit of the walk has been returned.
+    pub fn is_done(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    fn push(&mut self, commit: &git2::Commit) {
+        if self.seen.insert(commit.id()) {
+            self.queue.push((commit.time().seconds(), Reverse(self.seen.len()), commit.id()));
+        }
+    }
+}
+
+/// Kind of ref pointing at a commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RefKind {
+    Head,
+    LocalBranch,
+    RemoteBranch,
+    Tag,
+}
+
+/// A ref label shown next to a commit in the log.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Decoration {
+    pub kind: RefKind,
+    pub name: String,
+}
+
+impl Decoration {
+    pub fn new(kind: RefKind, name: impl Into<String>) -> Self {
+        Self { kind, name: name.into() }
+    }
+}
+
+impl Repository {
+    /// Replace HEAD with a commit of the given message (or HEAD's own) and,
+    /// with `include_staged`, the current index instead of HEAD's tree.
+    pub fn amend(&self, message: Option<&str>, include_staged: bool) -> Result<Oid, GitzError> {
+        let head = self.inner.head()?.peel_to_commit()?;
+        let tree = if include_staged {
+            let tree_id = self.inner.index()?.write_tree()?;
+            self.inner.find_tree(tree_id)?
+        } else {
+            head.tree()?
+        };
+        let committer = self.inner.signature()?;
+        Ok(head.amend(Some("HEAD"), None, Some(&committer), None, message, Some(&tree))?)
+    }
+
+    /// Commit the index as `fixup! <subject>` (or `squash! <subject>` followed
+    /// by `body`) of `target`, for a later autosquash rebase.
+    pub fn commit_fixup(&self, target: Oid, squash: bool, body: Option<&str>) -> Result<Oid, GitzError> {
+        let subject = self.inner.find_commit(target)?.summary().unwrap_or("").to_string();
+        let head_tree = self.head_tree()?.map(|t| t.id());
+        if head_tree == Some(self.inner.index()?.write_tree()?) {
+            return Err(GitzError::InvalidInput("Nothing staged for the fixup commit".into()));
+        }
+        let message = match (squash, body.map(str::trim).filter(|b| !b.is_empty())) {
+            (false, _) => format!("fixup! {}", subject),
+            (true, None) => format!("squash! {}", subject),
+            (true, Some(body)) => format!("squash! {}\n\n{}", subject, body),
+        };
+        self.commit(&message)
+    }
+
+    /// Change the message of `target`, an ancestor of HEAD on the first-parent
+    /// chain, and replay the commits above it. HEAD's tree is unchanged.
+    pub fn reword(&self, target: Oid, message: &str) -> Result<Oid, GitzError> {
+        let head = self.inner.head()?.peel_to_commit()?;
+        let mut descendants = Vec::new();
+        let mut current = head.clone();
+        while current.id() != target {
+            if current.parent_count() > 1 {
+                return Err(GitzError::InvalidInput("Cannot reword across a merge commit".into()));
+            }
+            descendants.push(current.id());
+            current = current.parent(0).map_err(|_| {
+                GitzError::InvalidInput(format!("{} is not an ancestor of HEAD", target))
+            })?;
+        }
+        descendants.reverse();
+
+        let committer = self.inner.signature()?;
+        let parents: Vec<git2::Commit> = current.parents().collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        let reworded = self.inner.commit(None, &current.author(), &committer, message, &current.tree()?, &parent_refs)?;
+        let new_head = self.replay_commits(reworded, &descendants)?;
+        self.inner.head()?.set_target(new_head, &format!("reword: {}", current.summary().unwrap_or("")))?;
+        Ok(new_head)
+    }
+
+    /// Cherry-pick `commits` (oldest first) on top of `onto` without touching
+    /// the work tree, index or refs. Returns the new tip. A conflicting step
+    /// aborts the whole replay with `MergeConflict`.
+    fn replay_commits(&self, onto: Oid, commits: &[Oid]) -> Result<Oid, GitzError> {
+        let committer = self.inner.signature()?;
+        let mut tip = self.inner.find_commit(onto)?;
+        for oid in commits {
+            let commit = self.inner.find_commit(*oid)?;
+            let mut index = self.inner.cherrypick_commit(&commit, &tip, 0, None)?;
+            if index.has_conflicts() {
+                return Err(GitzError::MergeConflict);
+            }
+            let tree = self.inner.find_tree(index.write_tree_to(&self.inner)?)?;
+            let message = commit.message().unwrap_or("");
+            let new_oid = self.inner.commit(None, &commit.author(), &committer, message, &tree, &[&tip])?;
+            tip = self.inner.find_commit(new_oid)?;
+        }
+        Ok(tip.id())
+    }
+
+    /// Contents of the file named by `commit.template`, if configured.
+    /// Relative paths are resolved against the work tree.
+    pub fn commit_template(&self) -> Result<Option<String>, GitzError> {
+        let config = self.inner.config()?;
+        let path = match config.get_path("commit.template") {
+            Ok(path) => path,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let path = match self.inner.workdir() {
+            Some(workdir) if path.is_relative() => workdir.join(path),
+            _ => path,
+        };
+        Ok(Some(std::fs::read_to_string(path)?))
+    }
+
+    /// Start a paged log of the history reachable from `start` (a branch,
+    /// tag or other revision), or from HEAD when `None`;
+    /// [`log_next`](Self::log_next) returns the pages. An unborn branch
+    /// has no history.
+    pub fn log_cursor(&self, start: Option<&str>) -> Result<LogCursor, GitzError> {
+        let mut cursor = LogCursor::default();
+        let tip = match start {
+            Some(spec) => self.inner.revparse_single(spec)?.peel_to_commit()?,
+            None if self.head_tree()?.is_none() => return Ok(cursor),
+            None => self.inner.head()?.peel_to_commit()?,
+        };
+        cursor.push(&tip);
+        Ok(cursor)
+    }
+
+    /// The next `limit` commits of a paged log, walking no further back
+    /// than their parents.
+    pub fn log_next(&self, cursor: &mut LogCursor, limit: usize) -> Result<Vec<CommitInfo>, GitzError> {
+        let mut commits = Vec::new();
+        while commits.len() < limit {
+            let Some((_, _, oid)) = cursor.queue.pop() else {
+                break;
+            };
+            let commit = self.inner.find_commit(oid)?;
+            for parent in commit.parents() {
+                cursor.push(&parent);
+            }
+            commits.push(CommitInfo::from_commit(&commit));
+        }
+        Ok(commits)
+    }
+
+    /// A newest-first walk from `start` (HEAD when `None`); `None` on an unborn branch.
+    pub(super) fn history_walk(&self, start: Option<&str>) -> Result<Option<git2::Revwalk<'_>>, GitzError> {
+        let mut revwalk = self.inner.revwalk()?;
+        match start {
+            Some(spec) => revwalk.push(self.inner.revparse_single(spec)?.peel_to_commit()?.id())?,
+            None if self.head_tree()?.is_none() => return Ok(None),
+            None => revwalk.push_head()?,
+        }
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        Ok(Some(revwalk))
+    }
+
+    /// Look up a single commit by id.
+    pub fn find_commit(&self, oid: Oid) -> Result<CommitInfo, GitzError> {
+        Ok(CommitInfo::from_commit(&self.inner.find_commit(oid)?))
+    }
+
+    /// Ref labels (HEAD, branches, tags) keyed by the commit they point at.
+    pub fn decorations(&self) -> Result<HashMap<Oid, Vec<Decoration>>, GitzError> {
+        let mut map: HashMap<Oid, Vec<Decoration>> = HashMap::new();
+        if let Ok(head) = self.inner.head() {
+            if let Some(oid) = head.target() {
+                map.entry(oid).or_default().push(Decoration::new(RefKind::Head, "HEAD"));
+            }
+        }
+        for reference in self.inner.references()? {
+            let reference = reference?;
+            if reference.symbolic_target().is_some() {
+                continue;
+            }
+            let kind = if reference.is_branch() {
+                RefKind::LocalBranch
+            } else if reference.is_remote() {
+                RefKind::RemoteBranch
+            } else if reference.is_tag() {
+                RefKind::Tag
+            } else {
+                continue;
+            };
+            let Ok(commit) = reference.peel_to_commit() else {
+                continue;
+            };
+            let name = reference.shorthand().unwrap_or_default().to_string();
+            map.entry(commit.id()).or_default().push(Decoration::new(kind, name));
+        }
+        for labels in map.values_mut() {
+            labels.sort();
+        }
+        Ok(map)
+    }
+
+    /// Changes introduced by a commit, relative to its first parent.
+    pub fn commit_diff(&self, oid: Oid, context_lines: u32) -> Result<Vec<FileDiff>, GitzError> {
+        let commit = self.inner.find_commit(oid)?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let mut opts = DiffOptions::new();
+        opts.context_lines(context_lines);
+        let mut diff = self.inner.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
+        diff.find_similar(None)?;
+        Ok(collect_file_diffs(&diff)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+    use crate::git::ChangeKind;
+
+    #[test]
+    fn test_log_pages_cover_history_once() {
+        let t = TestRepo::new();
+        assert!(t.repo.log_cursor(None).unwrap().is_done());
+        assert!(t.repo.recent_commits(10).unwrap().is_empty());
+        for i in 0..5 {
+            t.write("f.txt", &format!("{}\n", i));
+            t.commit_all(&format!("commit {}\n\nbody", i));
+        }
+
+        let mut cursor = t.repo.log_cursor(None).unwrap();
+        let mut pages = Vec::new();
+        while !cursor.is_done() {
+            pages.push(t.repo.log_next(&mut cursor, 2).unwrap());
+        }
+        assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), [2, 2, 1]);
+        let subjects: Vec<_> = pages.concat().iter().map(|c| c.summary().to_string()).collect();
+        assert_eq!(subjects, ["commit 4", "commit 3", "commit 2", "commit 1", "commit 0"]);
+        assert_eq!(pages[2][0].parents.len(), 0);
+        assert_eq!(pages[0][1].parents, vec![pages[1][0].oid]);
+        assert_eq!(pages[0][0].author, "Test User");
+        assert!(t.repo.log_next(&mut cursor, 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_log_cursor_walks_only_as_far_as_the_page() {
+        let t = TestRepo::new();
+        t.write("f.txt", "base\n");
+        let base = t.commit_all("base");
+        t.write("a.txt", "a\n");
+        let a = t.commit_all("a");
+        // A side commit on `base`, merged into HEAD.
+        let git = t.git();
+        let sig = git.signature().unwrap();
+        let base_commit = git.find_commit(base).unwrap();
+        let side = git.commit(None, &sig, &sig, "side", &base_commit.tree().unwrap(), &[&base_commit]).unwrap();
+        let head = git.find_commit(a).unwrap();
+        let side_commit = git.find_commit(side).unwrap();
+        let merge = git.commit(Some("HEAD"), &sig, &sig, "merge", &head.tree().unwrap(), &[&head, &side_commit]).unwrap();
+
+        let mut cursor = t.repo.log_cursor(None).unwrap();
+        assert_eq!(t.repo.log_next(&mut cursor, 1).unwrap()[0].oid, merge);
+        // Only the merge's parents have been looked at so far.
+        assert_eq!(cursor.seen, HashSet::from([merge, a, side]));
+
+        let rest: Vec<Oid> = t.repo.log_next(&mut cursor, 10).unwrap().iter().map(|c| c.oid).collect();
+        assert_eq!(rest.len(), 3);
+        assert!(rest[..2].contains(&a) && rest[..2].contains(&side));
+        assert_eq!(rest[2], base);
+        assert!(cursor.is_done());
+    }
+
+    #[test]
+    fn test_decorations_label_head_branches_and_tags() {
+        let t = TestRepo::new();
+        t.write("f.txt", "one\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "two\n");
+        let second = t.commit_all("second");
+        let git = t.git();
+        let first_commit = git.find_commit(first).unwrap();
+        git.branch("topic", &first_commit, false).unwrap();
+        git.tag_lightweight("v1.0", first_commit.as_object(), false).unwrap();
+
+        let decorations = t.repo.decorations().unwrap();
+        let head_branch = t.repo.current_branch().unwrap();
+        assert_eq!(
+            decorations[&second],
+            vec![Decoration::new(RefKind::Head, "HEAD"), Decoration::new(RefKind::LocalBranch, head_branch)]
+        );
+        assert_eq!(
+            decorations[&first],
+            vec![Decoration::new(RefKind::LocalBranch, "topic"), Decoration::new(RefKind::Tag, "v1.0")]
+        );
+    }
+
+    #[test]
+    fn test_commit_diff_against_first_parent() {
+        let t = TestRepo::new();
+        t.write("a.txt", "some longer content so rename detection has something to match\n");
+        let root = t.commit_all("root");
+        std::fs::rename(t.path("a.txt"), t.path("b.txt")).unwrap();
+        t.write("c.txt", "new\n");
+        let git = t.git();
+        let mut index = git.index().unwrap();
+        index.remove_path(std::path::Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let second = t.commit_all("move");
+
+        let files = t.repo.commit_diff(root, 3).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].change, ChangeKind::Added);
+
+        let files = t.repo.commit_diff(second, 3).unwrap();
+        let changes: Vec<_> = files.iter().map(|f| (f.path().to_string(), f.change)).collect();
+        assert_eq!(changes, [("b.txt".to_string(), ChangeKind::Renamed), ("c.txt".to_string(), ChangeKind::Added)]);
+    }
+
+    #[test]
+    fn test_commit_template() {
+        let t = TestRepo::new();
+        assert_eq!(t.repo.commit_template().unwrap(), None);
+        t.write(".gitmessage", "Subject\n\n# Why?\n");
+        t.git().config().unwrap().set_str("commit.template", ".gitmessage").unwrap();
+        assert_eq!(t.repo.commit_template().unwrap().as_deref(), Some("Subject\n\n# Why?\n"));
+    }
+
+    #[test]
+    fn test_amend_message_and_content() {
+        let t = TestRepo::new();
+        t.write("f.txt", "one\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "two\n");
+        let original = t.commit_all("second");
+
+        let amended = t.repo.amend(Some("second, reworded"), false).unwrap();
+        let commit = t.repo.find_commit(amended).unwrap();
+        assert_eq!(commit.message, "second, reworded");
+        assert_eq!(commit.parents, vec![first]);
+
+        t.write("f.txt", "three\n");
+        t.repo.add_all().unwrap();
+        let amended = t.repo.amend(None, true).unwrap();
+        assert_ne!(amended, original);
+        assert_eq!(t.repo.find_commit(amended).unwrap().message, "second, reworded");
+        assert!(t.repo.status().unwrap().is_clean());
+    }
+
+    #[test]
+    fn test_reword_replays_descendants() {
+        let t = TestRepo::new();
+        let mut oids = Vec::new();
+        for i in 0..4 {
+            t.write(&format!("f{}.txt", i), "x\n");
+            oids.push(t.commit_all(&format!("commit {}", i)));
+        }
+        let new_head = t.repo.reword(oids[1], "commit 1, reworded").unwrap();
+
+        let log = t.repo.recent_commits(10).unwrap();
+        let subjects: Vec<_> = log.iter().map(|c| c.summary().to_string()).collect();
+        assert_eq!(subjects, ["commit 3", "commit 2", "commit 1, reworded", "commit 0"]);
+        assert_eq!(log[0].oid, new_head);
+        assert_eq!(log[3].oid, oids[0]);
+        assert!(t.repo.status().unwrap().is_clean());
+        assert!(matches!(t.repo.reword(Oid::zero(), "x"), Err(crate::errors::GitzError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_fixup_and_squash_messages() {
+        let t = TestRepo::new();
+        t.write("f.txt", "one\n");
+        let target = t.commit_all("Add feature\n\nDetails");
+        assert!(t.repo.commit_fixup(target, false, None).is_err());
+
+        t.write("f.txt", "two\n");
+        t.repo.add_all().unwrap();
+        let fixup = t.repo.commit_fixup(target, false, None).unwrap();
+        assert_eq!(t.repo.find_commit(fixup).unwrap().message, "fixup! Add feature");
+
+        t.write("f.txt", "three\n");
+        t.repo.add_all().unwrap();
+        let squash = t.repo.commit_fixup(target, true, Some("More details")).unwrap();
+        assert_eq!(t.repo.find_commit(squash).unwrap().message, "squash! Add feature\n\nMore details");
+    }
 }
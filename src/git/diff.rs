@@ -0,0 +1,507 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use git2::{ApplyLocation, Delta, DiffOptions, Patch};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::ops::RangeInclusive;
+
+/// Which side of the working copy a diff describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffTarget {
+    /// Changes recorded in the index relative to HEAD.
+    Staged,
+    /// Changes in the work tree relative to the index.
+    Unstaged,
+}
+
+/// Kind of change a file went through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Copied,
+    Typechange,
+    Untracked,
+    Unreadable,
+}
+
+impl ChangeKind {
+    /// Convert a libgit2 delta status, returning `None` for unchanged entries.
+    pub fn from_delta(delta: Delta) -> Option<Self> {
+        match delta {
+            Delta::Added => Some(ChangeKind::Added),
+            Delta::Modified => Some(ChangeKind::Modified),
+            Delta::Deleted => Some(ChangeKind::Deleted),
+            Delta::Renamed => Some(ChangeKind::Renamed),
+            Delta::Copied => Some(ChangeKind::Copied),
+            Delta::Typechange => Some(ChangeKind::Typechange),
+            Delta::Untracked => Some(ChangeKind::Untracked),
+            Delta::Unreadable => Some(ChangeKind::Unreadable),
+            Delta::Unmodified | Delta::Ignored | Delta::Conflicted => None,
+        }
+    }
+
+    /// Short human-readable label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Deleted => "deleted",
+            ChangeKind::Renamed => "renamed",
+            ChangeKind::Copied => "copied",
+            ChangeKind::Typechange => "typechange",
+            ChangeKind::Untracked => "untracked",
+            ChangeKind::Unreadable => "unreadable",
+        }
+    }
+}
+
+/// Role of a single line inside a hunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Context,
+    Addition,
+    Deletion,
+}
+
+impl DiffLineKind {
+    /// Prefix character used in unified diff output.
+    pub fn prefix(&self) -> char {
+        match self {
+            DiffLineKind::Context => ' ',
+            DiffLineKind::Addition => '+',
+            DiffLineKind::Deletion => '-',
+        }
+    }
+}
+
+/// One line of a hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub old_lineno: Option<u32>,
+    pub new_lineno: Option<u32>,
+    /// Line content without the trailing newline.
+    pub content: String,
+    /// False when this is the last line of a file lacking a final newline.
+    pub has_newline: bool,
+}
+
+/// A contiguous block of changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+/// All changes to a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+    pub change: ChangeKind,
+    pub old_mode: u32,
+    pub new_mode: u32,
+    pub binary: bool,
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl FileDiff {
+    /// Path to show for this file (the new path unless it was deleted).
+    pub fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or("<unknown>")
+    }
+
+    /// Number of added lines.
+    pub fn additions(&self) -> usize {
+        self.count(DiffLineKind::Addition)
+    }
+
+    /// Number of deleted lines.
+    pub fn deletions(&self) -> usize {
+        self.count(DiffLineKind::Deletion)
+    }
+
+    fn count(&self, kind: DiffLineKind) -> usize {
+        self.hunks
+            .iter()
+            .flat_map(|h| h.lines.iter())
+            .filter(|l| l.kind == kind)
+            .count()
+    }
+}
+
+/// Part of a file diff picked for partial staging, unstaging or discarding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffSelection {
+    /// Index of the hunk inside `FileDiff::hunks`.
+    pub hunk: usize,
+    /// Range of line indices inside the hunk, or `None` for the whole hunk.
+    pub lines: Option<RangeInclusive<usize>>,
+}
+
+impl DiffSelection {
+    /// Select a whole hunk.
+    pub fn hunk(hunk: usize) -> Self {
+        Self { hunk, lines: None }
+    }
+
+    /// Select a range of lines inside a hunk.
+    pub fn lines(hunk: usize, lines: RangeInclusive<usize>) -> Self {
+        Self { hunk, lines: Some(lines) }
+    }
+
+    fn contains(&self, line: usize) -> bool {
+        self.lines.as_ref().is_none_or(|r| r.contains(&line))
+    }
+}
+
+/// Build a unified patch containing only the selected changes of one hunk.
+///
+/// A forward patch applies the selection on top of the diff's old side (staging).
+/// A reverse patch takes the selection back out of the new side (unstaging or
+/// discarding). Returns `None` when the selection contains no added or removed lines.
+pub fn selection_patch(file: &FileDiff, selection: &DiffSelection, reverse: bool) -> Option<String> {
+    let hunk = file.hunks.get(selection.hunk)?;
+
+    let mut body = String::new();
+    let (mut old_count, mut new_count) = (0u32, 0u32);
+    let mut changed = false;
+    let mut whole = true;
+    for (idx, line) in hunk.lines.iter().enumerate() {
+        let selected = selection.contains(idx);
+        // Map each line onto what the patch should do with it.
+        let prefix = match (line.kind, selected, reverse) {
+            (DiffLineKind::Context, _, _) => Some(' '),
+            (DiffLineKind::Addition, true, false) => Some('+'),
+            (DiffLineKind::Deletion, true, false) => Some('-'),
+            (DiffLineKind::Addition, true, true) => Some('-'),
+            (DiffLineKind::Deletion, true, true) => Some('+'),
+            // An unselected change stays as it is on the side being patched.
+            (DiffLineKind::Addition, false, false) | (DiffLineKind::Deletion, false, true) => None,
+            (DiffLineKind::Addition, false, true) | (DiffLineKind::Deletion, false, false) => Some(' '),
+        };
+        if line.kind != DiffLineKind::Context && !selected {
+            whole = false;
+        }
+        let Some(prefix) = prefix else { continue };
+        match prefix {
+            '+' => new_count += 1,
+            '-' => old_count += 1,
+            _ => {
+                old_count += 1;
+                new_count += 1;
+            }
+        }
+        changed |= prefix != ' ';
+        let _ = writeln!(body, "{}{}", prefix, line.content);
+        if !line.has_newline {
+            body.push_str("\\ No newline at end of file\n");
+        }
+    }
+    if !changed {
+        return None;
+    }
+
+    // Only the full selection of a single-hunk file may create or delete it.
+    whole &= file.hunks.len() == 1;
+    let (old_path, new_path) = (file.old_path.as_deref(), file.new_path.as_deref());
+    let path = file.path();
+    let (source, dest, mode_line) = match (old_path, new_path, reverse) {
+        (None, _, false) => (None, Some(path), Some(format!("new file mode {:o}", file.new_mode))),
+        (_, None, false) if whole => (Some(path), None, Some(format!("deleted file mode {:o}", file.old_mode))),
+        (None, _, true) if whole => (Some(path), None, Some(format!("deleted file mode {:o}", file.new_mode))),
+        (_, None, true) => (None, Some(path), Some(format!("new file mode {:o}", file.old_mode))),
+        _ => (Some(path), Some(path), None),
+    };
+
+    let old_start = if reverse { hunk.new_start } else { hunk.old_start };
+    let new_start = match (old_count, new_count) {
+        (_, 0) => old_start.saturating_sub(1),
+        (0, _) => old_start + 1,
+        _ => old_start,
+    };
+
+    let mut patch = String::new();
+    let a = source.unwrap_or(path);
+    let b = dest.unwrap_or(path);
+    let _ = writeln!(patch, "diff --git a/{} b/{}", a, b);
+    if let Some(mode_line) = mode_line {
+        let _ = writeln!(patch, "{}", mode_line);
+    }
+    let _ = writeln!(patch, "--- {}", source.map_or("/dev/null".to_string(), |p| format!("a/{}", p)));
+    let _ = writeln!(patch, "+++ {}", dest.map_or("/dev/null".to_string(), |p| format!("b/{}", p)));
+    let _ = writeln!(patch, "@@ -{},{} +{},{} @@", old_start, old_count, new_start, new_count);
+    patch.push_str(&body);
+    Some(patch)
+}
+
+/// Convert a libgit2 diff into the owned model used by the UI.
+pub(crate) fn collect_file_diffs(diff: &git2::Diff<'_>) -> Result<Vec<FileDiff>, git2::Error> {
+    let mut files = Vec::new();
+    for (idx, delta) in diff.deltas().enumerate() {
+        let Some(change) = ChangeKind::from_delta(delta.status()) else {
+            continue;
+        };
+        let old_path = match change {
+            ChangeKind::Added | ChangeKind::Untracked => None,
+            _ => delta.old_file().path().map(|p| p.to_string_lossy().into_owned()),
+        };
+        let new_path = match change {
+            ChangeKind::Deleted => None,
+            _ => delta.new_file().path().map(|p| p.to_string_lossy().into_owned()),
+        };
+
+        let mut file = FileDiff {
+            old_path,
+            new_path,
+            change,
+            old_mode: delta.old_file().mode().into(),
+            new_mode: delta.new_file().mode().into(),
+            binary: delta.flags().is_binary(),
+            hunks: Vec::new(),
+        };
+
+        match Patch::from_diff(diff, idx)? {
+            Some(patch) => {
+                file.binary |= patch.delta().flags().is_binary();
+                for hunk_idx in 0..patch.num_hunks() {
+                    let (hunk, line_count) = patch.hunk(hunk_idx)?;
+                    let mut lines = Vec::with_capacity(line_count);
+                    for line_idx in 0..line_count {
+                        let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+                        let kind = match line.origin() {
+                            ' ' => DiffLineKind::Context,
+                            '+' => DiffLineKind::Addition,
+                            '-' => DiffLineKind::Deletion,
+                            // End-of-file newline markers are folded into `has_newline`.
+                            _ => continue,
+                        };
+                        let raw = String::from_utf8_lossy(line.content());
+                        let has_newline = raw.ends_with('\n');
+                        lines.push(DiffLine {
+                            kind,
+                            old_lineno: line.old_lineno(),
+                            new_lineno: line.new_lineno(),
+                            content: raw.trim_end_matches(['\n', '\r']).to_string(),
+                            has_newline,
+                        });
+                    }
+                    file.hunks.push(DiffHunk {
+                        header: String::from_utf8_lossy(hunk.header()).trim_end().to_string(),
+                        old_start: hunk.old_start(),
+                        old_lines: hunk.old_lines(),
+                        new_start: hunk.new_start(),
+                        new_lines: hunk.new_lines(),
+                        lines,
+                    });
+                }
+            }
+            None => file.binary = true,
+        }
+        files.push(file);
+    }
+    Ok(files)
+}
+
+impl Repository {
+    /// Structured diff of the staged or unstaged changes, limited to `paths`
+    /// unless empty. Renames are detected among the diffed paths only, so
+    /// pass both sides of a staged rename to get it as one delta.
+    pub fn diff(&self, target: DiffTarget, paths: &[&str], context_lines: u32) -> Result<Vec<FileDiff>, GitzError> {
+        let mut opts = DiffOptions::new();
+        opts.context_lines(context_lines);
+        for path in paths {
+            opts.pathspec(path);
+        }
+        opts.disable_pathspec_match(!paths.is_empty());
+        let mut diff = match target {
+            DiffTarget::Staged => {
+                let tree = self.head_tree()?;
+                self.inner.diff_tree_to_index(tree.as_ref(), None, Some(&mut opts))?
+            }
+            DiffTarget::Unstaged => {
+                opts.include_untracked(true)
+                    .recurse_untracked_dirs(true)
+                    .show_untracked_content(true);
+                self.inner.diff_index_to_workdir(None, Some(&mut opts))?
+            }
+        };
+        if target == DiffTarget::Staged {
+            diff.find_similar(None)?;
+        }
+        Ok(collect_file_diffs(&diff)?)
+    }
+
+    /// Stage part of an unstaged file diff.
+    pub fn stage_selection(&self, file: &FileDiff, selection: &DiffSelection) -> Result<(), GitzError> {
+        self.apply_selection(file, selection, false, ApplyLocation::Index)
+    }
+
+    /// Remove part of a staged file diff from the index.
+    pub fn unstage_selection(&self, file: &FileDiff, selection: &DiffSelection) -> Result<(), GitzError> {
+        self.apply_selection(file, selection, true, ApplyLocation::Index)
+    }
+
+    /// Throw away part of an unstaged file diff in the work tree.
+    pub fn discard_selection(&self, file: &FileDiff, selection: &DiffSelection) -> Result<(), GitzError> {
+        self.apply_selection(file, selection, true, ApplyLocation::WorkDir)
+    }
+
+    fn apply_selection(
+        &self,
+        file: &FileDiff,
+        selection: &DiffSelection,
+        reverse: bool,
+        location: ApplyLocation,
+    ) -> Result<(), GitzError> {
+        if file.binary {
+            return Err(GitzError::InvalidInput(format!("{} is a binary file", file.path())));
+        }
+        let patch = selection_patch(file, selection, reverse)
+            .ok_or_else(|| GitzError::InvalidInput("Selection contains no changes".into()))?;
+        let diff = git2::Diff::from_buffer(patch.as_bytes())?;
+        self.inner.apply(&diff, location, None)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_unstaged_diff_has_line_numbers() {
+        let t = TestRepo::new();
+        t.write("a.txt", "one\ntwo\nthree\n");
+        t.commit_all("initial");
+        t.write("a.txt", "one\n2\nthree\n");
+
+        let files = t.repo.diff(DiffTarget::Unstaged, &[], 3).unwrap();
+        assert_eq!(files.len(), 1);
+        let file = &files[0];
+        assert_eq!(file.path(), "a.txt");
+        assert_eq!(file.change, ChangeKind::Modified);
+        assert_eq!((file.additions(), file.deletions()), (1, 1));
+
+        let hunk = &file.hunks[0];
+        assert_eq!((hunk.old_start, hunk.new_start), (1, 1));
+        let removed = hunk.lines.iter().find(|l| l.kind == DiffLineKind::Deletion).unwrap();
+        assert_eq!(removed.content, "two");
+        assert_eq!((removed.old_lineno, removed.new_lineno), (Some(2), None));
+        let added = hunk.lines.iter().find(|l| l.kind == DiffLineKind::Addition).unwrap();
+        assert_eq!(added.content, "2");
+        assert_eq!((added.old_lineno, added.new_lineno), (None, Some(2)));
+    }
+
+    #[test]
+    fn test_staged_diff_on_unborn_head() {
+        let t = TestRepo::new();
+        t.write("new.txt", "hello\n");
+        t.repo.add_all().unwrap();
+
+        let staged = t.repo.diff(DiffTarget::Staged, &[], 3).unwrap();
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].change, ChangeKind::Added);
+        assert_eq!(staged[0].old_path, None);
+        assert!(t.repo.diff(DiffTarget::Unstaged, &[], 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_staged_rename_is_one_delta() {
+        let t = TestRepo::new();
+        t.write("old.txt", "one\ntwo\nthree\nfour\n");
+        t.write("other.txt", "other\n");
+        t.commit_all("initial");
+        std::fs::rename(t.path("old.txt"), t.path("new.txt")).unwrap();
+        t.write("other.txt", "changed\n");
+        t.repo.add_all().unwrap();
+
+        let staged = t.repo.diff(DiffTarget::Staged, &["new.txt", "old.txt"], 3).unwrap();
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].change, ChangeKind::Renamed);
+        assert_eq!(staged[0].old_path.as_deref(), Some("old.txt"));
+        assert_eq!(staged[0].new_path.as_deref(), Some("new.txt"));
+    }
+
+    fn two_change_repo() -> TestRepo {
+        let t = TestRepo::new();
+        t.write("a.txt", "a\nb\nc\nd\n");
+        t.commit_all("initial");
+        t.write("a.txt", "a\nB\nc\nD\n");
+        t
+    }
+
+    #[test]
+    fn test_selection_patch_keeps_unselected_changes_out() {
+        let t = two_change_repo();
+        let file = &t.repo.diff(DiffTarget::Unstaged, &[], 3).unwrap()[0];
+        // Lines: " a", "-b", "+B", " c", "-d", "+D"
+        let patch = selection_patch(file, &DiffSelection::lines(0, 1..=2), false).unwrap();
+        assert!(patch.ends_with("@@ -1,4 +1,4 @@\n a\n-b\n+B\n c\n d\n"), "{}", patch);
+        assert!(selection_patch(file, &DiffSelection::lines(0, 0..=0), false).is_none());
+    }
+
+    #[test]
+    fn test_stage_and_unstage_single_line() {
+        let t = two_change_repo();
+        let file = t.repo.diff(DiffTarget::Unstaged, &[], 3).unwrap().remove(0);
+        t.repo.stage_selection(&file, &DiffSelection::lines(0, 1..=2)).unwrap();
+        assert_eq!(t.index_contents("a.txt").unwrap(), "a\nB\nc\nd\n");
+
+        let staged = t.repo.diff(DiffTarget::Staged, &[], 3).unwrap().remove(0);
+        t.repo.unstage_selection(&staged, &DiffSelection::hunk(0)).unwrap();
+        assert_eq!(t.index_contents("a.txt").unwrap(), "a\nb\nc\nd\n");
+        assert_eq!(t.read("a.txt"), "a\nB\nc\nD\n");
+    }
+
+    #[test]
+    fn test_discard_line_only_touches_work_tree() {
+        let t = two_change_repo();
+        let file = t.repo.diff(DiffTarget::Unstaged, &[], 3).unwrap().remove(0);
+        t.repo.discard_selection(&file, &DiffSelection::lines(0, 4..=5)).unwrap();
+        assert_eq!(t.read("a.txt"), "a\nB\nc\nd\n");
+        assert_eq!(t.index_contents("a.txt").unwrap(), "a\nb\nc\nd\n");
+    }
+
+    #[test]
+    fn test_stage_lines_of_untracked_file() {
+        let t = TestRepo::new();
+        t.write("base.txt", "base\n");
+        t.commit_all("initial");
+        t.write("new.txt", "keep\nskip\n");
+
+        let file = t.repo.diff(DiffTarget::Unstaged, &["new.txt"], 3).unwrap().remove(0);
+        t.repo.stage_selection(&file, &DiffSelection::lines(0, 0..=0)).unwrap();
+        assert_eq!(t.index_contents("new.txt").unwrap(), "keep\n");
+    }
+
+    #[test]
+    fn test_untracked_file_shows_content_and_missing_newline() {
+        let t = TestRepo::new();
+        t.write("base.txt", "base\n");
+        t.commit_all("initial");
+        t.write("notes.txt", "first\nlast");
+
+        let files = t.repo.diff(DiffTarget::Unstaged, &["notes.txt"], 3).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].change, ChangeKind::Untracked);
+        let lines = &files[0].hunks[0].lines;
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].has_newline);
+        assert!(!lines[1].has_newline);
+    }
+}
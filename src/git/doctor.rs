@@ -0,0 +1,454 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use git2::{BranchType, Oid, Repository as Git2Repo};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Blobs at least this big are reported as large unless told otherwise.
+pub const DEFAULT_LARGE_BLOB_BYTES: u64 = 1024 * 1024;
+
+/// How many of the largest blobs a report lists.
+pub const MAX_LARGE_BLOBS: usize = 20;
+
+/// Object database size, like `git count-objects -v`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ObjectCounts {
+    pub loose: usize,
+    pub loose_bytes: u64,
+    pub packs: usize,
+    /// Objects in all pack files.
+    pub packed: usize,
+    pub pack_bytes: u64,
+}
+
+/// An object no ref, reflog, HEAD or index reaches, and no other
+/// unreachable object points to, as `git fsck` reports it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DanglingObject {
+    #[serde(with = "crate::git::serde_oid")]
+    pub oid: Oid,
+    /// `commit`, `tree`, `blob` or `tag`.
+    pub kind: String,
+}
+
+/// A lock file left in a git directory, usually by a git process that crashed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LockFile {
+    pub path: PathBuf,
+    pub age_secs: u64,
+}
+
+/// A ref that cannot be read or points nowhere.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BrokenRef {
+    pub name: String,
+    pub problem: String,
+}
+
+/// A big blob somewhere in the history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LargeBlob {
+    #[serde(with = "crate::git::serde_oid")]
+    pub oid: Oid,
+    /// A path the blob was committed at.
+    pub path: String,
+    pub size: u64,
+}
+
+/// A local branch whose upstream configuration does not work.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UpstreamProblem {
+    pub branch: String,
+    pub problem: String,
+}
+
+/// Result of checking a repository's health.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DoctorReport {
+    pub objects: ObjectCounts,
+    pub dangling: Vec<DanglingObject>,
+    pub lock_files: Vec<LockFile>,
+    pub broken_refs: Vec<BrokenRef>,
+    /// Largest first, at most `MAX_LARGE_BLOBS`.
+    pub large_blobs: Vec<LargeBlob>,
+    /// Config keys git needs to commit that are unset, e.g. `user.email`.
+    pub missing_config: Vec<String>,
+    pub upstreams: Vec<UpstreamProblem>,
+}
+
+impl DoctorReport {
+    /// Number of things that need fixing. Dangling objects and large blobs
+    /// are only reported; `git gc` and history rewrites deal with them.
+    pub fn problems(&self) -> usize {
+        self.lock_files.len() + self.broken_refs.len() + self.missing_config.len() + self.upstreams.len()
+    }
+}
+
+/// Number of objects in a pack index (`.idx`), version 1 or 2: the last
+/// entry of its fan-out table.
+pub fn pack_index_count(idx: &[u8]) -> Option<usize> {
+    let fanout = if idx.starts_with(b"\xfftOc") { 8 } else { 0 };
+    let last = idx.get(fanout + 255 * 4..fanout + 256 * 4)?;
+    Some(u32::from_be_bytes(last.try_into().ok()?) as usize)
+}
+
+impl Repository {
+    /// Check the repository's health, counting blobs of `large_blob_bytes`
+    /// or more as large.
+    pub fn doctor(&self, large_blob_bytes: u64) -> Result<DoctorReport, GitzError> {
+        let (dangling, large_blobs) = self.dangling_and_large_blobs(large_blob_bytes)?;
+        Ok(DoctorReport {
+            objects: self.object_counts()?,
+            dangling,
+            lock_files: self.lock_files()?,
+            broken_refs: self.broken_refs()?,
+            large_blobs,
+            missing_config: self.missing_identity()?,
+            upstreams: self.upstream_problems()?,
+        })
+    }
+
+    /// Loose objects and packs in the object directory.
+    fn object_counts(&self) -> Result<ObjectCounts, GitzError> {
+        let objects = self.common_dir().join("objects");
+        let mut counts = ObjectCounts::default();
+        for dir in std::fs::read_dir(&objects)?.flatten() {
+            let name = dir.file_name().to_string_lossy().into_owned();
+            if name.len() != 2 || !name.chars().all(|c| c.is_ascii_hexdigit()) {
+                continue;
+            }
+            for file in std::fs::read_dir(dir.path())?.flatten() {
+                counts.loose += 1;
+                counts.loose_bytes += file.metadata()?.len();
+            }
+        }
+        if let Ok(packs) = std::fs::read_dir(objects.join("pack")) {
+            for path in packs.flatten().map(|e| e.path()) {
+                match path.extension().and_then(|e| e.to_str()) {
+                    Some("pack") => {
+                        counts.packs += 1;
+                        counts.pack_bytes += path.metadata()?.len();
+                    }
+                    Some("idx") => {
+                        // The count is at the end of the header's fan-out table.
+                        let mut header = Vec::new();
+                        std::fs::File::open(&path)?.take(8 + 256 * 4).read_to_end(&mut header)?;
+                        counts.packed += pack_index_count(&header).unwrap_or(0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Walk everything reachable from refs, reflogs, HEADs and indexes.
+    /// Returns the dangling objects, and the largest blobs of at least
+    /// `large_blob_bytes` with a path each was reached at.
+    fn dangling_and_large_blobs(&self, large_blob_bytes: u64) -> Result<(Vec<DanglingObject>, Vec<LargeBlob>), GitzError> {
+        use git2::ObjectType;
+
+        let mut stack: Vec<(Oid, Option<ObjectType>, String)> = Vec::new();
+        let push_reflog = |stack: &mut Vec<_>, repo: &Git2Repo, name: &str| {
+            if let Ok(reflog) = repo.reflog(name) {
+                for entry in reflog.iter() {
+                    stack.push((entry.id_old(), None, String::new()));
+                    stack.push((entry.id_new(), None, String::new()));
+                }
+            }
+        };
+        // Unreadable refs are skipped here; `broken_refs` reports them.
+        for reference in self.inner.references()?.flatten() {
+            if let Some(oid) = reference.target() {
+                stack.push((oid, None, String::new()));
+            }
+            if let Some(name) = reference.name() {
+                push_reflog(&mut stack, &self.inner, name);
+            }
+        }
+        for dir in self.checkout_git_dirs() {
+            let Ok(repo) = Git2Repo::open(&dir) else {
+                continue;
+            };
+            if let Some(oid) = repo.head().ok().and_then(|head| head.target()) {
+                stack.push((oid, None, String::new()));
+            }
+            push_reflog(&mut stack, &repo, "HEAD");
+            if let Ok(index) = repo.index() {
+                for entry in index.iter().filter(|e| e.mode != 0o160000) {
+                    let path = String::from_utf8_lossy(&entry.path).into_owned();
+                    stack.push((entry.id, Some(ObjectType::Blob), path));
+                }
+            }
+        }
+
+        let odb = self.inner.odb()?;
+        let mut reachable = HashSet::new();
+        let mut large = Vec::new();
+        while let Some((oid, kind, path)) = stack.pop() {
+            if oid.is_zero() || !reachable.insert(oid) {
+                continue;
+            }
+            let Some(kind) = kind.or_else(|| odb.read_header(oid).ok().map(|(_, kind)| kind)) else {
+                continue;
+            };
+            match kind {
+                ObjectType::Blob => {
+                    let size = odb.read_header(oid).map_or(0, |(size, _)| size as u64);
+                    if size >= large_blob_bytes {
+                        large.push(LargeBlob { oid, path, size });
+                    }
+                }
+                ObjectType::Commit => {
+                    if let Ok(commit) = self.inner.find_commit(oid) {
+                        stack.push((commit.tree_id(), Some(ObjectType::Tree), String::new()));
+                        stack.extend(commit.parent_ids().map(|p| (p, Some(ObjectType::Commit), String::new())));
+                    }
+                }
+                ObjectType::Tree => {
+                    if let Ok(tree) = self.inner.find_tree(oid) {
+                        // Submodule commits live in another repository.
+                        for entry in tree.iter().filter(|e| e.kind() != Some(ObjectType::Commit)) {
+                            let name = String::from_utf8_lossy(entry.name_bytes());
+                            let entry_path = if path.is_empty() { name.into_owned() } else { format!("{}/{}", path, name) };
+                            stack.push((entry.id(), entry.kind(), entry_path));
+                        }
+                    }
+                }
+                ObjectType::Tag => {
+                    if let Ok(tag) = self.inner.find_tag(oid) {
+                        stack.push((tag.target_id(), tag.target_type(), String::new()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        large.sort_by(|a, b| b.size.cmp(&a.size).then(a.path.cmp(&b.path)));
+        large.truncate(MAX_LARGE_BLOBS);
+
+        // Like `git fsck`, only report the tips of unreachable chains.
+        let mut unreachable = HashSet::new();
+        odb.foreach(|oid| {
+            if !reachable.contains(oid) {
+                unreachable.insert(*oid);
+            }
+            true
+        })?;
+        let mut referenced = HashSet::new();
+        let mut kinds = HashMap::new();
+        for &oid in &unreachable {
+            let Ok(object) = self.inner.find_object(oid, None) else {
+                continue;
+            };
+            if let Some(commit) = object.as_commit() {
+                referenced.insert(commit.tree_id());
+                referenced.extend(commit.parent_ids());
+            } else if let Some(tree) = object.as_tree() {
+                referenced.extend(tree.iter().map(|e| e.id()));
+            } else if let Some(tag) = object.as_tag() {
+                referenced.insert(tag.target_id());
+            }
+            kinds.insert(oid, object.kind().map_or("unknown", |k| k.str()));
+        }
+        let mut dangling: Vec<DanglingObject> = unreachable
+            .difference(&referenced)
+            .map(|&oid| DanglingObject { oid, kind: kinds.get(&oid).copied().unwrap_or("unknown").to_string() })
+            .collect();
+        dangling.sort_by(|a, b| (&a.kind, a.oid).cmp(&(&b.kind, b.oid)));
+        Ok((dangling, large))
+    }
+
+    /// `index.lock` files in the git directory of every checkout. Git
+    /// refuses to touch the index while one exists.
+    fn lock_files(&self) -> Result<Vec<LockFile>, GitzError> {
+        let mut locks = Vec::new();
+        for dir in self.checkout_git_dirs() {
+            let path = dir.join("index.lock");
+            let Ok(metadata) = path.metadata() else {
+                continue;
+            };
+            let age_secs = metadata.modified()?.elapsed().map_or(0, |age| age.as_secs());
+            locks.push(LockFile { path, age_secs });
+        }
+        Ok(locks)
+    }
+
+    /// Refs that cannot be parsed, point at missing objects, or are
+    /// symbolic refs to refs that do not exist.
+    fn broken_refs(&self) -> Result<Vec<BrokenRef>, GitzError> {
+        // libgit2 skips loose ref files it cannot parse, so list those too.
+        fn loose_refs(dir: &Path, name: &str, names: &mut BTreeSet<String>) {
+            for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+                let child = format!("{}/{}", name, entry.file_name().to_string_lossy());
+                if entry.path().is_dir() {
+                    loose_refs(&entry.path(), &child, names);
+                } else if !child.ends_with(".lock") {
+                    names.insert(child);
+                }
+            }
+        }
+        let mut names = BTreeSet::new();
+        for name in self.inner.references()?.names() {
+            names.insert(name?.to_string());
+        }
+        loose_refs(&self.common_dir().join("refs"), "refs", &mut names);
+        names.insert("HEAD".to_string());
+        let mut broken = Vec::new();
+        for name in names {
+            let problem = match self.inner.find_reference(&name) {
+                Err(e) => e.message().to_string(),
+                Ok(reference) => match (reference.target(), reference.symbolic_target()) {
+                    (Some(oid), _) if self.inner.find_object(oid, None).is_err() => {
+                        format!("points to missing object {}", oid)
+                    }
+                    // HEAD names a branch that does not exist yet before the first commit.
+                    (None, Some(target)) if name != "HEAD" && self.inner.find_reference(target).is_err() => {
+                        format!("points to missing ref {}", target)
+                    }
+                    _ => continue,
+                },
+            };
+            broken.push(BrokenRef { name, problem });
+        }
+        broken.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(broken)
+    }
+
+    /// The identity keys git needs to commit that are unset or empty.
+    fn missing_identity(&self) -> Result<Vec<String>, GitzError> {
+        let config = self.inner.config()?;
+        Ok(["user.name", "user.email"]
+            .into_iter()
+            .filter(|key| config.get_string(key).map_or(true, |value| value.trim().is_empty()))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Local branches whose upstream config is incomplete, names a remote
+    /// that does not exist, or tracks a ref that is not there.
+    fn upstream_problems(&self) -> Result<Vec<UpstreamProblem>, GitzError> {
+        let config = self.inner.config()?;
+        let mut problems = Vec::new();
+        for branch in self.inner.branches(Some(BranchType::Local))? {
+            let (branch, _) = branch?;
+            let Some(name) = branch.name()?.map(str::to_string) else {
+                continue;
+            };
+            let remote = config.get_string(&format!("branch.{}.remote", name)).ok();
+            let merge = config.get_string(&format!("branch.{}.merge", name)).ok();
+            let problem = match (remote, merge) {
+                (None, None) => continue,
+                (Some(_), None) => format!("branch.{}.remote is set but branch.{}.merge is not", name, name),
+                (None, Some(_)) => format!("branch.{}.merge is set but branch.{}.remote is not", name, name),
+                // A URL instead of a remote name has no remote-tracking refs.
+                (Some(remote), Some(_)) if remote.contains(['/', ':']) => continue,
+                (Some(remote), Some(_)) if remote != "." && self.inner.find_remote(&remote).is_err() => {
+                    format!("remote `{}` does not exist", remote)
+                }
+                (Some(remote), Some(merge)) => match self.inner.branch_upstream_name(&format!("refs/heads/{}", name)) {
+                    Err(_) => format!("no fetch refspec of `{}` maps {}", remote, merge),
+                    Ok(upstream) => {
+                        let upstream = upstream.as_str().unwrap_or_default().to_string();
+                        if self.inner.find_reference(&upstream).is_ok() {
+                            continue;
+                        }
+                        let short = upstream
+                            .strip_prefix("refs/remotes/")
+                            .or_else(|| upstream.strip_prefix("refs/heads/"))
+                            .unwrap_or(&upstream);
+                        format!("upstream {} is gone", short)
+                    }
+                },
+            };
+            problems.push(UpstreamProblem { branch: name, problem });
+        }
+        problems.sort_by(|a, b| a.branch.cmp(&b.branch));
+        Ok(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_pack_index_count() {
+        let mut v2 = b"\xfftOc\0\0\0\x02".to_vec();
+        v2.extend([0; 255 * 4]);
+        v2.extend(7u32.to_be_bytes());
+        assert_eq!(pack_index_count(&v2), Some(7));
+        assert_eq!(pack_index_count(&v2[8..]), Some(7));
+        assert_eq!(pack_index_count(b"short"), None);
+    }
+
+    #[test]
+    fn test_healthy_repository() {
+        let t = TestRepo::new();
+        t.write("a.txt", "a\n");
+        t.commit_all("initial");
+
+        let report = t.repo.doctor(DEFAULT_LARGE_BLOB_BYTES).unwrap();
+        assert_eq!(report.problems(), 0);
+        assert_eq!(report.objects.loose, 3);
+        assert!(report.dangling.is_empty());
+        assert!(report.large_blobs.is_empty());
+    }
+
+    #[test]
+    fn test_dangling_objects_and_large_blobs() {
+        let t = TestRepo::new();
+        t.write("big/data.bin", &"x".repeat(4096));
+        let first = t.commit_all("initial");
+        t.write("a.txt", "a\n");
+        let second = t.commit_all("second");
+        // Rewind the branch and forget the reflogs, so nothing reaches the
+        // second commit; the index still has its blob.
+        let branch = format!("refs/heads/{}", t.repo.current_branch().unwrap());
+        let git = t.git();
+        git.reference(&branch, first, true, "rewind").unwrap();
+        git.reflog_delete("HEAD").unwrap();
+        git.reflog_delete(&branch).unwrap();
+
+        let report = t.repo.doctor(1024).unwrap();
+        // The second commit is dangling; its tree and blob hang off it.
+        assert_eq!(report.dangling, vec![DanglingObject { oid: second, kind: "commit".into() }]);
+        assert_eq!(report.large_blobs.len(), 1);
+        assert_eq!((report.large_blobs[0].path.as_str(), report.large_blobs[0].size), ("big/data.bin", 4096));
+    }
+
+    #[test]
+    fn test_reports_locks_refs_identity_and_upstreams() {
+        let t = TestRepo::new();
+        t.write("a.txt", "a\n");
+        let head = t.commit_all("initial");
+        let branch = t.repo.current_branch().unwrap();
+        std::fs::write(t.path(".git/index.lock"), "").unwrap();
+        std::fs::write(t.path(".git/refs/heads/garbage"), "not a sha\n").unwrap();
+        std::fs::write(t.path(".git/refs/heads/missing"), format!("{}\n", "1".repeat(40))).unwrap();
+        t.repo.create_branch("gone", head).unwrap();
+        let mut config = t.git().config().unwrap();
+        config.set_str("user.email", "").unwrap();
+        config.set_str(&format!("branch.{}.remote", branch), "nowhere").unwrap();
+        config.set_str(&format!("branch.{}.merge", branch), &format!("refs/heads/{}", branch)).unwrap();
+        t.git().remote("origin", "https://example.com/repo.git").unwrap();
+        config.set_str("branch.gone.remote", "origin").unwrap();
+        config.set_str("branch.gone.merge", "refs/heads/gone").unwrap();
+
+        let report = t.repo.doctor(DEFAULT_LARGE_BLOB_BYTES).unwrap();
+        assert_eq!(report.lock_files.len(), 1);
+        assert!(report.lock_files[0].path.ends_with("index.lock"));
+        let broken: Vec<_> = report.broken_refs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(broken, ["refs/heads/garbage", "refs/heads/missing"]);
+        assert_eq!(report.missing_config, ["user.email"]);
+        let problem = |name: &str| report.upstreams.iter().find(|u| u.branch == name).unwrap().problem.clone();
+        assert_eq!(report.upstreams.len(), 2);
+        assert_eq!(problem(&branch), "remote `nowhere` does not exist");
+        assert_eq!(problem("gone"), "upstream origin/gone is gone");
+        assert_eq!(report.problems(), 6);
+    }
+}
@@ -0,0 +1,149 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::diff::collect_file_diffs;
+use crate::git::{ChangeKind, CommitInfo, FileDiff, Repository};
+use git2::DiffOptions;
+use std::path::Path;
+
+/// A commit that touched a file, and what the file was called there.
+#[derive(Debug, Clone)]
+pub struct FileRevision {
+    pub commit: CommitInfo,
+    /// Path of the file in this commit.
+    pub path: String,
+    /// Set when this commit renamed the file from another path.
+    pub renamed_from: Option<String>,
+    pub change: ChangeKind,
+}
+
+impl Repository {
+    /// Up to `limit` commits that touched `path`, newest first, following
+    /// renames back through history like `git log --follow`.
+    pub fn file_history(&self, path: &str, limit: usize) -> Result<Vec<FileRevision>, GitzError> {
+        let Some(revwalk) = self.history_walk(None)? else {
+            return Ok(Vec::new());
+        };
+        let blob_at = |tree: &git2::Tree, path: &str| tree.get_path(Path::new(path)).ok().map(|e| e.id());
+        let mut path = path.to_string();
+        let mut revisions = Vec::new();
+        for oid in revwalk {
+            if revisions.len() >= limit {
+                break;
+            }
+            let commit = self.inner.find_commit(oid?)?;
+            let tree = commit.tree()?;
+            let ours = blob_at(&tree, &path);
+            let parent_trees = commit.parents().map(|p| p.tree()).collect::<Result<Vec<_>, _>>()?;
+            // Like git's history simplification, a merge matching one of
+            // its parents did not change the file.
+            if parent_trees.iter().any(|t| blob_at(t, &path) == ours) {
+                continue;
+            }
+            if ours.is_none() && parent_trees.is_empty() {
+                continue;
+            }
+            let parent_tree = parent_trees.first();
+            let (change, renamed_from) = match (parent_tree.and_then(|t| blob_at(t, &path)), ours) {
+                (Some(_), Some(_)) => (ChangeKind::Modified, None),
+                (Some(_), None) => (ChangeKind::Deleted, None),
+                (None, _) => match self.find_rename(parent_tree, &tree, &path)? {
+                    Some(from) => (ChangeKind::Renamed, Some(from)),
+                    None => (ChangeKind::Added, None),
+                },
+            };
+            revisions.push(FileRevision {
+                commit: CommitInfo::from_commit(&commit),
+                path: path.clone(),
+                renamed_from: renamed_from.clone(),
+                change,
+            });
+            // Older commits know the file by its previous name.
+            if let Some(from) = renamed_from {
+                path = from;
+            }
+        }
+        Ok(revisions)
+    }
+
+    /// The path `path` was renamed from between two trees, if it was.
+    fn find_rename(&self, old: Option<&git2::Tree>, new: &git2::Tree, path: &str) -> Result<Option<String>, GitzError> {
+        let Some(old) = old else { return Ok(None) };
+        let mut diff = self.inner.diff_tree_to_tree(Some(old), Some(new), None)?;
+        let mut find = git2::DiffFindOptions::new();
+        find.renames(true);
+        diff.find_similar(Some(&mut find))?;
+        let from = diff
+            .deltas()
+            .filter(|d| d.status() == git2::Delta::Renamed)
+            .find(|d| d.new_file().path() == Some(Path::new(path)))
+            .and_then(|d| d.old_file().path().map(|p| p.to_string_lossy().into_owned()));
+        Ok(from)
+    }
+
+    /// The changes a revision made to its file only, with a rename shown as one entry.
+    pub fn file_revision_diff(&self, revision: &FileRevision, context_lines: u32) -> Result<Vec<FileDiff>, GitzError> {
+        let commit = self.inner.find_commit(revision.commit.oid)?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let mut opts = DiffOptions::new();
+        opts.context_lines(context_lines).disable_pathspec_match(true).pathspec(&revision.path);
+        if let Some(from) = &revision.renamed_from {
+            opts.pathspec(from);
+        }
+        let mut diff = self.inner.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
+        let mut find = git2::DiffFindOptions::new();
+        find.renames(true);
+        diff.find_similar(Some(&mut find))?;
+        Ok(collect_file_diffs(&diff)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::testing::TestRepo;
+    use crate::git::ChangeKind;
+
+    #[test]
+    fn test_history_follows_renames() {
+        let t = TestRepo::new();
+        t.write("old.txt", "line 1\nline 2\nline 3\nline 4\n");
+        t.write("other.txt", "x\n");
+        t.commit_all("add old");
+        t.write("other.txt", "y\n");
+        t.commit_all("unrelated");
+        t.write("old.txt", "line 1\nline 2\nline 3\nline 4\nline 5\n");
+        t.commit_all("edit old");
+        std::fs::rename(t.path("old.txt"), t.path("new.txt")).unwrap();
+        t.commit_all("rename");
+        t.write("new.txt", "line 0\nline 1\nline 2\nline 3\nline 4\nline 5\n");
+        t.commit_all("edit new");
+
+        let history = t.repo.file_history("new.txt", 100).unwrap();
+        let summary: Vec<(&str, &str, ChangeKind)> = history
+            .iter()
+            .map(|r| (r.commit.summary(), r.path.as_str(), r.change))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("edit new", "new.txt", ChangeKind::Modified),
+                ("rename", "new.txt", ChangeKind::Renamed),
+                ("edit old", "old.txt", ChangeKind::Modified),
+                ("add old", "old.txt", ChangeKind::Added),
+            ]
+        );
+        assert_eq!(history[1].renamed_from.as_deref(), Some("old.txt"));
+        assert_eq!(t.repo.file_history("new.txt", 2).unwrap().len(), 2);
+
+        // The diff only covers the file, shown as a rename where it moved.
+        let diff = t.repo.file_revision_diff(&history[1], 3).unwrap();
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].change, ChangeKind::Renamed);
+        let diff = t.repo.file_revision_diff(&history[2], 3).unwrap();
+        assert_eq!((diff.len(), diff[0].additions()), (1, 1));
+    }
+}
@@ -0,0 +1,539 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::{BranchKind, Repository};
+use git2::build::CheckoutBuilder;
+use git2::{BranchType, Oid};
+use std::path::Path;
+
+/// How a branch is merged into the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMode {
+    /// Fast-forward when possible, otherwise create a merge commit.
+    FastForward,
+    /// Always create a merge commit.
+    NoFastForward,
+    /// Apply the changes as a single ordinary commit.
+    Squash,
+}
+
+impl MergeMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergeMode::FastForward => "merge",
+            MergeMode::NoFastForward => "merge --no-ff",
+            MergeMode::Squash => "merge --squash",
+        }
+    }
+}
+
+/// What a merge did to the current branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeOutcome {
+    UpToDate,
+    FastForward(Oid),
+    /// A merge commit was created.
+    Merged(Oid),
+    /// A single squash commit was created.
+    Squashed(Oid),
+}
+
+impl MergeOutcome {
+    pub fn describe(&self) -> String {
+        match self {
+            MergeOutcome::UpToDate => "Already up to date".to_string(),
+            MergeOutcome::FastForward(oid) => format!("Fast-forwarded to {}", &oid.to_string()[..7]),
+            MergeOutcome::Merged(oid) => format!("Created merge commit {}", &oid.to_string()[..7]),
+            MergeOutcome::Squashed(oid) => format!("Created squash commit {}", &oid.to_string()[..7]),
+        }
+    }
+}
+
+/// A merge waiting for its conflicts to be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeState {
+    /// The commit being merged; `None` for a squash merge, which records no second parent.
+    pub theirs: Option<Oid>,
+    /// Prepared commit message (`MERGE_MSG` or `SQUASH_MSG`).
+    pub message: String,
+}
+
+/// Which side of a conflict to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+/// The three versions of a conflicted file as recorded in the index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConflictVersions {
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+/// One `<<<<<<<` … `>>>>>>>` block of a conflicted file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictHunk {
+    pub ours: String,
+    /// Present with the `diff3` conflict style.
+    pub base: Option<String>,
+    pub theirs: String,
+    /// The block as written in the file, markers included.
+    raw: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Region {
+    Clean(String),
+    Conflict(ConflictHunk),
+}
+
+/// A work tree file split into clean text and conflict hunks, so single
+/// hunks can be resolved while the others keep their markers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictedText {
+    regions: Vec<Region>,
+}
+
+/// Whether `line` starts with the 7-character conflict marker `marker`.
+fn is_marker(line: &str, marker: char) -> bool {
+    let body = line.trim_end_matches(['\n', '\r']);
+    body.len() >= 7
+        && body.chars().take(7).all(|c| c == marker)
+        && body[7..].chars().next().is_none_or(|c| c == ' ')
+}
+
+impl ConflictedText {
+    pub fn parse(text: &str) -> Self {
+        #[derive(PartialEq)]
+        enum Part {
+            Ours,
+            Base,
+            Theirs,
+        }
+
+        let mut regions = Vec::new();
+        let mut clean = String::new();
+        // The hunk being read and which part of it we are in.
+        let mut open: Option<(Part, ConflictHunk)> = None;
+
+        for line in text.split_inclusive('\n') {
+            let Some((part, hunk)) = &mut open else {
+                if is_marker(line, '<') {
+                    let hunk = ConflictHunk { ours: String::new(), base: None, theirs: String::new(), raw: line.to_string() };
+                    open = Some((Part::Ours, hunk));
+                } else {
+                    clean.push_str(line);
+                }
+                continue;
+            };
+            hunk.raw.push_str(line);
+            match part {
+                Part::Ours if is_marker(line, '|') => {
+                    hunk.base = Some(String::new());
+                    *part = Part::Base;
+                }
+                Part::Ours | Part::Base if is_marker(line, '=') => *part = Part::Theirs,
+                Part::Ours => hunk.ours.push_str(line),
+                Part::Base => hunk.base.get_or_insert_with(String::new).push_str(line),
+                Part::Theirs if is_marker(line, '>') => {
+                    let (_, hunk) = open.take().expect("open hunk");
+                    if !clean.is_empty() {
+                        regions.push(Region::Clean(std::mem::take(&mut clean)));
+                    }
+                    regions.push(Region::Conflict(hunk));
+                }
+                Part::Theirs => hunk.theirs.push_str(line),
+            }
+        }
+        // An unterminated block is not a conflict; keep it as text.
+        if let Some((_, hunk)) = open {
+            clean.push_str(&hunk.raw);
+        }
+        if !clean.is_empty() {
+            regions.push(Region::Clean(clean));
+        }
+        Self { regions }
+    }
+
+    /// The unresolved hunks, in file order.
+    pub fn hunks(&self) -> Vec<&ConflictHunk> {
+        self.regions
+            .iter()
+            .filter_map(|r| match r {
+                Region::Conflict(hunk) => Some(hunk),
+                Region::Clean(_) => None,
+            })
+            .collect()
+    }
+
+    /// Replace hunk `index` with one side. Returns false if there is no such hunk.
+    pub fn resolve(&mut self, index: usize, side: ConflictSide) -> bool {
+        let Some(region) = self.regions.iter_mut().filter(|r| matches!(r, Region::Conflict(_))).nth(index) else {
+            return false;
+        };
+        if let Region::Conflict(hunk) = region {
+            let text = match side {
+                ConflictSide::Ours => std::mem::take(&mut hunk.ours),
+                ConflictSide::Theirs => std::mem::take(&mut hunk.theirs),
+            };
+            *region = Region::Clean(text);
+        }
+        true
+    }
+
+    /// The file contents, with remaining hunks written back with their markers.
+    pub fn render(&self) -> String {
+        self.regions
+            .iter()
+            .map(|r| match r {
+                Region::Clean(text) => text.as_str(),
+                Region::Conflict(hunk) => hunk.raw.as_str(),
+            })
+            .collect()
+    }
+}
+
+impl Repository {
+    /// Merge a local or remote-tracking branch into the current branch.
+    /// Conflicts leave the merge in progress and return `MergeConflict`.
+    pub fn merge_branch(&self, name: &str, kind: BranchKind, mode: MergeMode) -> Result<MergeOutcome, GitzError> {
+        self.ensure_idle()?;
+        if self.merge_state()?.is_some() {
+            return Err(GitzError::InvalidInput("A merge is already in progress".into()));
+        }
+        let current = self
+            .head_branch_name()
+            .ok_or_else(|| GitzError::InvalidInput("HEAD is detached; check out a branch to merge into".into()))?;
+        if self.has_tracked_changes()? {
+            return Err(GitzError::InvalidInput("Commit or stash your changes before merging".into()));
+        }
+        let (branch_type, what) = match kind {
+            BranchKind::Local => (BranchType::Local, "branch"),
+            BranchKind::Remote => (BranchType::Remote, "remote-tracking branch"),
+        };
+        let reference = self.inner.find_branch(name, branch_type)?.into_reference();
+        let theirs = self.inner.reference_to_annotated_commit(&reference)?;
+
+        let (analysis, _) = self.inner.merge_analysis(&[&theirs])?;
+        if analysis.is_up_to_date() {
+            return Ok(MergeOutcome::UpToDate);
+        }
+        // There is nothing to merge into on an unborn branch.
+        if analysis.is_unborn() || (mode == MergeMode::FastForward && analysis.is_fast_forward()) {
+            self.fast_forward(&current, theirs.id())?;
+            return Ok(MergeOutcome::FastForward(theirs.id()));
+        }
+
+        let mut checkout = CheckoutBuilder::new();
+        checkout.safe().conflict_style_diff3(true);
+        self.inner.merge(&[&theirs], None, Some(&mut checkout))?;
+        let git_dir = self.inner.path();
+        if mode == MergeMode::Squash {
+            // A squash records no second parent, so drop MERGE_HEAD like git does.
+            self.inner.cleanup_state()?;
+            let message = self.squash_message(name, &current, theirs.id())?;
+            std::fs::write(git_dir.join("SQUASH_MSG"), message)?;
+        } else {
+            std::fs::write(git_dir.join("MERGE_MSG"), format!("Merge {} '{}' into {}\n", what, name, current))?;
+        }
+        if self.inner.index()?.has_conflicts() {
+            return Err(GitzError::MergeConflict);
+        }
+        let oid = self.merge_conclude()?;
+        Ok(match mode {
+            MergeMode::Squash => MergeOutcome::Squashed(oid),
+            _ => MergeOutcome::Merged(oid),
+        })
+    }
+
+    /// Subject plus the summaries of the commits a squash merge brings in.
+    fn squash_message(&self, name: &str, current: &str, theirs: Oid) -> Result<String, GitzError> {
+        let mut revwalk = self.inner.revwalk()?;
+        revwalk.push(theirs)?;
+        revwalk.hide_head()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        let mut message = format!("Squash merge '{}' into {}\n\n", name, current);
+        for oid in revwalk {
+            let commit = self.inner.find_commit(oid?)?;
+            message.push_str(&format!("* {}\n", commit.summary().unwrap_or("")));
+        }
+        Ok(message)
+    }
+
+    /// The merge in progress, if any: a regular merge (`MERGE_HEAD`) or a
+    /// squash merge waiting to be committed (`SQUASH_MSG`).
+    pub fn merge_state(&self) -> Result<Option<MergeState>, GitzError> {
+        let git_dir = self.inner.path();
+        let read = |name: &str| match std::fs::read_to_string(git_dir.join(name)) {
+            Ok(text) => Ok(Some(text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        };
+        if let Some(head) = read("MERGE_HEAD")? {
+            let theirs = Oid::from_str(head.lines().next().unwrap_or("").trim())?;
+            let message = read("MERGE_MSG")?.unwrap_or_else(|| format!("Merge commit '{}'\n", theirs));
+            return Ok(Some(MergeState { theirs: Some(theirs), message }));
+        }
+        Ok(read("SQUASH_MSG")?.map(|message| MergeState { theirs: None, message }))
+    }
+
+    /// The base, our and their versions of a conflicted path.
+    pub fn conflict_versions(&self, path: &str) -> Result<ConflictVersions, GitzError> {
+        let index = self.inner.index()?;
+        let stage = |n: i32| -> Result<Option<String>, GitzError> {
+            match index.get_path(Path::new(path), n) {
+                Some(entry) => {
+                    let blob = self.inner.find_blob(entry.id)?;
+                    Ok(Some(String::from_utf8_lossy(blob.content()).into_owned()))
+                }
+                None => Ok(None),
+            }
+        };
+        Ok(ConflictVersions { base: stage(1)?, ours: stage(2)?, theirs: stage(3)? })
+    }
+
+    /// The conflict hunks left in the work tree copy of `path`.
+    pub fn conflict_hunks(&self, path: &str) -> Result<ConflictedText, GitzError> {
+        let full = self.workdir_or_err()?.join(path);
+        match std::fs::read(full) {
+            Ok(bytes) => Ok(ConflictedText::parse(&String::from_utf8_lossy(&bytes))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ConflictedText::parse("")),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replace one conflict hunk of `path` in the work tree with one side.
+    pub fn resolve_conflict_hunk(&self, path: &str, hunk: usize, side: ConflictSide) -> Result<(), GitzError> {
+        let mut text = self.conflict_hunks(path)?;
+        if !text.resolve(hunk, side) {
+            return Err(GitzError::InvalidInput(format!("{} has no conflict hunk {}", path, hunk + 1)));
+        }
+        std::fs::write(self.workdir_or_err()?.join(path), text.render())?;
+        Ok(())
+    }
+
+    /// Take the whole file from one side and mark it resolved. A side that
+    /// deleted the file resolves to a deletion.
+    pub fn resolve_conflict_file(&self, path: &str, side: ConflictSide) -> Result<(), GitzError> {
+        let full = self.workdir_or_err()?.join(path);
+        let stage = match side {
+            ConflictSide::Ours => 2,
+            ConflictSide::Theirs => 3,
+        };
+        let mut index = self.inner.index()?;
+        match index.get_path(Path::new(path), stage) {
+            Some(entry) => {
+                let blob = self.inner.find_blob(entry.id)?;
+                if let Some(parent) = full.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&full, blob.content())?;
+                index.add_path(Path::new(path))?;
+            }
+            None => {
+                if full.exists() {
+                    std::fs::remove_file(&full)?;
+                }
+                index.remove_path(Path::new(path))?;
+            }
+        }
+        index.write()?;
+        Ok(())
+    }
+
+    /// Stage the work tree copy of a conflicted path as its resolution.
+    /// Refused while conflict markers remain.
+    pub fn mark_resolved(&self, path: &str) -> Result<(), GitzError> {
+        let full = self.workdir_or_err()?.join(path);
+        let mut index = self.inner.index()?;
+        if full.exists() {
+            if !self.conflict_hunks(path)?.hunks().is_empty() {
+                return Err(GitzError::InvalidInput(format!("{} still has conflict markers", path)));
+            }
+            index.add_path(Path::new(path))?;
+        } else {
+            index.remove_path(Path::new(path))?;
+        }
+        index.write()?;
+        Ok(())
+    }
+
+    /// Commit a merge whose conflicts are all resolved.
+    pub fn merge_conclude(&self) -> Result<Oid, GitzError> {
+        let state = self
+            .merge_state()?
+            .ok_or_else(|| GitzError::InvalidInput("No merge in progress".into()))?;
+        let mut index = self.inner.index()?;
+        if index.has_conflicts() {
+            return Err(GitzError::MergeConflict);
+        }
+        let tree = self.inner.find_tree(index.write_tree()?)?;
+        let sig = self.inner.signature()?;
+        let mut parents = vec![self.inner.head()?.peel_to_commit()?];
+        if let Some(theirs) = state.theirs {
+            parents.push(self.inner.find_commit(theirs)?);
+        }
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        // libgit2 lists the conflicted paths as `#` comments.
+        let message: Vec<&str> = state.message.lines().filter(|l| !l.starts_with('#')).collect();
+        let message = format!("{}\n", message.join("\n").trim_end());
+        let oid = self.inner.commit(Some("HEAD"), &sig, &sig, &message, &tree, &parent_refs)?;
+        self.clear_merge_state()?;
+        Ok(oid)
+    }
+
+    /// Throw the merge away, restoring HEAD's tree.
+    pub fn merge_abort(&self) -> Result<(), GitzError> {
+        if self.merge_state()?.is_none() {
+            return Err(GitzError::InvalidInput("No merge in progress".into()));
+        }
+        let head = self.inner.head()?.peel_to_commit()?;
+        self.inner.reset(head.as_object(), git2::ResetType::Hard, None)?;
+        self.clear_merge_state()
+    }
+
+    fn clear_merge_state(&self) -> Result<(), GitzError> {
+        self.inner.cleanup_state()?;
+        match std::fs::remove_file(self.inner.path().join("SQUASH_MSG")) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::GitzError;
+    use crate::git::testing::TestRepo;
+    use crate::git::{BranchKind, StatusSection};
+
+    const CONFLICTED: &str = "top\n<<<<<<< HEAD\nours 1\n||||||| base\nbase 1\n=======\ntheirs 1\n>>>>>>> topic\nmiddle\n<<<<<<< HEAD\nours 2\n=======\ntheirs 2\n>>>>>>> topic\n";
+
+    /// `main` and `topic` both changing `f.txt` from a common base; returns `main`'s name.
+    fn diverged(ours: &str, theirs: &str) -> (TestRepo, String) {
+        let t = TestRepo::new();
+        t.write("f.txt", "one\ntwo\nthree\n");
+        let base = t.commit_all("base");
+        let main = t.repo.current_branch().unwrap();
+        t.repo.create_branch("topic", base).unwrap();
+        t.repo.checkout_branch("topic", BranchKind::Local).unwrap();
+        t.write("f.txt", theirs);
+        t.write("t.txt", "topic only\n");
+        t.commit_all("topic change");
+        t.repo.checkout_branch(&main, BranchKind::Local).unwrap();
+        t.write("f.txt", ours);
+        t.commit_all("main change");
+        (t, main)
+    }
+
+    #[test]
+    fn test_parse_and_resolve_hunks() {
+        let mut text = ConflictedText::parse(CONFLICTED);
+        assert_eq!(text.render(), CONFLICTED);
+        let hunks = text.hunks();
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].ours, "ours 1\n");
+        assert_eq!(hunks[0].base.as_deref(), Some("base 1\n"));
+        assert_eq!(hunks[0].theirs, "theirs 1\n");
+        assert_eq!(hunks[1].base, None);
+
+        assert!(text.resolve(1, ConflictSide::Theirs));
+        assert_eq!(text.hunks().len(), 1);
+        assert!(text.resolve(0, ConflictSide::Ours));
+        assert!(!text.resolve(0, ConflictSide::Ours));
+        assert_eq!(text.render(), "top\nours 1\nmiddle\ntheirs 2\n");
+
+        // Unterminated or look-alike markers are plain text.
+        let odd = "<<<<<<<< not a marker\n<<<<<<< HEAD\nx\n";
+        assert!(ConflictedText::parse(odd).hunks().is_empty());
+        assert_eq!(ConflictedText::parse(odd).render(), odd);
+    }
+
+    #[test]
+    fn test_fast_forward_no_ff_and_squash() {
+        let t = TestRepo::new();
+        t.write("f.txt", "base\n");
+        let base = t.commit_all("base");
+        let main = t.repo.current_branch().unwrap();
+        t.repo.create_branch("topic", base).unwrap();
+        t.repo.checkout_branch("topic", BranchKind::Local).unwrap();
+        t.write("f.txt", "topic\n");
+        let tip = t.commit_all("topic 1");
+        t.repo.checkout_branch(&main, BranchKind::Local).unwrap();
+
+        assert_eq!(
+            t.repo.merge_branch("topic", BranchKind::Local, MergeMode::Squash).unwrap(),
+            MergeOutcome::Squashed(t.repo.recent_commits(1).unwrap()[0].oid)
+        );
+        let head = t.repo.recent_commits(1).unwrap().remove(0);
+        assert_eq!(head.parents, [base]);
+        assert!(head.message.contains("topic 1"));
+        assert_eq!(t.read("f.txt"), "topic\n");
+        assert!(t.repo.merge_state().unwrap().is_none());
+
+        t.repo.create_branch("ff", base).unwrap();
+        t.repo.checkout_branch("ff", BranchKind::Local).unwrap();
+        assert_eq!(t.repo.merge_branch("topic", BranchKind::Local, MergeMode::FastForward).unwrap(), MergeOutcome::FastForward(tip));
+        assert_eq!(t.repo.merge_branch("topic", BranchKind::Local, MergeMode::FastForward).unwrap(), MergeOutcome::UpToDate);
+
+        t.repo.create_branch("noff", base).unwrap();
+        t.repo.checkout_branch("noff", BranchKind::Local).unwrap();
+        let MergeOutcome::Merged(oid) = t.repo.merge_branch("topic", BranchKind::Local, MergeMode::NoFastForward).unwrap() else {
+            panic!("expected a merge commit");
+        };
+        let merge = t.repo.find_commit(oid).unwrap();
+        assert_eq!(merge.parents, [base, tip]);
+        assert_eq!(merge.summary(), "Merge branch 'topic' into noff");
+    }
+
+    #[test]
+    fn test_resolve_conflicts_and_conclude() {
+        let (t, _) = diverged("one\nTWO\nthree\n", "one\nzwei\nthree\n");
+        let result = t.repo.merge_branch("topic", BranchKind::Local, MergeMode::NoFastForward);
+        assert!(matches!(result, Err(GitzError::MergeConflict)));
+        let state = t.repo.merge_state().unwrap().unwrap();
+        assert!(state.theirs.is_some());
+        assert!(state.message.starts_with("Merge branch 'topic'"));
+
+        let versions = t.repo.conflict_versions("f.txt").unwrap();
+        assert_eq!(versions.base.as_deref(), Some("one\ntwo\nthree\n"));
+        assert_eq!(versions.ours.as_deref(), Some("one\nTWO\nthree\n"));
+        assert_eq!(versions.theirs.as_deref(), Some("one\nzwei\nthree\n"));
+
+        let hunks = t.repo.conflict_hunks("f.txt").unwrap();
+        assert_eq!(hunks.hunks().len(), 1);
+        assert_eq!(hunks.hunks()[0].base.as_deref(), Some("two\n"));
+        // Markers left in the file block marking it resolved.
+        assert!(matches!(t.repo.mark_resolved("f.txt"), Err(GitzError::InvalidInput(_))));
+        assert!(matches!(t.repo.merge_conclude(), Err(GitzError::MergeConflict)));
+
+        t.repo.resolve_conflict_hunk("f.txt", 0, ConflictSide::Theirs).unwrap();
+        assert_eq!(t.read("f.txt"), "one\nzwei\nthree\n");
+        t.repo.mark_resolved("f.txt").unwrap();
+        assert!(t.repo.status().unwrap().section(StatusSection::Conflicts).is_empty());
+
+        let oid = t.repo.merge_conclude().unwrap();
+        assert_eq!(t.repo.find_commit(oid).unwrap().parents.len(), 2);
+        assert_eq!(t.read("t.txt"), "topic only\n");
+        assert!(t.repo.merge_state().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_take_whole_file_and_abort() {
+        let (t, _) = diverged("ours\n", "theirs\n");
+        assert!(t.repo.merge_branch("topic", BranchKind::Local, MergeMode::Squash).is_err());
+        assert_eq!(t.repo.merge_state().unwrap().unwrap().theirs, None);
+        t.repo.resolve_conflict_file("f.txt", ConflictSide::Ours).unwrap();
+        assert_eq!(t.read("f.txt"), "ours\n");
+        assert!(t.repo.status().unwrap().section(StatusSection::Conflicts).is_empty());
+
+        t.repo.merge_abort().unwrap();
+        assert!(t.repo.merge_state().unwrap().is_none());
+        assert!(!t.path("t.txt").exists());
+        assert!(t.repo.status().unwrap().is_clean());
+    }
+}
@@ -2,7 +2,42 @@
 mod repository;
 mod status;
 mod commit;
+mod diff;
+mod branch;
+mod stash;
+mod remote;
+mod rebase;
+mod merge;
+mod blame;
+mod history;
+mod reflog;
+mod bisect;
+mod reset;
+mod submodule;
+mod tag;
+pub(crate) mod sequencer;
+mod serde_oid;
+mod serde_time;
+mod worktree;
+mod doctor;
+#[cfg(test)]
+pub(crate) mod testing;
 
 pub use repository::Repository;
-pub use status::RepoStatus;
-pub use commit::CommitInfo;
+pub use status::{ConflictInfo, FileChange, FileStatus, RepoStatus, StatusEntry, StatusSection};
+pub use commit::{CommitInfo, Decoration, LogCursor, RefKind};
+pub use branch::{BranchInfo, BranchKind};
+pub use stash::{StashInfo, StashOptions};
+pub use tag::TagInfo;
+pub use reflog::ReflogEntry;
+pub use bisect::{BisectMark, BisectState, BisectStep};
+pub use reset::{HardResetChoice, ResetMode};
+pub use submodule::SubmoduleInfo;
+pub use doctor::{DoctorReport, DEFAULT_LARGE_BLOB_BYTES};
+pub use remote::{RemoteInfo, RemoteProgress};
+pub use sequencer::{PickKind, PickOptions, SequenceOutcome, SequenceState, SequenceStop};
+pub use history::FileRevision;
+pub use blame::{Blame, BlameLine};
+pub use merge::{ConflictHunk, ConflictSide, ConflictVersions, MergeMode, MergeState};
+pub use rebase::{RebaseAction, RebaseOutcome, RebasePlan, RebaseState, RebaseStep, RebaseStop};
+pub use diff::{ChangeKind, DiffLine, DiffLineKind, DiffSelection, DiffTarget, FileDiff};
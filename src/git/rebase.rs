@@ -0,0 +1,644 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use git2::Oid;
+use git2::build::CheckoutBuilder;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// What to do with one commit of an interactive rebase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RebaseAction {
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Edit => "edit",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    /// Whether the step is folded into the commit before it.
+    pub fn melds(&self) -> bool {
+        matches!(self, RebaseAction::Squash | RebaseAction::Fixup)
+    }
+}
+
+/// One line of the todo list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RebaseStep {
+    pub action: RebaseAction,
+    #[serde(with = "crate::git::serde_oid")]
+    pub oid: Oid,
+    pub summary: String,
+    /// New message for a `reword` step; `None` keeps the original.
+    pub message: Option<String>,
+}
+
+impl RebaseStep {
+    pub fn pick(oid: Oid, summary: impl Into<String>) -> Self {
+        Self { action: RebaseAction::Pick, oid, summary: summary.into(), message: None }
+    }
+}
+
+/// An editable todo list: the commits above `onto`, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebasePlan {
+    pub onto: Oid,
+    pub steps: Vec<RebaseStep>,
+}
+
+impl RebasePlan {
+    /// Swap step `index` with the one above it. Returns whether it moved.
+    pub fn move_up(&mut self, index: usize) -> bool {
+        if index == 0 || index >= self.steps.len() {
+            return false;
+        }
+        self.steps.swap(index - 1, index);
+        true
+    }
+
+    /// Swap step `index` with the one below it. Returns whether it moved.
+    pub fn move_down(&mut self, index: usize) -> bool {
+        if index + 1 >= self.steps.len() {
+            return false;
+        }
+        self.steps.swap(index, index + 1);
+        true
+    }
+
+    pub fn set_action(&mut self, index: usize, action: RebaseAction) {
+        if let Some(step) = self.steps.get_mut(index) {
+            step.action = action;
+            if action != RebaseAction::Reword {
+                step.message = None;
+            }
+        }
+    }
+
+    /// Move `fixup! X` / `squash! X` commits below the commit whose subject is
+    /// `X` and mark them, like `git rebase --autosquash`.
+    pub fn autosquash(&mut self) {
+        let mut index = 0;
+        while index < self.steps.len() {
+            let step = &self.steps[index];
+            let (action, target) = if let Some(rest) = step.summary.strip_prefix("fixup! ") {
+                (RebaseAction::Fixup, rest.to_string())
+            } else if let Some(rest) = step.summary.strip_prefix("squash! ") {
+                (RebaseAction::Squash, rest.to_string())
+            } else {
+                index += 1;
+                continue;
+            };
+            let target_pos = self.steps[..index].iter().position(|s| s.summary == target);
+            let Some(target_pos) = target_pos else {
+                index += 1;
+                continue;
+            };
+            // Insert after the target and any fixups already attached to it.
+            let mut insert_at = target_pos + 1;
+            while insert_at < index && self.steps[insert_at].action.melds() {
+                insert_at += 1;
+            }
+            let mut step = self.steps.remove(index);
+            step.action = action;
+            self.steps.insert(insert_at, step);
+            index += 1;
+        }
+    }
+
+    /// Reject plans git would reject: a squash/fixup with nothing to fold into.
+    pub fn validate(&self) -> Result<(), GitzError> {
+        let first = self.steps.iter().find(|s| s.action != RebaseAction::Drop);
+        match first {
+            Some(step) if step.action.melds() => Err(GitzError::InvalidInput(format!(
+                "cannot {} {} without a previous commit",
+                step.action.label(),
+                &step.oid.to_string()[..7]
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Why a running rebase is paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RebaseStop {
+    /// The last step in `done` conflicted and is not committed yet.
+    Conflict,
+    /// The last step in `done` is an `edit` that has been applied.
+    Edit,
+}
+
+/// A rebase in progress, persisted in the git directory between steps.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RebaseState {
+    /// Branch being rebased (`refs/heads/...`), `None` for a detached HEAD.
+    pub head_name: Option<String>,
+    #[serde(with = "crate::git::serde_oid")]
+    pub orig_head: Oid,
+    #[serde(with = "crate::git::serde_oid")]
+    pub onto: Oid,
+    pub done: Vec<RebaseStep>,
+    pub todo: Vec<RebaseStep>,
+    pub stopped: Option<RebaseStop>,
+    /// Steps dropped because their changes are already on `onto`.
+    #[serde(default)]
+    pub emptied: Vec<RebaseStep>,
+}
+
+impl RebaseState {
+    /// The step execution is paused on.
+    pub fn current(&self) -> Option<&RebaseStep> {
+        self.stopped.and(self.done.last())
+    }
+}
+
+/// Result of running a rebase until it finishes or pauses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebaseOutcome {
+    /// All steps were applied. `emptied` lists the picks that were dropped
+    /// because applying them changed nothing.
+    Finished { tip: Oid, emptied: Vec<RebaseStep> },
+    /// Paused at an `edit` step.
+    Stopped(RebaseStep),
+}
+
+impl Repository {
+    /// A todo list picking `from` and every commit above it up to HEAD,
+    /// oldest first, with `fixup!`/`squash!` commits already placed.
+    pub fn rebase_plan(&self, from: Oid) -> Result<RebasePlan, GitzError> {
+        let from_commit = self.inner.find_commit(from)?;
+        let onto = from_commit
+            .parent_id(0)
+            .map_err(|_| GitzError::InvalidInput("Cannot rebase from the root commit".into()))?;
+        let mut steps = Vec::new();
+        let mut current = self.inner.head()?.peel_to_commit()?;
+        loop {
+            if current.parent_count() > 1 {
+                return Err(GitzError::InvalidInput("Cannot rebase across a merge commit".into()));
+            }
+            steps.push(RebaseStep::pick(current.id(), current.summary().unwrap_or("")));
+            if current.id() == from {
+                break;
+            }
+            current = current
+                .parent(0)
+                .map_err(|_| GitzError::InvalidInput(format!("{} is not an ancestor of HEAD", from)))?;
+        }
+        steps.reverse();
+        let mut plan = RebasePlan { onto, steps };
+        plan.autosquash();
+        Ok(plan)
+    }
+
+    fn rebase_state_path(&self) -> PathBuf {
+        self.inner.path().join("gitz-rebase.json")
+    }
+
+    /// The rebase in progress, if any.
+    pub fn rebase_state(&self) -> Result<Option<RebaseState>, GitzError> {
+        match std::fs::read_to_string(self.rebase_state_path()) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| GitzError::GitOperationFailed(format!("corrupt rebase state: {}", e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save_rebase_state(&self, state: &RebaseState) -> Result<(), GitzError> {
+        let json = serde_json::to_string_pretty(state)
+            .map_err(|e| GitzError::GitOperationFailed(e.to_string()))?;
+        std::fs::write(self.rebase_state_path(), json)?;
+        self.write_rebase_merge(state)
+    }
+
+    /// Mirror the state in `rebase-merge/`, where `git rebase -i` keeps its
+    /// own, so git and other tools see that a rebase is in progress.
+    fn write_rebase_merge(&self, state: &RebaseState) -> Result<(), GitzError> {
+        let dir = self.inner.path().join("rebase-merge");
+        std::fs::create_dir_all(&dir)?;
+        let todo_lines = |steps: &[RebaseStep]| -> String {
+            steps.iter().map(|s| format!("{} {} {}\n", s.action.label(), s.oid, s.summary)).collect()
+        };
+        let head_name = state.head_name.as_deref().unwrap_or("detached HEAD");
+        std::fs::write(dir.join("head-name"), format!("{}\n", head_name))?;
+        std::fs::write(dir.join("onto"), format!("{}\n", state.onto))?;
+        std::fs::write(dir.join("orig-head"), format!("{}\n", state.orig_head))?;
+        std::fs::write(dir.join("interactive"), "")?;
+        std::fs::write(dir.join("done"), todo_lines(&state.done))?;
+        std::fs::write(dir.join("git-rebase-todo"), todo_lines(&state.todo))?;
+        std::fs::write(dir.join("msgnum"), format!("{}\n", state.done.len()))?;
+        std::fs::write(dir.join("end"), format!("{}\n", state.done.len() + state.todo.len()))?;
+        Ok(())
+    }
+
+    /// Forget the rebase: its state file and the `rebase-merge/` mirror.
+    fn remove_rebase_state(&self) -> Result<(), GitzError> {
+        std::fs::remove_file(self.rebase_state_path())?;
+        match std::fs::remove_dir_all(self.inner.path().join("rebase-merge")) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn require_rebase_state(&self) -> Result<RebaseState, GitzError> {
+        self.rebase_state()?
+            .ok_or_else(|| GitzError::InvalidInput("No rebase in progress".into()))
+    }
+
+    /// Start an interactive rebase: detach HEAD at `plan.onto` and apply the
+    /// steps. Stops with `MergeConflict` when a step conflicts.
+    pub fn rebase_start(&self, plan: &RebasePlan) -> Result<RebaseOutcome, GitzError> {
+        self.ensure_idle()?;
+        if self.has_tracked_changes()? {
+            return Err(GitzError::InvalidInput("Commit or stash your changes before rebasing".into()));
+        }
+        plan.validate()?;
+        let head = self.inner.head()?;
+        let state = RebaseState {
+            head_name: head.is_branch().then(|| head.name().map(str::to_string)).flatten(),
+            orig_head: head.peel_to_commit()?.id(),
+            onto: plan.onto,
+            done: Vec::new(),
+            todo: plan.steps.clone(),
+            stopped: None,
+            emptied: Vec::new(),
+        };
+        let onto = self.inner.find_commit(plan.onto)?;
+        self.inner.checkout_tree(onto.as_object(), Some(CheckoutBuilder::new().safe()))?;
+        self.inner.set_head_detached(plan.onto)?;
+        self.save_rebase_state(&state)?;
+        self.run_rebase(state)
+    }
+
+    /// Resume after resolving a conflict or finishing an `edit` stop.
+    pub fn rebase_continue(&self) -> Result<RebaseOutcome, GitzError> {
+        let mut state = self.require_rebase_state()?;
+        match state.stopped {
+            Some(RebaseStop::Conflict) => {
+                if self.inner.index()?.has_conflicts() {
+                    return Err(GitzError::MergeConflict);
+                }
+                // The stopped step is the last one done, unless the state file was edited.
+                let step = state.done.last().cloned().ok_or_else(|| {
+                    GitzError::GitOperationFailed("rebase state is corrupt; run abort".into())
+                })?;
+                // Without CHERRY_PICK_HEAD the resolution was already committed (`git commit`).
+                if self.inner.path().join("CHERRY_PICK_HEAD").exists() && !self.commit_rebase_step(&step)? {
+                    state.emptied.push(step);
+                }
+            }
+            Some(RebaseStop::Edit) if self.has_tracked_changes()? => {
+                return Err(GitzError::InvalidInput("Commit or amend your changes before continuing".into()));
+            }
+            Some(RebaseStop::Edit) | None => {}
+        }
+        state.stopped = None;
+        self.save_rebase_state(&state)?;
+        self.run_rebase(state)
+    }
+
+    /// Drop the step the rebase stopped on and carry on with the rest.
+    pub fn rebase_skip(&self) -> Result<RebaseOutcome, GitzError> {
+        let mut state = self.require_rebase_state()?;
+        if state.stopped == Some(RebaseStop::Conflict) {
+            let head = self.inner.head()?.peel_to_commit()?;
+            self.inner.reset(head.as_object(), git2::ResetType::Hard, None)?;
+            self.clear_pick_head()?;
+        }
+        state.stopped = None;
+        self.save_rebase_state(&state)?;
+        self.run_rebase(state)
+    }
+
+    /// Give up and put the branch and work tree back where they were.
+    pub fn rebase_abort(&self) -> Result<(), GitzError> {
+        let state = self.require_rebase_state()?;
+        // The branch itself is only moved once the rebase finishes.
+        match &state.head_name {
+            Some(refname) => self.inner.set_head(refname)?,
+            None => self.inner.set_head_detached(state.orig_head)?,
+        }
+        let orig = self.inner.find_commit(state.orig_head)?;
+        self.inner.reset(orig.as_object(), git2::ResetType::Hard, None)?;
+        self.clear_pick_head()?;
+        self.remove_rebase_state()
+    }
+
+    /// Apply the remaining steps, saving the state after each one.
+    fn run_rebase(&self, mut state: RebaseState) -> Result<RebaseOutcome, GitzError> {
+        while !state.todo.is_empty() {
+            let step = state.todo.remove(0);
+            state.done.push(step.clone());
+            if step.action == RebaseAction::Drop {
+                continue;
+            }
+            let commit = self.inner.find_commit(step.oid)?;
+            let head = self.inner.head()?.peel_to_commit()?;
+            // Unchanged prefix: keep the original commit instead of recreating it.
+            if step.action == RebaseAction::Pick && commit.parent_id(0).ok() == Some(head.id()) {
+                self.inner.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+                self.inner.set_head_detached(commit.id())?;
+                self.save_rebase_state(&state)?;
+                continue;
+            }
+            // On a conflict CHERRY_PICK_HEAD stays, as with `git rebase -i`.
+            self.inner.cherrypick(&commit, None)?;
+            if self.inner.index()?.has_conflicts() {
+                state.stopped = Some(RebaseStop::Conflict);
+                self.save_rebase_state(&state)?;
+                return Err(GitzError::MergeConflict);
+            }
+            if !self.commit_rebase_step(&step)? {
+                state.emptied.push(step.clone());
+            }
+            if step.action == RebaseAction::Edit {
+                state.stopped = Some(RebaseStop::Edit);
+                self.save_rebase_state(&state)?;
+                return Ok(RebaseOutcome::Stopped(step));
+            }
+            self.save_rebase_state(&state)?;
+        }
+
+        let tip = self.inner.head()?.peel_to_commit()?.id();
+        if let Some(refname) = &state.head_name {
+            self.inner.reference(refname, tip, true, "rebase (finish)")?;
+            self.inner.set_head(refname)?;
+        }
+        self.remove_rebase_state()?;
+        Ok(RebaseOutcome::Finished { tip, emptied: state.emptied })
+    }
+
+    /// Commit the index for `step` on top of HEAD, or fold it into HEAD for
+    /// squash/fixup. Like git, a step whose changes are already in HEAD is
+    /// dropped, but a commit that was empty to begin with is kept. Returns
+    /// whether the step left a commit.
+    fn commit_rebase_step(&self, step: &RebaseStep) -> Result<bool, GitzError> {
+        let original = self.inner.find_commit(step.oid)?;
+        let head = self.inner.head()?.peel_to_commit()?;
+        let tree_id = self.inner.index()?.write_tree()?;
+        let tree = self.inner.find_tree(tree_id)?;
+        let committer = self.inner.signature()?;
+        let original_message = original.message().unwrap_or("");
+        let originally_empty = match original.parents().next() {
+            Some(parent) => parent.tree_id() == original.tree_id(),
+            None => original.tree()?.is_empty(),
+        };
+
+        if step.action.melds() {
+            let message = match step.action {
+                RebaseAction::Squash => format!(
+                    "{}\n\n{}",
+                    head.message().unwrap_or("").trim_end(),
+                    original_message.trim_end()
+                ),
+                _ => head.message().unwrap_or("").to_string(),
+            };
+            let parents: Vec<git2::Commit> = head.parents().collect();
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+            let oid = self.inner.commit(None, &head.author(), &committer, &message, &tree, &parent_refs)?;
+            self.inner.set_head_detached(oid)?;
+        } else if tree_id != head.tree_id() || originally_empty {
+            let message = step.message.as_deref().unwrap_or(original_message);
+            self.inner.commit(Some("HEAD"), &original.author(), &committer, message, &tree, &[&head])?;
+        } else {
+            self.clear_pick_head()?;
+            return Ok(false);
+        }
+        self.clear_pick_head()?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+    use crate::git::StatusSection;
+
+    fn step(n: u8, summary: &str) -> RebaseStep {
+        RebaseStep::pick(Oid::from_bytes(&[n; 20]).unwrap(), summary)
+    }
+
+    fn subjects(t: &TestRepo) -> Vec<String> {
+        t.repo.recent_commits(20).unwrap().iter().map(|c| c.summary().to_string()).collect()
+    }
+
+    /// A repository with commits `base`, `a`, `b`, `c`, each touching its own file.
+    fn linear_history() -> (TestRepo, Vec<Oid>) {
+        let t = TestRepo::new();
+        let mut oids = Vec::new();
+        for name in ["base", "a", "b", "c"] {
+            t.write(&format!("{}.txt", name), &format!("{}\n", name));
+            oids.push(t.commit_all(name));
+        }
+        (t, oids)
+    }
+
+    #[test]
+    fn test_plan_reorder_and_autosquash() {
+        let mut plan = RebasePlan {
+            onto: Oid::zero(),
+            steps: vec![step(1, "Add x"), step(2, "Add y"), step(3, "fixup! Add x"), step(4, "squash! Add x")],
+        };
+        plan.autosquash();
+        let order: Vec<_> = plan.steps.iter().map(|s| (s.action, s.summary.as_str())).collect();
+        assert_eq!(
+            order,
+            [
+                (RebaseAction::Pick, "Add x"),
+                (RebaseAction::Fixup, "fixup! Add x"),
+                (RebaseAction::Squash, "squash! Add x"),
+                (RebaseAction::Pick, "Add y"),
+            ]
+        );
+
+        assert!(plan.move_up(3));
+        assert!(!plan.move_up(0));
+        assert!(!plan.move_down(3));
+        assert_eq!(plan.steps[2].summary, "Add y");
+
+        plan.set_action(0, RebaseAction::Drop);
+        assert!(plan.validate().is_err());
+        plan.set_action(1, RebaseAction::Pick);
+        assert!(plan.validate().is_ok());
+    }
+
+    #[test]
+    fn test_state_round_trips_through_json() {
+        let state = RebaseState {
+            head_name: Some("refs/heads/main".into()),
+            orig_head: Oid::from_bytes(&[7; 20]).unwrap(),
+            onto: Oid::from_bytes(&[8; 20]).unwrap(),
+            done: vec![step(1, "a")],
+            todo: vec![RebaseStep { action: RebaseAction::Reword, message: Some("new".into()), ..step(2, "b") }],
+            stopped: Some(RebaseStop::Edit),
+            emptied: Vec::new(),
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(json.contains("\"0707070707070707070707070707070707070707\""));
+        assert!(json.contains("\"reword\""));
+        assert_eq!(serde_json::from_str::<RebaseState>(&json).unwrap(), state);
+        assert_eq!(state.current().map(|s| s.summary.as_str()), Some("a"));
+    }
+
+    #[test]
+    fn test_rebase_reorders_drops_rewords_and_squashes() {
+        let (t, oids) = linear_history();
+        let mut plan = t.repo.rebase_plan(oids[1]).unwrap();
+        assert_eq!(plan.onto, oids[0]);
+        assert_eq!(plan.steps.iter().map(|s| s.summary.as_str()).collect::<Vec<_>>(), ["a", "b", "c"]);
+
+        plan.move_up(2); // a, c, b
+        plan.set_action(1, RebaseAction::Squash); // c folds into a
+        plan.steps[0].action = RebaseAction::Reword;
+        plan.steps[0].message = Some("a and c".into());
+        plan.set_action(2, RebaseAction::Drop);
+
+        let outcome = t.repo.rebase_start(&plan).unwrap();
+        assert!(matches!(outcome, RebaseOutcome::Finished { .. }));
+        assert_eq!(subjects(&t), ["a and c", "base"]);
+        let head = t.repo.recent_commits(1).unwrap().remove(0);
+        assert_eq!(head.message, "a and c\n\nc");
+        assert!(t.path("c.txt").exists());
+        assert!(!t.path("b.txt").exists());
+        assert!(t.repo.current_branch().unwrap() != "HEAD (detached)");
+        assert!(t.repo.rebase_state().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rebase_stops_for_edit_and_continues() {
+        let (t, oids) = linear_history();
+        let mut plan = t.repo.rebase_plan(oids[2]).unwrap();
+        plan.set_action(0, RebaseAction::Edit);
+
+        let outcome = t.repo.rebase_start(&plan).unwrap();
+        assert!(matches!(outcome, RebaseOutcome::Stopped(ref s) if s.summary == "b"));
+        let state = t.repo.rebase_state().unwrap().unwrap();
+        assert_eq!(state.stopped, Some(RebaseStop::Edit));
+
+        t.write("b.txt", "b edited\n");
+        t.repo.add_all().unwrap();
+        t.repo.amend(None, true).unwrap();
+        assert!(matches!(t.repo.rebase_continue().unwrap(), RebaseOutcome::Finished { .. }));
+        assert_eq!(subjects(&t), ["c", "b", "a", "base"]);
+        assert_eq!(t.read("b.txt"), "b edited\n");
+    }
+
+    #[test]
+    fn test_rebase_keeps_empty_commits_and_reports_emptied_picks() {
+        let (t, oids) = linear_history();
+        let git = t.git();
+        let sig = git.signature().unwrap();
+        let tip = git.find_commit(oids[3]).unwrap();
+        let empty = git.commit(Some("HEAD"), &sig, &sig, "empty", &tip.tree().unwrap(), &[&tip]).unwrap();
+
+        // b, empty, c, then `a` again, whose change is already there.
+        let mut plan = t.repo.rebase_plan(oids[2]).unwrap();
+        plan.move_up(2);
+        plan.steps.push(RebaseStep::pick(oids[1], "a"));
+        assert_eq!(plan.steps[1].oid, empty);
+        let RebaseOutcome::Finished { emptied, .. } = t.repo.rebase_start(&plan).unwrap() else {
+            panic!("expected the rebase to finish")
+        };
+        assert_eq!(emptied, [RebaseStep::pick(oids[1], "a")]);
+        assert_eq!(subjects(&t), ["c", "empty", "b", "a", "base"]);
+    }
+
+    #[test]
+    fn test_rebase_conflict_then_continue_skip_or_abort() {
+        let t = TestRepo::new();
+        t.write("f.txt", "base\n");
+        let base = t.commit_all("base");
+        t.write("f.txt", "one\n");
+        let one = t.commit_all("one");
+        t.write("f.txt", "two\n");
+        t.commit_all("two");
+
+        // Dropping "one" makes "two" conflict with "base".
+        let mut plan = t.repo.rebase_plan(one).unwrap();
+        assert_eq!(plan.onto, base);
+        plan.set_action(0, RebaseAction::Drop);
+        assert!(matches!(t.repo.rebase_start(&plan), Err(GitzError::MergeConflict)));
+        assert_eq!(t.repo.status().unwrap().section(StatusSection::Conflicts).len(), 1);
+        // The stop is recorded where `git rebase -i` records it.
+        assert!(t.path(".git/CHERRY_PICK_HEAD").exists());
+        assert_eq!(t.git().state(), git2::RepositoryState::RebaseInteractive);
+        let done = std::fs::read_to_string(t.path(".git/rebase-merge/done")).unwrap();
+        assert_eq!(done.lines().map(|l| l.split(' ').next().unwrap()).collect::<Vec<_>>(), ["drop", "pick"]);
+        assert!(std::fs::read_to_string(t.path(".git/rebase-merge/git-rebase-todo")).unwrap().is_empty());
+        assert!(matches!(t.repo.rebase_continue(), Err(GitzError::MergeConflict)));
+
+        t.write("f.txt", "resolved\n");
+        t.repo.add_all().unwrap();
+        assert!(matches!(t.repo.rebase_continue().unwrap(), RebaseOutcome::Finished { .. }));
+        assert_eq!(subjects(&t), ["two", "base"]);
+        assert_eq!(t.read("f.txt"), "resolved\n");
+        assert!(!t.path(".git/rebase-merge").exists());
+        assert_eq!(t.git().state(), git2::RepositoryState::Clean);
+
+        // Same conflict again, this time skipped.
+        let head = t.repo.recent_commits(1).unwrap().remove(0).oid;
+        let mut plan = t.repo.rebase_plan(head).unwrap();
+        plan.steps.insert(0, RebaseStep::pick(one, "one"));
+        plan.steps.reverse(); // two, one: "one" conflicts on top of "two"
+        assert!(matches!(t.repo.rebase_start(&plan), Err(GitzError::MergeConflict)));
+        assert!(matches!(t.repo.rebase_skip().unwrap(), RebaseOutcome::Finished { .. }));
+        assert_eq!(subjects(&t), ["two", "base"]);
+
+        // And aborted, which restores the original branch.
+        let before = t.repo.recent_commits(1).unwrap().remove(0).oid;
+        let mut plan = t.repo.rebase_plan(before).unwrap();
+        plan.steps.push(RebaseStep::pick(one, "one"));
+        assert!(matches!(t.repo.rebase_start(&plan), Err(GitzError::MergeConflict)));
+        t.repo.rebase_abort().unwrap();
+        assert_eq!(t.repo.recent_commits(1).unwrap()[0].oid, before);
+        assert!(t.repo.status().unwrap().is_clean());
+        assert!(t.repo.rebase_state().unwrap().is_none());
+        assert_eq!(t.git().state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_continue_with_corrupt_state_fails() {
+        let (t, oids) = linear_history();
+        let state = RebaseState {
+            head_name: None,
+            orig_head: oids[3],
+            onto: oids[2],
+            done: Vec::new(),
+            todo: Vec::new(),
+            stopped: Some(RebaseStop::Conflict),
+            emptied: Vec::new(),
+        };
+        std::fs::write(t.repo.git_dir().join("gitz-rebase.json"), serde_json::to_string(&state).unwrap()).unwrap();
+
+        let err = t.repo.rebase_continue().unwrap_err();
+        assert!(err.to_string().contains("rebase state is corrupt; run abort"));
+        t.repo.rebase_abort().unwrap();
+        assert_eq!(t.repo.head_oid(), Some(oids[3]));
+    }
+
+    #[test]
+    fn test_rebase_requires_clean_work_tree() {
+        let (t, oids) = linear_history();
+        t.write("a.txt", "dirty\n");
+        let plan = t.repo.rebase_plan(oids[1]).unwrap();
+        assert!(matches!(t.repo.rebase_start(&plan), Err(GitzError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,134 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use git2::{BranchType, Oid};
+
+/// One entry of a ref's reflog, `<ref>@{index}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+    pub index: usize,
+    /// Where the ref pointed before the operation; zero when it was created.
+    pub old: Oid,
+    /// Where the ref pointed after the operation.
+    pub new: Oid,
+    /// Full reflog message, e.g. `commit (amend): fix typo`.
+    pub message: String,
+    pub committer: String,
+    pub time: i64, // seconds since epoch
+}
+
+impl ReflogEntry {
+    /// The operation that moved the ref, e.g. `commit (amend)` or `rebase (finish)`.
+    pub fn operation(&self) -> &str {
+        self.message.split_once(": ").map_or(self.message.as_str(), |(op, _)| op)
+    }
+
+    /// Message after the operation, e.g. the commit summary.
+    pub fn description(&self) -> &str {
+        self.message.split_once(": ").map_or("", |(_, rest)| rest)
+    }
+
+    pub fn short_old(&self) -> String {
+        if self.old.is_zero() {
+            "0000000".to_string()
+        } else {
+            self.old.to_string()[..7].to_string()
+        }
+    }
+
+    pub fn short_new(&self) -> String {
+        self.new.to_string()[..7].to_string()
+    }
+}
+
+impl Repository {
+    /// Entries of a ref's reflog (`HEAD` or a full ref name), newest first.
+    pub fn reflog(&self, refname: &str) -> Result<Vec<ReflogEntry>, GitzError> {
+        let reflog = self.inner.reflog(refname)?;
+        Ok(reflog
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| ReflogEntry {
+                index,
+                old: entry.id_old(),
+                new: entry.id_new(),
+                message: entry.message().unwrap_or("").to_string(),
+                committer: entry.committer().name().unwrap_or("<unknown>").to_string(),
+                time: entry.committer().when().seconds(),
+            })
+            .collect())
+    }
+
+    /// Refs whose reflog can be browsed: `HEAD`, then every local branch.
+    pub fn reflog_refs(&self) -> Result<Vec<String>, GitzError> {
+        let mut branches = Vec::new();
+        for branch in self.inner.branches(Some(BranchType::Local))? {
+            let (branch, _) = branch?;
+            if let Some(name) = branch.get().name() {
+                branches.push(name.to_string());
+            }
+        }
+        branches.sort();
+        let mut refs = vec!["HEAD".to_string()];
+        refs.extend(branches);
+        Ok(refs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+    use crate::git::{HardResetChoice, ResetMode};
+
+    fn entry(message: &str) -> ReflogEntry {
+        ReflogEntry {
+            index: 0,
+            old: Oid::zero(),
+            new: Oid::zero(),
+            message: message.into(),
+            committer: "a".into(),
+            time: 0,
+        }
+    }
+
+    #[test]
+    fn test_operation_and_description() {
+        let amend = entry("commit (amend): fix typo");
+        assert_eq!((amend.operation(), amend.description()), ("commit (amend)", "fix typo"));
+        let bare = entry("fetch");
+        assert_eq!((bare.operation(), bare.description()), ("fetch", ""));
+        assert_eq!(bare.short_old(), "0000000");
+    }
+
+    #[test]
+    fn test_reflog_and_restore() {
+        let mut t = TestRepo::new();
+        t.write("f.txt", "one\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "two\n");
+        let second = t.commit_all("second");
+        let branch = t.repo.current_branch().unwrap();
+
+        let head = t.repo.reflog("HEAD").unwrap();
+        assert_eq!(head.len(), 2);
+        assert_eq!((head[0].old, head[0].new), (first, second));
+        assert_eq!(head[0].operation(), "commit");
+        assert_eq!(head[1].operation(), "commit (initial)");
+        let refs = t.repo.reflog_refs().unwrap();
+        assert_eq!(refs, ["HEAD".to_string(), format!("refs/heads/{}", branch)]);
+
+        t.repo.reset(first, ResetMode::Hard, &mut |_| HardResetChoice::Cancel).unwrap();
+        assert_eq!(t.read("f.txt"), "one\n");
+        assert_eq!(t.repo.current_branch().unwrap(), branch);
+        assert_eq!(t.repo.reflog("HEAD").unwrap()[0].operation(), "reset");
+
+        // The entry before the reset brings the lost commit back.
+        t.repo.checkout_detached(second).unwrap();
+        assert_eq!(t.repo.head_oid(), Some(second));
+        assert_eq!(t.read("f.txt"), "two\n");
+        t.repo.create_branch("rescued", second).unwrap();
+        assert!(t.repo.reflog("refs/heads/rescued").is_ok());
+    }
+}
@@ -0,0 +1,444 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use crate::utils::format_bytes;
+use git2::{BranchType, Oid};
+use git2::build::CheckoutBuilder;
+use std::cell::RefCell;
+
+/// A configured remote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteInfo {
+    pub name: String,
+    pub fetch_url: Option<String>,
+    /// Only set when it differs from the fetch URL (`remote.<name>.pushurl`).
+    pub push_url: Option<String>,
+}
+
+/// Progress reported by git2 while talking to a remote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteProgress {
+    /// Objects received and indexed while fetching.
+    Transfer { received: usize, indexed: usize, total: usize, bytes: usize },
+    /// Objects written while pushing.
+    Push { current: usize, total: usize, bytes: usize },
+    /// Text sent by the remote, e.g. `Counting objects: 100% (3/3)`.
+    Message(String),
+}
+
+impl RemoteProgress {
+    /// One-line description for the status bar.
+    pub fn describe(&self) -> String {
+        match self {
+            RemoteProgress::Transfer { received, indexed, total, bytes } => {
+                if received < total {
+                    format!("Receiving objects: {}% ({}/{}), {}", percent(*received, *total), received, total, format_bytes(*bytes))
+                } else {
+                    format!("Resolving objects: {}% ({}/{})", percent(*indexed, *total), indexed, total)
+                }
+            }
+            RemoteProgress::Push { current, total, bytes } => {
+                format!("Writing objects: {}% ({}/{}), {}", percent(*current, *total), current, total, format_bytes(*bytes))
+            }
+            RemoteProgress::Message(text) => text.trim().to_string(),
+        }
+    }
+}
+
+fn percent(done: usize, total: usize) -> usize {
+    (done * 100).checked_div(total).unwrap_or(100)
+}
+
+/// What a pull did to the current branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullOutcome {
+    UpToDate,
+    FastForward,
+    /// A merge commit was created.
+    Merged,
+}
+
+impl PullOutcome {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            PullOutcome::UpToDate => "Already up to date",
+            PullOutcome::FastForward => "Fast-forwarded",
+            PullOutcome::Merged => "Merged",
+        }
+    }
+}
+
+/// Progress callback shared between the git2 remote callbacks.
+type ProgressSink<'f> = RefCell<&'f mut dyn FnMut(RemoteProgress)>;
+
+/// Report authentication and non-fast-forward failures with dedicated errors.
+pub(super) fn network_error(e: git2::Error) -> GitzError {
+    match e.code() {
+        git2::ErrorCode::Auth => GitzError::AuthFailed,
+        git2::ErrorCode::NotFastForward => GitzError::GitOperationFailed(format!("push rejected: {}", e.message())),
+        _ => GitzError::Git(e),
+    }
+}
+
+impl Repository {
+    /// Configured remotes, sorted by name.
+    pub fn remotes(&self) -> Result<Vec<RemoteInfo>, GitzError> {
+        let mut remotes = Vec::new();
+        for name in self.inner.remotes()?.iter().flatten() {
+            let remote = self.inner.find_remote(name)?;
+            remotes.push(RemoteInfo {
+                name: name.to_string(),
+                fetch_url: remote.url().map(str::to_string),
+                push_url: remote.pushurl().map(str::to_string),
+            });
+        }
+        remotes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(remotes)
+    }
+
+    fn validate_remote_name(name: &str) -> Result<(), GitzError> {
+        if git2::Remote::is_valid_name(name) {
+            Ok(())
+        } else {
+            Err(GitzError::InvalidInput(format!("invalid remote name `{}`", name)))
+        }
+    }
+
+    /// Add a remote with the default fetch refspec.
+    pub fn add_remote(&self, name: &str, url: &str) -> Result<(), GitzError> {
+        Self::validate_remote_name(name)?;
+        if url.trim().is_empty() {
+            return Err(GitzError::InvalidInput("remote URL must not be empty".into()));
+        }
+        self.inner.remote(name, url.trim())?;
+        Ok(())
+    }
+
+    /// Rename a remote together with its remote-tracking branches.
+    pub fn rename_remote(&self, old: &str, new: &str) -> Result<(), GitzError> {
+        Self::validate_remote_name(new)?;
+        self.inner.remote_rename(old, new)?;
+        Ok(())
+    }
+
+    pub fn set_remote_url(&self, name: &str, url: &str) -> Result<(), GitzError> {
+        self.inner.remote_set_url(name, url.trim())?;
+        Ok(())
+    }
+
+    /// Remove a remote and its remote-tracking branches.
+    pub fn remove_remote(&self, name: &str) -> Result<(), GitzError> {
+        self.inner.remote_delete(name)?;
+        Ok(())
+    }
+
+    /// Callbacks shared by fetch and push: credentials and progress reporting.
+    pub(super) fn remote_callbacks<'a, 'f: 'a>(&self, progress: &'a ProgressSink<'f>) -> git2::RemoteCallbacks<'a> {
+        let mut callbacks = git2::RemoteCallbacks::new();
+        let config = self.inner.config().ok();
+        let mut attempts = 0;
+        callbacks.credentials(move |url, username, allowed| {
+            // libgit2 keeps asking as long as we hand out credentials; give up
+            // instead of looping on a key the server keeps refusing.
+            attempts += 1;
+            if attempts > 3 {
+                return Err(git2::Error::new(git2::ErrorCode::Auth, git2::ErrorClass::Net, "authentication failed"));
+            }
+            if allowed.contains(git2::CredentialType::SSH_KEY) {
+                return git2::Cred::ssh_key_from_agent(username.unwrap_or("git"));
+            }
+            if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+                if let Some(config) = &config {
+                    return git2::Cred::credential_helper(config, url, username);
+                }
+            }
+            if allowed.contains(git2::CredentialType::USERNAME) {
+                return git2::Cred::username(username.unwrap_or("git"));
+            }
+            git2::Cred::default()
+        });
+        callbacks.transfer_progress(move |stats| {
+            (progress.borrow_mut())(RemoteProgress::Transfer {
+                received: stats.received_objects(),
+                indexed: stats.indexed_objects(),
+                total: stats.total_objects(),
+                bytes: stats.received_bytes(),
+            });
+            true
+        });
+        callbacks.sideband_progress(move |data| {
+            let text = String::from_utf8_lossy(data);
+            // Progress lines are redrawn with `\r`; report the latest one.
+            if let Some(line) = text.split(['\r', '\n']).rfind(|l| !l.trim().is_empty()) {
+                (progress.borrow_mut())(RemoteProgress::Message(line.to_string()));
+            }
+            true
+        });
+        callbacks.push_transfer_progress(move |current, total, bytes| {
+            (progress.borrow_mut())(RemoteProgress::Push { current, total, bytes });
+        });
+        callbacks
+    }
+
+    /// Fetch from a remote using its configured refspecs.
+    pub fn fetch(&self, remote: &str, progress: &mut dyn FnMut(RemoteProgress)) -> Result<(), GitzError> {
+        let progress = RefCell::new(progress);
+        let mut remote = self.inner.find_remote(remote)?;
+        let mut opts = git2::FetchOptions::new();
+        opts.remote_callbacks(self.remote_callbacks(&progress));
+        remote.fetch::<&str>(&[], Some(&mut opts), None).map_err(network_error)?;
+        Ok(())
+    }
+
+    /// Fetch from `remote` and merge the current branch's counterpart into it:
+    /// its upstream when that lives on `remote`, else `<remote>/<branch>`.
+    pub fn pull(&self, remote: &str, progress: &mut dyn FnMut(RemoteProgress)) -> Result<PullOutcome, GitzError> {
+        let branch = self
+            .head_branch_name()
+            .ok_or_else(|| GitzError::InvalidInput("HEAD is detached; check out a branch to pull".into()))?;
+        self.fetch(remote, progress)?;
+
+        let local_ref = format!("refs/heads/{}", branch);
+        let upstream_remote = self.inner.branch_upstream_remote(&local_ref).ok();
+        let tracking_ref = match upstream_remote.as_ref().and_then(|r| r.as_str()) {
+            Some(name) if name == remote => self.inner.branch_upstream_name(&local_ref)?.as_str().map(str::to_string),
+            _ => None,
+        }
+        .unwrap_or_else(|| format!("refs/remotes/{}/{}", remote, branch));
+        let target = self
+            .inner
+            .refname_to_id(&tracking_ref)
+            .map_err(|_| GitzError::InvalidInput(format!("remote `{}` has no branch `{}`", remote, branch)))?;
+        let theirs = self.inner.find_annotated_commit(target)?;
+        let message = format!("Merge branch '{}' of {}", branch, remote);
+        self.merge_into_head(&branch, &theirs, &message)
+    }
+
+    /// Merge `theirs` into the checked out `branch`: fast-forward when possible,
+    /// otherwise create a merge commit. Conflicts are left in the index and
+    /// reported as `MergeConflict`.
+    fn merge_into_head(&self, branch: &str, theirs: &git2::AnnotatedCommit, message: &str) -> Result<PullOutcome, GitzError> {
+        let (analysis, _) = self.inner.merge_analysis(&[theirs])?;
+        if analysis.is_up_to_date() {
+            return Ok(PullOutcome::UpToDate);
+        }
+        if analysis.is_unborn() || analysis.is_fast_forward() {
+            self.fast_forward(branch, theirs.id())?;
+            return Ok(PullOutcome::FastForward);
+        }
+
+        let mut checkout = CheckoutBuilder::new();
+        checkout.safe().conflict_style_diff3(true);
+        self.inner.merge(&[theirs], None, Some(&mut checkout))?;
+        let mut index = self.inner.index()?;
+        if index.has_conflicts() {
+            return Err(GitzError::MergeConflict);
+        }
+        let tree = self.inner.find_tree(index.write_tree()?)?;
+        let sig = self.inner.signature()?;
+        let head = self.inner.head()?.peel_to_commit()?;
+        let their_commit = self.inner.find_commit(theirs.id())?;
+        self.inner.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&head, &their_commit])?;
+        self.inner.cleanup_state()?;
+        Ok(PullOutcome::Merged)
+    }
+
+    /// Move `branch` (checked out or unborn) to `target` and update the work tree.
+    pub(super) fn fast_forward(&self, branch: &str, target: Oid) -> Result<(), GitzError> {
+        let commit = self.inner.find_commit(target)?;
+        let mut checkout = CheckoutBuilder::new();
+        checkout.safe();
+        self.inner.checkout_tree(commit.as_object(), Some(&mut checkout))?;
+        let refname = format!("refs/heads/{}", branch);
+        self.inner.reference(&refname, target, true, &format!("merge {}: Fast-forward", target))?;
+        self.inner.set_head(&refname)?;
+        Ok(())
+    }
+
+    /// Remote a push goes to by default: the current branch's upstream
+    /// remote, else `origin`.
+    pub fn default_push_remote(&self) -> String {
+        self.head_branch_name()
+            .and_then(|branch| self.inner.branch_upstream_remote(&format!("refs/heads/{}", branch)).ok())
+            .and_then(|remote| remote.as_str().map(str::to_string))
+            .unwrap_or_else(|| "origin".to_string())
+    }
+
+    /// Push the current branch to the branch of the same name on `remote`.
+    /// Sets the upstream on the first push.
+    pub fn push(&self, remote: &str, progress: &mut dyn FnMut(RemoteProgress)) -> Result<(), GitzError> {
+        let branch = self
+            .head_branch_name()
+            .ok_or_else(|| GitzError::InvalidInput("HEAD is detached; check out a branch to push".into()))?;
+        let refspec = format!("refs/heads/{0}:refs/heads/{0}", branch);
+        self.push_refspec(remote, &refspec, progress)?;
+
+        let mut local = self.inner.find_branch(&branch, BranchType::Local)?;
+        let tracking = format!("{}/{}", remote, branch);
+        if local.upstream().is_err() && self.inner.find_branch(&tracking, BranchType::Remote).is_ok() {
+            local.set_upstream(Some(&tracking))?;
+        }
+        Ok(())
+    }
+
+    /// Push one refspec, failing if the remote rejects the update.
+    pub(super) fn push_refspec(&self, remote: &str, refspec: &str, progress: &mut dyn FnMut(RemoteProgress)) -> Result<(), GitzError> {
+        let progress = RefCell::new(progress);
+        let rejected = RefCell::new(Vec::new());
+        {
+            let mut callbacks = self.remote_callbacks(&progress);
+            callbacks.push_update_reference(|refname, status| {
+                if let Some(reason) = status {
+                    rejected.borrow_mut().push(format!("{} ({})", refname, reason));
+                }
+                Ok(())
+            });
+            let mut opts = git2::PushOptions::new();
+            opts.remote_callbacks(callbacks);
+            let mut handle = self.inner.find_remote(remote)?;
+            handle.push(&[refspec], Some(&mut opts)).map_err(network_error)?;
+        }
+        let rejected = rejected.into_inner();
+        if !rejected.is_empty() {
+            return Err(GitzError::GitOperationFailed(format!("push rejected: {}", rejected.join(", "))));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::GitzError;
+    use crate::git::testing::TestRepo;
+    use tempfile::TempDir;
+
+    /// A bare repository reachable over `file://`.
+    fn bare_remote() -> (TempDir, String) {
+        let dir = TempDir::new().unwrap();
+        git2::Repository::init_bare(dir.path()).unwrap();
+        let url = format!("file://{}", dir.path().display());
+        (dir, url)
+    }
+
+    /// An empty repository with `url` added as `origin` and fetched.
+    fn clone_of(url: &str) -> TestRepo {
+        let t = TestRepo::new();
+        let git = t.git();
+        git.remote("origin", url).unwrap();
+        drop(git);
+        let mut progress = |_| {};
+        t.repo.fetch("origin", &mut progress).unwrap();
+        t
+    }
+
+    #[test]
+    fn test_progress_description() {
+        let transfer = RemoteProgress::Transfer { received: 5, indexed: 0, total: 10, bytes: 2048 };
+        assert_eq!(transfer.describe(), "Receiving objects: 50% (5/10), 2.0 KiB");
+        let resolving = RemoteProgress::Transfer { received: 10, indexed: 10, total: 10, bytes: 2048 };
+        assert_eq!(resolving.describe(), "Resolving objects: 100% (10/10)");
+        let push = RemoteProgress::Push { current: 0, total: 0, bytes: 0 };
+        assert_eq!(push.describe(), "Writing objects: 100% (0/0), 0 B");
+    }
+
+    #[test]
+    fn test_manage_remotes() {
+        let t = TestRepo::new();
+        t.repo.add_remote("origin", "https://example.com/a.git").unwrap();
+        t.repo.add_remote("backup", "https://example.com/b.git").unwrap();
+        assert!(matches!(t.repo.add_remote("bad name", "x"), Err(GitzError::InvalidInput(_))));
+
+        t.repo.rename_remote("backup", "mirror").unwrap();
+        t.repo.set_remote_url("mirror", "https://example.com/c.git").unwrap();
+        let remotes = t.repo.remotes().unwrap();
+        let names: Vec<_> = remotes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["mirror", "origin"]);
+        assert_eq!(remotes[0].fetch_url.as_deref(), Some("https://example.com/c.git"));
+        assert_eq!(remotes[0].push_url, None);
+
+        t.repo.remove_remote("mirror").unwrap();
+        assert_eq!(t.repo.remotes().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_push_fetch_and_pull_over_file_url() {
+        let (_bare, url) = bare_remote();
+        let a = TestRepo::new();
+        a.repo.add_remote("origin", &url).unwrap();
+        a.write("f.txt", "one\n");
+        a.commit_all("one");
+        let branch = a.repo.current_branch().unwrap();
+
+        let mut events = Vec::new();
+        a.repo.push("origin", &mut |p| events.push(p)).unwrap();
+        assert!(events.iter().any(|p| matches!(p, RemoteProgress::Push { .. })));
+        // The first push sets the upstream.
+        let upstream = a.repo.branches().unwrap().into_iter().find(|b| b.is_head).unwrap().upstream;
+        assert_eq!(upstream, Some(format!("origin/{}", branch)));
+
+        // A second clone pulls into an unborn branch, then pushes a new commit.
+        let b = clone_of(&url);
+        let mut ignore = |_| {};
+        assert_eq!(b.repo.pull("origin", &mut ignore).unwrap(), PullOutcome::FastForward);
+        assert_eq!(b.read("f.txt"), "one\n");
+        b.write("f.txt", "two\n");
+        b.commit_all("two");
+        b.repo.push("origin", &mut ignore).unwrap();
+
+        assert_eq!(a.repo.pull("origin", &mut ignore).unwrap(), PullOutcome::FastForward);
+        assert_eq!(a.read("f.txt"), "two\n");
+        assert_eq!(a.repo.pull("origin", &mut ignore).unwrap(), PullOutcome::UpToDate);
+    }
+
+    #[test]
+    fn test_pull_merges_diverged_history_and_push_is_rejected() {
+        let (_bare, url) = bare_remote();
+        let a = TestRepo::new();
+        a.repo.add_remote("origin", &url).unwrap();
+        a.write("a.txt", "base\n");
+        a.commit_all("base");
+        let mut ignore = |_| {};
+        a.repo.push("origin", &mut ignore).unwrap();
+
+        let b = clone_of(&url);
+        b.repo.pull("origin", &mut ignore).unwrap();
+        b.write("b.txt", "from b\n");
+        b.commit_all("b");
+        b.repo.push("origin", &mut ignore).unwrap();
+
+        a.write("a.txt", "from a\n");
+        a.commit_all("a");
+        assert!(matches!(a.repo.push("origin", &mut ignore), Err(GitzError::GitOperationFailed(_))));
+
+        assert_eq!(a.repo.pull("origin", &mut ignore).unwrap(), PullOutcome::Merged);
+        assert_eq!(a.read("b.txt"), "from b\n");
+        assert_eq!(a.read("a.txt"), "from a\n");
+        let head = a.repo.recent_commits(1).unwrap().remove(0);
+        assert!(head.is_merge());
+        a.repo.push("origin", &mut ignore).unwrap();
+    }
+
+    #[test]
+    fn test_pull_stops_on_conflict() {
+        let (_bare, url) = bare_remote();
+        let a = TestRepo::new();
+        a.repo.add_remote("origin", &url).unwrap();
+        a.write("f.txt", "base\n");
+        a.commit_all("base");
+        let mut ignore = |_| {};
+        a.repo.push("origin", &mut ignore).unwrap();
+
+        let b = clone_of(&url);
+        b.repo.pull("origin", &mut ignore).unwrap();
+        b.write("f.txt", "theirs\n");
+        b.commit_all("theirs");
+        b.repo.push("origin", &mut ignore).unwrap();
+
+        a.write("f.txt", "ours\n");
+        a.commit_all("ours");
+        assert!(matches!(a.repo.pull("origin", &mut ignore), Err(GitzError::MergeConflict)));
+        assert_eq!(a.repo.status().unwrap().section(crate::git::StatusSection::Conflicts).len(), 1);
+    }
+}
@@ -1,34 +1,86 @@
-#![allow(dead_code)]
-
 use crate::errors::GitzError;
-use crate::git::{RepoStatus, CommitInfo};
+use crate::git::{RepoStatus, StatusSection, CommitInfo, ChangeKind, FileChange, FileStatus};
 use git2::{Repository as Git2Repo, StatusOptions, Oid};
-use std::path::PathBuf;
+use git2::build::CheckoutBuilder;
+use std::path::{Path, PathBuf};
 
 /// Wrapper around `git2::Repository` providing high‑level helpers.
 pub struct Repository {
-    inner: Git2Repo,
-    path: PathBuf,
+    pub(super) inner: Git2Repo,
+    /// The `.git` directory (for a bare repository, the repository itself).
+    pub(super) git_dir: PathBuf,
+    /// The checked-out files; `None` for a bare repository.
+    workdir: Option<PathBuf>,
 }
 
 impl Repository {
+    fn wrap(repo: Git2Repo) -> Self {
+        let git_dir = repo.path().to_path_buf();
+        let workdir = repo.workdir().map(Path::to_path_buf);
+        Self { inner: repo, git_dir, workdir }
+    }
+
     /// Open an existing repository.
     pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, GitzError> {
-        let repo = Git2Repo::open(path.as_ref())?;
-        let path = repo.path().to_path_buf();
-        Ok(Self { inner: repo, path })
+        Ok(Self::wrap(Git2Repo::open(path.as_ref())?))
+    }
+
+    /// Find the repository containing `start`, like git does: search
+    /// upward for a `.git` directory or file (linked work trees), honouring
+    /// `GIT_DIR` and `GIT_WORK_TREE` when set.
+    pub fn discover<P: AsRef<Path>>(start: P) -> Result<Self, GitzError> {
+        let git_dir = std::env::var_os("GIT_DIR").map(PathBuf::from);
+        let work_tree = std::env::var_os("GIT_WORK_TREE").map(PathBuf::from);
+        Self::discover_with(start.as_ref(), git_dir.as_deref(), work_tree.as_deref())
+    }
+
+    /// `discover` with the environment passed in.
+    fn discover_with(start: &Path, git_dir: Option<&Path>, work_tree: Option<&Path>) -> Result<Self, GitzError> {
+        let not_found = |e: git2::Error| match e.code() {
+            git2::ErrorCode::NotFound => GitzError::RepoNotFound(start.display().to_string()),
+            _ => GitzError::Git(e),
+        };
+        let repo = match git_dir {
+            Some(dir) => Git2Repo::open_ext(dir, git2::RepositoryOpenFlags::NO_SEARCH, std::iter::empty::<&Path>()),
+            None => Git2Repo::discover(start),
+        }
+        .map_err(not_found)?;
+        match (work_tree, git_dir) {
+            (Some(work_tree), _) => repo.set_workdir(work_tree, false)?,
+            // As with git, an explicit GIT_DIR makes the current directory the
+            // top of the work tree unless the repository says otherwise.
+            (None, Some(_)) if !repo.is_bare() && repo.config()?.get_path("core.worktree").is_err() => {
+                repo.set_workdir(start, false)?;
+            }
+            _ => {}
+        }
+        Ok(Self::wrap(repo))
     }
 
     /// Initialise a new repository.
     pub fn init<P: AsRef<std::path::Path>>(path: P) -> Result<Self, GitzError> {
-        let repo = Git2Repo::init(path.as_ref())?;
-        let path = repo.path().to_path_buf();
-        Ok(Self { inner: repo, path })
+        Ok(Self::wrap(Git2Repo::init(path.as_ref())?))
     }
 
-    /// Absolute path to the repository root.
-    pub fn path(&self) -> &PathBuf {
-        &self.path
+    /// The git directory: `.git` in a work tree, the repository itself when bare.
+    pub fn git_dir(&self) -> &Path {
+        &self.git_dir
+    }
+
+    /// The work tree; `None` for a bare repository.
+    pub fn workdir(&self) -> Option<&Path> {
+        self.workdir.as_deref()
+    }
+
+    /// Whether there is no work tree, so only history and refs can be browsed.
+    pub fn is_bare(&self) -> bool {
+        self.workdir.is_none()
+    }
+
+    /// Where the repository lives for display: the work tree, or the git
+    /// directory of a bare repository.
+    pub fn root(&self) -> &Path {
+        self.workdir().unwrap_or(&self.git_dir)
     }
 
     /// Current branch name (or detached HEAD).
@@ -43,79 +95,252 @@ impl Repository {
 
     /// Stage all changes (equivalent to `git add .`).
     pub fn add_all(&self) -> Result<(), GitzError> {
+        self.add_pathspecs(&["*"])
+    }
+
+    /// Stage new, modified and deleted files matching `pathspecs` (paths
+    /// relative to the work tree root; a directory matches everything in it).
+    pub fn add_pathspecs(&self, pathspecs: &[&str]) -> Result<(), GitzError> {
+        self.workdir_or_err()?;
+        let mut index = self.inner.index()?;
+        // Pick up entries written by other tools (e.g. a new submodule's gitlink)
+        // so nested repositories are not mistaken for untracked directories.
+        index.read(false)?;
+        index.add_all(pathspecs.iter(), git2::IndexAddOption::DEFAULT, None)?;
+        index.update_all(pathspecs.iter(), None)?;
+        index.write()?;
+        Ok(())
+    }
+
+    /// Stage a single path, recording a deletion if it is gone from the work tree.
+    pub fn stage_path(&self, path: &str) -> Result<(), GitzError> {
+        let workdir = self.workdir_or_err()?;
         let mut index = self.inner.index()?;
-        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+        if workdir.join(path).symlink_metadata().is_ok() {
+            index.add_path(Path::new(path))?;
+        } else {
+            index.remove_path(Path::new(path))?;
+        }
         index.write()?;
         Ok(())
     }
 
+    /// Reset a path's index entry to HEAD, or drop it when HEAD is unborn.
+    pub fn unstage_path(&self, path: &str) -> Result<(), GitzError> {
+        match self.inner.head() {
+            Ok(head) => {
+                let commit = head.peel_to_commit()?;
+                self.inner.reset_default(Some(commit.as_object()), [path])?;
+            }
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+                let mut index = self.inner.index()?;
+                index.remove_path(Path::new(path))?;
+                index.write()?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+        Ok(())
+    }
+
+    /// Throw away work tree changes to a path: restore it from the index, or
+    /// delete it if it is untracked.
+    pub fn discard_path(&self, path: &str) -> Result<(), GitzError> {
+        let workdir = self.workdir_or_err()?;
+        let index = self.inner.index()?;
+        if index.get_path(Path::new(path), 0).is_some() {
+            let mut checkout = CheckoutBuilder::new();
+            checkout.force().path(path);
+            self.inner.checkout_index(None, Some(&mut checkout))?;
+        } else {
+            let full = workdir.join(path);
+            if full.is_dir() {
+                std::fs::remove_dir_all(full)?;
+            } else {
+                std::fs::remove_file(full)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn workdir_or_err(&self) -> Result<&Path, GitzError> {
+        self.workdir()
+            .ok_or_else(|| GitzError::GitOperationFailed("Repository has no work tree".into()))
+    }
+
     /// Create a commit with the given message.
     pub fn commit(&self, message: &str) -> Result<Oid, GitzError> {
         let sig = self.inner.signature()?;
         let mut index = self.inner.index()?;
         let tree_id = index.write_tree()?;
         let tree = self.inner.find_tree(tree_id)?;
-        let parent = self.inner.head()?.peel_to_commit()?;
+        // An unborn HEAD (fresh repository) produces a root commit.
+        let parent = match self.inner.head() {
+            Ok(head) => Some(head.peel_to_commit()?),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
+            Err(e) => return Err(e.into()),
+        };
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
         let oid = self.inner.commit(
             Some("HEAD"),
             &sig,
             &sig,
             message,
             &tree,
-            &[&parent],
+            &parents,
         )?;
         Ok(oid)
     }
 
-    /// Get a short status (modified, added, deleted files).
+    /// Tree of the commit HEAD points to, or `None` on an unborn branch.
+    pub(super) fn head_tree(&self) -> Result<Option<git2::Tree<'_>>, GitzError> {
+        match self.inner.head() {
+            Ok(head) => Ok(Some(head.peel_to_tree()?)),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Per-file index and work tree status, including renames and conflicts.
     pub fn status(&self) -> Result<RepoStatus, GitzError> {
         let mut opts = StatusOptions::new();
-        opts.include_untracked(true).recurse_untracked_dirs(true);
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .renames_head_to_index(true)
+            .renames_index_to_workdir(true);
         let statuses = self.inner.statuses(Some(&mut opts))?;
-        let mut modified = Vec::new();
-        let mut added = Vec::new();
-        let mut deleted = Vec::new();
+        let conflicts = self.conflict_stages()?;
+        let submodules: Vec<PathBuf> = self.inner.submodules()?.iter().map(|sm| sm.path().to_path_buf()).collect();
+
+        let mut files = Vec::new();
         for entry in statuses.iter() {
-            let path = entry.path().unwrap_or("<unknown>").to_string();
             let s = entry.status();
-            if s.is_index_new() || s.is_wt_new() {
-                added.push(path);
-            } else if s.is_index_modified() || s.is_wt_modified() {
-                modified.push(path);
-            } else if s.is_index_deleted() || s.is_wt_deleted() {
-                deleted.push(path);
+            let path_of = |delta: Option<git2::DiffDelta<'_>>, new: bool| {
+                delta.and_then(|d| {
+                    let file = if new { d.new_file() } else { d.old_file() };
+                    file.path().map(|p| p.to_string_lossy().into_owned())
+                })
+            };
+
+            let index = if s.is_index_new() {
+                Some(FileChange::new(ChangeKind::Added))
+            } else if s.is_index_modified() {
+                Some(FileChange::new(ChangeKind::Modified))
+            } else if s.is_index_deleted() {
+                Some(FileChange::new(ChangeKind::Deleted))
+            } else if s.is_index_renamed() {
+                let from = path_of(entry.head_to_index(), false).unwrap_or_default();
+                Some(FileChange::renamed(from))
+            } else if s.is_index_typechange() {
+                Some(FileChange::new(ChangeKind::Typechange))
+            } else {
+                None
+            };
+
+            let worktree = if s.is_wt_new() {
+                Some(FileChange::new(ChangeKind::Untracked))
+            } else if s.is_wt_modified() {
+                Some(FileChange::new(ChangeKind::Modified))
+            } else if s.is_wt_deleted() {
+                Some(FileChange::new(ChangeKind::Deleted))
+            } else if s.is_wt_renamed() {
+                let from = path_of(entry.index_to_workdir(), false).unwrap_or_default();
+                Some(FileChange::renamed(from))
+            } else if s.is_wt_typechange() {
+                Some(FileChange::new(ChangeKind::Typechange))
+            } else {
+                None
+            };
+
+            // Renamed entries are keyed by their new location.
+            let path = if s.is_wt_renamed() {
+                path_of(entry.index_to_workdir(), true)
+            } else if s.is_index_renamed() {
+                path_of(entry.head_to_index(), true)
+            } else {
+                None
             }
+            .or_else(|| entry.path().map(str::to_string))
+            .unwrap_or_else(|| "<unknown>".to_string());
+
+            let conflict = if s.is_conflicted() {
+                Some(conflicts.get(&path).copied().unwrap_or_default())
+            } else {
+                None
+            };
+
+            if index.is_none() && worktree.is_none() && conflict.is_none() {
+                continue;
+            }
+            let submodule = submodules.iter().any(|p| Path::new(&path) == p);
+            files.push(FileStatus { path, index, worktree, conflict, submodule });
         }
-        Ok(RepoStatus { modified, added, deleted })
+        Ok(RepoStatus { files })
     }
 
     /// Retrieve the last N commits (default 20).
     pub fn recent_commits(&self, n: usize) -> Result<Vec<CommitInfo>, GitzError> {
-        let mut revwalk = self.inner.revwalk()?;
-        revwalk.push_head()?;
-        revwalk.set_sorting(git2::Sort::TIME)?;
-        let mut commits = Vec::new();
-        for oid_result in revwalk.take(n) {
-            let oid = oid_result?;
-            let commit = self.inner.find_commit(oid)?;
-            commits.push(CommitInfo {
-                oid,
-                message: commit.message().unwrap_or("<no message>").to_string(),
-                author: commit.author().name().unwrap_or("<unknown>").to_string(),
-                time: commit.time().seconds(),
-            });
+        let mut cursor = self.log_cursor(None)?;
+        self.log_next(&mut cursor, n)
+    }
+
+    /// Refuse to start an operation while a rebase or sequence is paused.
+    pub(super) fn ensure_idle(&self) -> Result<(), GitzError> {
+        if self.rebase_state()?.is_some() {
+            return Err(GitzError::InvalidInput("A rebase is already in progress".into()));
+        }
+        if let Some(state) = self.sequence_state()? {
+            return Err(GitzError::InvalidInput(format!("A {} is already in progress", state.kind.label())));
+        }
+        if self.bisect_state()?.is_some() {
+            return Err(GitzError::InvalidInput("A bisect is already in progress".into()));
         }
-        Ok(commits)
+        // Operations started with git itself.
+        match self.inner.state() {
+            git2::RepositoryState::Clean => Ok(()),
+            state => Err(GitzError::InvalidInput(format!("A git operation is already in progress ({:?})", state))),
+        }
+    }
+
+    /// Remove what libgit2's cherry-pick and revert record about the commit
+    /// being applied. Unlike `cleanup_state`, this leaves `rebase-merge/`
+    /// and `sequencer/` alone.
+    pub(super) fn clear_pick_head(&self) -> Result<(), GitzError> {
+        for file in ["CHERRY_PICK_HEAD", "REVERT_HEAD", "MERGE_MSG"] {
+            match std::fs::remove_file(self.inner.path().join(file)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether tracked files have staged, unstaged or conflicting changes.
+    pub(super) fn has_tracked_changes(&self) -> Result<bool, GitzError> {
+        let status = self.status()?;
+        Ok([StatusSection::Staged, StatusSection::Unstaged, StatusSection::Conflicts]
+            .iter()
+            .any(|s| !status.section(*s).is_empty()))
+    }
+
+    /// Short name of the branch HEAD points at, also when it is unborn.
+    /// `None` when HEAD is detached.
+    pub(super) fn head_branch_name(&self) -> Option<String> {
+        let head = self.inner.find_reference("HEAD").ok()?;
+        let target = head.symbolic_target()?;
+        target.strip_prefix("refs/heads/").map(str::to_string)
+    }
+
+    /// The commit HEAD points at; `None` on an unborn branch.
+    pub fn head_oid(&self) -> Option<Oid> {
+        self.inner.head().ok().and_then(|head| head.target())
     }
 
     /// List all worktrees in the repository.
     pub fn list_worktrees(&self) -> Result<Vec<String>, GitzError> {
         let worktrees = self.inner.worktrees()?;
         let mut names = Vec::new();
-        for name in worktrees.iter() {
-            if let Some(name) = name {
-                names.push(name.to_string());
-            }
+        for name in worktrees.iter().flatten() {
+            names.push(name.to_string());
         }
         Ok(names)
     }
@@ -124,7 +349,9 @@ impl Repository {
     pub fn create_worktree<P: AsRef<std::path::Path>>(&self, path: P, branch: &str) -> Result<(), GitzError> {
         let branch_ref = self.inner.find_branch(branch, git2::BranchType::Local)?;
         let _commit = branch_ref.get().peel_to_commit()?;
-        self.inner.worktree(branch, path.as_ref(), Some(&git2::WorktreeAddOptions::new()))?;
+        let mut opts = git2::WorktreeAddOptions::new();
+        opts.reference(Some(branch_ref.get()));
+        self.inner.worktree(branch, path.as_ref(), Some(&opts))?;
         Ok(())
     }
 
@@ -136,7 +363,14 @@ impl Repository {
 
 impl Clone for Repository {
     fn clone(&self) -> Self {
-        Self::open(&self.path).expect("Failed to clone repository")
+        let inner = Git2Repo::open(&self.git_dir).expect("Failed to clone repository");
+        // Keep a work tree set from GIT_WORK_TREE.
+        if let Some(workdir) = &self.workdir {
+            if inner.workdir() != Some(workdir.as_path()) {
+                inner.set_workdir(workdir, false).expect("Failed to clone repository");
+            }
+        }
+        Self::wrap(inner)
     }
 }
 
@@ -144,7 +378,83 @@ impl Clone for Repository {
 impl std::fmt::Debug for Repository {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Repository")
-            .field("path", &self.path)
+            .field("git_dir", &self.git_dir)
+            .field("workdir", &self.workdir)
             .finish()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+    use tempfile::TempDir;
+
+    fn canonical(path: &Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap()
+    }
+
+    #[test]
+    fn test_discover_searches_upward() {
+        let t = TestRepo::new();
+        t.write("src/deep/f.txt", "x\n");
+        let repo = Repository::discover(t.path("src/deep")).unwrap();
+        assert_eq!(canonical(repo.workdir().unwrap()), canonical(t.root()));
+        assert_eq!(canonical(repo.git_dir()), canonical(&t.path(".git")));
+
+        let outside = TempDir::new().unwrap();
+        assert!(matches!(Repository::discover(outside.path()), Err(GitzError::RepoNotFound(_))));
+    }
+
+    #[test]
+    fn test_discover_linked_worktree() {
+        let t = TestRepo::new();
+        t.write("f.txt", "x\n");
+        let head = t.commit_all("initial");
+        t.repo.create_branch("topic", head).unwrap();
+        let dir = TempDir::new().unwrap();
+        let linked = dir.path().join("topic");
+        t.repo.create_worktree(&linked, "topic").unwrap();
+        std::fs::create_dir(linked.join("sub")).unwrap();
+
+        let repo = Repository::discover(linked.join("sub")).unwrap();
+        assert_eq!(canonical(repo.workdir().unwrap()), canonical(&linked));
+        assert_eq!(repo.current_branch().unwrap(), "topic");
+    }
+
+    #[test]
+    fn test_bare_repository_is_browse_only() {
+        let t = TestRepo::new();
+        t.write("src/f.txt", "x\n");
+        let head = t.commit_all("initial");
+        t.repo.create_tag("v1", head, None).unwrap();
+        let dir = TempDir::new().unwrap();
+        let url = format!("file://{}", t.root().display());
+        git2::build::RepoBuilder::new().bare(true).clone(&url, &dir.path().join("mirror.git")).unwrap();
+
+        let repo = Repository::discover(dir.path().join("mirror.git")).unwrap();
+        assert!(repo.is_bare());
+        assert_eq!(repo.workdir(), None);
+        assert_eq!(repo.root(), repo.git_dir());
+        assert_eq!(repo.recent_commits(10).unwrap()[0].oid, head);
+        assert!(!repo.branches().unwrap().is_empty());
+        assert_eq!(repo.tags().unwrap()[0].name, "v1");
+        assert_eq!(repo.commit_diff(head, 3).unwrap()[0].path(), "src/f.txt");
+        assert_eq!(repo.blame("src/f.txt", None).unwrap().lines.len(), 1);
+        assert!(repo.status().is_err());
+        assert!(repo.clone().is_bare());
+    }
+
+    #[test]
+    fn test_discover_with_git_dir_and_work_tree() {
+        let t = TestRepo::new();
+        let elsewhere = TempDir::new().unwrap();
+        let git_dir = t.path(".git");
+
+        let repo = Repository::discover_with(elsewhere.path(), Some(&git_dir), Some(t.root())).unwrap();
+        assert_eq!(canonical(repo.workdir().unwrap()), canonical(t.root()));
+        // Without GIT_WORK_TREE the current directory is the work tree.
+        let repo = Repository::discover_with(elsewhere.path(), Some(&git_dir), None).unwrap();
+        assert_eq!(canonical(repo.workdir().unwrap()), canonical(elsewhere.path()));
+        assert_eq!(canonical(repo.clone().workdir().unwrap()), canonical(elsewhere.path()));
+    }
+}
@@ -0,0 +1,184 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::{Repository, StashOptions, StatusEntry, StatusSection};
+use git2::Oid;
+use std::path::Path;
+
+/// How far `Repository::reset` moves state along with the branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetMode {
+    /// Move the branch only; the old commits' changes stay staged.
+    Soft,
+    /// Move the branch and the index; changes stay in the work tree.
+    Mixed,
+    /// Move the branch, the index and the work tree.
+    Hard,
+}
+
+impl ResetMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResetMode::Soft => "soft",
+            ResetMode::Mixed => "mixed",
+            ResetMode::Hard => "hard",
+        }
+    }
+
+    pub(crate) fn git2(&self) -> git2::ResetType {
+        match self {
+            ResetMode::Soft => git2::ResetType::Soft,
+            ResetMode::Mixed => git2::ResetType::Mixed,
+            ResetMode::Hard => git2::ResetType::Hard,
+        }
+    }
+}
+
+/// Answer to a hard reset that would throw away uncommitted changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardResetChoice {
+    /// Stash the changes (untracked files the reset would overwrite
+    /// included), then reset.
+    StashFirst,
+    /// Reset and lose the changes.
+    Discard,
+    /// Leave everything as it is.
+    Cancel,
+}
+
+impl Repository {
+    /// Uncommitted changes a hard reset to `target` would throw away: every
+    /// staged, unstaged or conflicted file, and untracked files that `target`
+    /// would overwrite.
+    pub fn reset_losses(&self, target: Oid) -> Result<Vec<StatusEntry>, GitzError> {
+        let tree = self.inner.find_commit(target)?.tree()?;
+        Ok(self
+            .status()?
+            .entries()
+            .into_iter()
+            .filter(|e| e.section != StatusSection::Untracked || tree.get_path(Path::new(&e.path)).is_ok())
+            .collect())
+    }
+
+    /// Move the current branch (or detached HEAD) to `target`. A hard reset
+    /// that would lose changes passes them to `confirm` first, and is
+    /// `Cancelled` unless it answers to stash or discard them. Returns the
+    /// stash created on the way.
+    pub fn reset(
+        &mut self,
+        target: Oid,
+        mode: ResetMode,
+        confirm: &mut dyn FnMut(&[StatusEntry]) -> HardResetChoice,
+    ) -> Result<Option<Oid>, GitzError> {
+        let mut stash = None;
+        if mode == ResetMode::Hard {
+            let losses = self.reset_losses(target)?;
+            if !losses.is_empty() {
+                match confirm(&losses) {
+                    HardResetChoice::Cancel => return Err(GitzError::Cancelled),
+                    HardResetChoice::Discard => {}
+                    HardResetChoice::StashFirst => {
+                        // Stash only what would be lost. libgit2 stashes and removes
+                        // every untracked file when asked for any, even with a
+                        // pathspec, so the ones the reset overwrites are staged instead.
+                        for entry in losses.iter().filter(|e| e.section == StatusSection::Untracked) {
+                            self.stage_path(&entry.path)?;
+                        }
+                        let opts = StashOptions {
+                            message: Some(format!("gitz: before reset to {}", &target.to_string()[..7])),
+                            include_untracked: false,
+                            keep_index: false,
+                        };
+                        stash = Some(self.stash_save(&opts)?);
+                    }
+                }
+            }
+        }
+        let commit = self.inner.find_commit(target)?;
+        self.inner.reset(commit.as_object(), mode.git2(), None)?;
+        Ok(stash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::GitzError;
+    use crate::git::testing::TestRepo;
+    use crate::git::StatusSection;
+
+    #[test]
+    fn test_soft_and_mixed_keep_changes() {
+        let mut t = TestRepo::new();
+        t.write("f.txt", "one\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "two\n");
+        t.commit_all("second");
+        let mut never = |_: &[_]| -> HardResetChoice { panic!("no confirmation needed") };
+
+        t.repo.reset(first, ResetMode::Soft, &mut never).unwrap();
+        assert_eq!(t.repo.head_oid(), Some(first));
+        assert_eq!(t.index_contents("f.txt").unwrap(), "two\n");
+
+        t.repo.reset(first, ResetMode::Mixed, &mut never).unwrap();
+        assert_eq!(t.index_contents("f.txt").unwrap(), "one\n");
+        assert_eq!(t.read("f.txt"), "two\n");
+    }
+
+    #[test]
+    fn test_hard_reset_lists_losses_and_asks() {
+        let mut t = TestRepo::new();
+        t.write("f.txt", "one\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "two\n");
+        t.write("new.txt", "x\n");
+        t.commit_all("second");
+        t.write("f.txt", "dirty\n");
+        std::fs::remove_file(t.path("new.txt")).unwrap();
+        t.write("notes.txt", "keep\n");
+
+        // The untracked file is not in `first`, so reset leaves it alone.
+        let losses = t.repo.reset_losses(first).unwrap();
+        let listed: Vec<(&str, StatusSection)> = losses.iter().map(|e| (e.path.as_str(), e.section)).collect();
+        assert_eq!(listed, [("f.txt", StatusSection::Unstaged), ("new.txt", StatusSection::Unstaged)]);
+
+        let mut seen = 0;
+        let mut cancel = |l: &[_]| {
+            seen = l.len();
+            HardResetChoice::Cancel
+        };
+        assert!(matches!(t.repo.reset(first, ResetMode::Hard, &mut cancel), Err(GitzError::Cancelled)));
+        assert_eq!(seen, 2);
+        assert_eq!(t.read("f.txt"), "dirty\n");
+
+        let stash = t.repo.reset(first, ResetMode::Hard, &mut |_| HardResetChoice::StashFirst).unwrap();
+        assert!(stash.is_some());
+        assert_eq!(t.read("f.txt"), "one\n");
+        assert_eq!(t.read("notes.txt"), "keep\n");
+        assert_eq!(t.repo.stashes().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_stash_first_only_stashes_what_would_be_lost() {
+        let mut t = TestRepo::new();
+        t.write("f.txt", "one\n");
+        t.write("g.txt", "g\n");
+        let first = t.commit_all("first");
+        std::fs::remove_file(t.path("g.txt")).unwrap();
+        t.commit_all("second");
+        // The reset brings g.txt back over the untracked copy; notes.txt is unrelated.
+        t.write("g.txt", "mine\n");
+        t.write("notes.txt", "keep\n");
+        let losses = t.repo.reset_losses(first).unwrap();
+        assert_eq!(losses.iter().map(|e| e.path.as_str()).collect::<Vec<_>>(), ["g.txt"]);
+
+        t.repo.reset(first, ResetMode::Hard, &mut |_| HardResetChoice::StashFirst).unwrap();
+        assert_eq!(t.read("g.txt"), "g\n");
+        assert_eq!(t.read("notes.txt"), "keep\n");
+        let stashes = t.repo.stashes().unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert!(stashes[0].message.ends_with(&format!(": gitz: before reset to {}", &first.to_string()[..7])));
+        let stashed: Vec<String> = t.repo.stash_diff(0, 3).unwrap().iter().map(|f| f.path().to_string()).collect();
+        assert_eq!(stashed, ["g.txt"]);
+    }
+}
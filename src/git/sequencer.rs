@@ -0,0 +1,437 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use git2::Oid;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Which way a commit is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PickKind {
+    CherryPick,
+    Revert,
+}
+
+impl PickKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PickKind::CherryPick => "cherry-pick",
+            PickKind::Revert => "revert",
+        }
+    }
+
+    /// The file libgit2 leaves in the git directory while the commit being
+    /// applied is not committed yet.
+    fn head_file(&self) -> &'static str {
+        match self {
+            PickKind::CherryPick => "CHERRY_PICK_HEAD",
+            PickKind::Revert => "REVERT_HEAD",
+        }
+    }
+}
+
+/// Options for a cherry-pick or revert sequence.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PickOptions {
+    /// Pause before each commit so its message can be edited; otherwise
+    /// commit straight away with the default message.
+    pub edit: bool,
+}
+
+/// Why a sequence paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SequenceStop {
+    /// The commit did not apply cleanly; resolve and stage, then continue.
+    Conflict,
+    /// The commit applied and waits for its message.
+    Message,
+}
+
+/// A cherry-pick or revert in progress, persisted in the git directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceState {
+    pub kind: PickKind,
+    pub edit: bool,
+    #[serde(with = "crate::git::serde_oid")]
+    pub orig_head: Oid,
+    /// Commits still to apply, in order.
+    #[serde(with = "crate::git::serde_oid::list")]
+    pub todo: Vec<Oid>,
+    /// The commit being applied when stopped.
+    #[serde(with = "crate::git::serde_oid::option", default)]
+    pub current: Option<Oid>,
+    pub stopped: Option<SequenceStop>,
+    /// Default message for `current`.
+    pub message: Option<String>,
+    /// Commits created so far.
+    pub created: usize,
+}
+
+/// Result of running a sequence until it finishes or pauses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceOutcome {
+    /// All commits were applied; carries the number of commits created.
+    Finished(usize),
+    /// Paused for the message of the given commit.
+    Stopped { oid: Oid, message: String },
+}
+
+/// Default message for reverting a commit.
+pub fn revert_message(oid: Oid, summary: &str) -> String {
+    format!("Revert \"{}\"\n\nThis reverts commit {}.\n", summary, oid)
+}
+
+impl Repository {
+    /// Cherry-pick `oids` onto HEAD, parents before children.
+    pub fn cherry_pick(&self, oids: &[Oid], opts: PickOptions) -> Result<SequenceOutcome, GitzError> {
+        let todo = self.topological(oids)?;
+        self.start_sequence(PickKind::CherryPick, todo, opts)
+    }
+
+    /// Revert `oids` on top of HEAD, children before parents.
+    pub fn revert(&self, oids: &[Oid], opts: PickOptions) -> Result<SequenceOutcome, GitzError> {
+        let mut todo = self.topological(oids)?;
+        todo.reverse();
+        self.start_sequence(PickKind::Revert, todo, opts)
+    }
+
+    /// `oids` sorted oldest first. Merge commits are refused since picking
+    /// them needs a mainline parent.
+    fn topological(&self, oids: &[Oid]) -> Result<Vec<Oid>, GitzError> {
+        if oids.is_empty() {
+            return Err(GitzError::InvalidInput("No commits selected".into()));
+        }
+        let mut selected: HashSet<Oid> = HashSet::with_capacity(oids.len());
+        let mut revwalk = self.inner.revwalk()?;
+        for oid in oids {
+            if self.inner.find_commit(*oid)?.parent_count() > 1 {
+                return Err(GitzError::InvalidInput(format!("{} is a merge commit", oid)));
+            }
+            if selected.insert(*oid) {
+                revwalk.push(*oid)?;
+            }
+        }
+        if selected.len() == 1 {
+            return Ok(selected.into_iter().collect());
+        }
+        // Sorted walks are prepared up front, so bound this one below the
+        // selection's merge base rather than letting it reach the root.
+        let selection: Vec<Oid> = selected.iter().copied().collect();
+        if let Ok(base) = self.inner.merge_base_many(&selection) {
+            for parent in self.inner.find_commit(base)?.parent_ids() {
+                revwalk.hide(parent)?;
+            }
+        }
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+        let mut sorted = Vec::with_capacity(selected.len());
+        for oid in revwalk {
+            let oid = oid?;
+            if selected.remove(&oid) {
+                sorted.push(oid);
+                if selected.is_empty() {
+                    break;
+                }
+            }
+        }
+        if let Some(missing) = selected.iter().next() {
+            return Err(GitzError::GitOperationFailed(format!("{} was not reached walking the history", missing)));
+        }
+        sorted.reverse();
+        Ok(sorted)
+    }
+
+    fn sequence_state_path(&self) -> PathBuf {
+        self.inner.path().join("gitz-sequence.json")
+    }
+
+    /// The cherry-pick or revert in progress, if any.
+    pub fn sequence_state(&self) -> Result<Option<SequenceState>, GitzError> {
+        match std::fs::read_to_string(self.sequence_state_path()) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| GitzError::GitOperationFailed(format!("corrupt sequencer state: {}", e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save_sequence_state(&self, state: &SequenceState) -> Result<(), GitzError> {
+        let json = serde_json::to_string_pretty(state)
+            .map_err(|e| GitzError::GitOperationFailed(e.to_string()))?;
+        std::fs::write(self.sequence_state_path(), json)?;
+        self.write_sequencer_dir(state)
+    }
+
+    /// Mirror the state in `sequencer/`, where git keeps a cherry-pick or
+    /// revert of several commits, so git sees it as in progress too.
+    fn write_sequencer_dir(&self, state: &SequenceState) -> Result<(), GitzError> {
+        let dir = self.inner.path().join("sequencer");
+        std::fs::create_dir_all(&dir)?;
+        let command = match state.kind {
+            PickKind::CherryPick => "pick",
+            PickKind::Revert => "revert",
+        };
+        // Like git, the stopped commit stays at the top of the todo list.
+        let mut todo = String::new();
+        for oid in state.current.iter().chain(&state.todo) {
+            let commit = self.inner.find_commit(*oid)?;
+            todo.push_str(&format!("{} {} {}\n", command, oid, commit.summary().unwrap_or("")));
+        }
+        let head = self.inner.head()?.peel_to_commit()?.id();
+        std::fs::write(dir.join("head"), format!("{}\n", state.orig_head))?;
+        std::fs::write(dir.join("abort-safety"), format!("{}\n", head))?;
+        std::fs::write(dir.join("todo"), todo)?;
+        Ok(())
+    }
+
+    /// Forget the sequence: its state file and the `sequencer/` mirror.
+    fn remove_sequence_state(&self) -> Result<(), GitzError> {
+        std::fs::remove_file(self.sequence_state_path())?;
+        match std::fs::remove_dir_all(self.inner.path().join("sequencer")) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn start_sequence(&self, kind: PickKind, todo: Vec<Oid>, opts: PickOptions) -> Result<SequenceOutcome, GitzError> {
+        self.ensure_idle()?;
+        if self.has_tracked_changes()? {
+            return Err(GitzError::InvalidInput(format!("Commit or stash your changes before you {}", kind.label())));
+        }
+        let state = SequenceState {
+            kind,
+            edit: opts.edit,
+            orig_head: self.inner.head()?.peel_to_commit()?.id(),
+            todo,
+            current: None,
+            stopped: None,
+            message: None,
+            created: 0,
+        };
+        self.save_sequence_state(&state)?;
+        self.run_sequence(state)
+    }
+
+    /// Commit the paused commit, with `message` instead of the default if
+    /// given, and apply the rest.
+    pub fn sequence_continue(&self, message: Option<&str>) -> Result<SequenceOutcome, GitzError> {
+        let mut state = self
+            .sequence_state()?
+            .ok_or_else(|| GitzError::InvalidInput("No cherry-pick or revert in progress".into()))?;
+        if let (Some(oid), Some(_)) = (state.current, state.stopped) {
+            if self.inner.index()?.has_conflicts() {
+                return Err(GitzError::MergeConflict);
+            }
+            let message = message.or(state.message.as_deref()).unwrap_or_default().to_string();
+            // Without CHERRY_PICK_HEAD/REVERT_HEAD, `git commit` already made the commit.
+            let committed = !self.inner.path().join(state.kind.head_file()).exists();
+            if committed || self.commit_sequence_step(state.kind, oid, &message)? {
+                state.created += 1;
+            }
+        }
+        state.current = None;
+        state.stopped = None;
+        state.message = None;
+        self.save_sequence_state(&state)?;
+        self.run_sequence(state)
+    }
+
+    /// Stop and put HEAD and the work tree back where they started.
+    pub fn sequence_abort(&self) -> Result<(), GitzError> {
+        let state = self
+            .sequence_state()?
+            .ok_or_else(|| GitzError::InvalidInput("No cherry-pick or revert in progress".into()))?;
+        let orig = self.inner.find_commit(state.orig_head)?;
+        self.inner.reset(orig.as_object(), git2::ResetType::Hard, None)?;
+        self.clear_pick_head()?;
+        self.remove_sequence_state()
+    }
+
+    fn run_sequence(&self, mut state: SequenceState) -> Result<SequenceOutcome, GitzError> {
+        while !state.todo.is_empty() {
+            let oid = state.todo.remove(0);
+            let commit = self.inner.find_commit(oid)?;
+            let message = match state.kind {
+                PickKind::CherryPick => {
+                    self.inner.cherrypick(&commit, None)?;
+                    commit.message().unwrap_or("").to_string()
+                }
+                PickKind::Revert => {
+                    self.inner.revert(&commit, None)?;
+                    revert_message(oid, commit.summary().unwrap_or(""))
+                }
+            };
+            // CHERRY_PICK_HEAD/REVERT_HEAD stay until the commit is made, as with git.
+            let conflict = self.inner.index()?.has_conflicts();
+            if conflict || state.edit {
+                state.current = Some(oid);
+                state.stopped = Some(if conflict { SequenceStop::Conflict } else { SequenceStop::Message });
+                state.message = Some(message.clone());
+                self.save_sequence_state(&state)?;
+                if conflict {
+                    return Err(GitzError::MergeConflict);
+                }
+                return Ok(SequenceOutcome::Stopped { oid, message });
+            }
+            if self.commit_sequence_step(state.kind, oid, &message)? {
+                state.created += 1;
+            }
+            self.save_sequence_state(&state)?;
+        }
+        self.remove_sequence_state()?;
+        Ok(SequenceOutcome::Finished(state.created))
+    }
+
+    /// Commit the index on HEAD for a picked or reverted commit. Changes
+    /// that are already present leave nothing to commit and are skipped.
+    fn commit_sequence_step(&self, kind: PickKind, oid: Oid, message: &str) -> Result<bool, GitzError> {
+        let original = self.inner.find_commit(oid)?;
+        let head = self.inner.head()?.peel_to_commit()?;
+        let tree_id = self.inner.index()?.write_tree()?;
+        let created = tree_id != head.tree_id();
+        if created {
+            let tree = self.inner.find_tree(tree_id)?;
+            let committer = self.inner.signature()?;
+            let author = match kind {
+                PickKind::CherryPick => original.author(),
+                PickKind::Revert => committer.clone(),
+            };
+            self.inner.commit(Some("HEAD"), &author, &committer, message, &tree, &[&head])?;
+        }
+        self.clear_pick_head()?;
+        Ok(created)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::GitzError;
+    use crate::git::testing::TestRepo;
+    use crate::git::{BranchKind, StatusSection};
+
+    fn subjects(t: &TestRepo) -> Vec<String> {
+        t.repo.recent_commits(20).unwrap().iter().map(|c| c.summary().to_string()).collect()
+    }
+
+    /// `main` with `base`, and a `release` branch with three fixes on top.
+    fn release_branch() -> (TestRepo, String, Vec<Oid>) {
+        let t = TestRepo::new();
+        t.write("base.txt", "base\n");
+        let base = t.commit_all("base");
+        let main = t.repo.current_branch().unwrap();
+        t.repo.create_branch("release", base).unwrap();
+        t.repo.checkout_branch("release", BranchKind::Local).unwrap();
+        let mut fixes = Vec::new();
+        for name in ["fix1", "fix2", "fix3"] {
+            t.write(&format!("{}.txt", name), &format!("{}\n", name));
+            fixes.push(t.commit_all(name));
+        }
+        t.repo.checkout_branch(&main, BranchKind::Local).unwrap();
+        (t, main, fixes)
+    }
+
+    #[test]
+    fn test_state_round_trips_through_json() {
+        let state = SequenceState {
+            kind: PickKind::CherryPick,
+            edit: true,
+            orig_head: Oid::from_bytes(&[1; 20]).unwrap(),
+            todo: vec![Oid::from_bytes(&[2; 20]).unwrap()],
+            current: Some(Oid::from_bytes(&[3; 20]).unwrap()),
+            stopped: Some(SequenceStop::Conflict),
+            message: Some("msg".into()),
+            created: 1,
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(json.contains("\"cherry-pick\""));
+        assert!(json.contains("\"0202020202020202020202020202020202020202\""));
+        assert_eq!(serde_json::from_str::<SequenceState>(&json).unwrap(), state);
+    }
+
+    #[test]
+    fn test_cherry_pick_applies_in_topological_order() {
+        let (t, _, fixes) = release_branch();
+        let mut release = t.repo.log_cursor(Some("release")).unwrap();
+        assert_eq!(t.repo.log_next(&mut release, 10).unwrap().len(), 4);
+        // Selection order and duplicates do not matter; parents are applied first.
+        let outcome = t.repo.cherry_pick(&[fixes[2], fixes[0], fixes[2]], PickOptions::default()).unwrap();
+        assert_eq!(outcome, SequenceOutcome::Finished(2));
+        assert_eq!(subjects(&t), ["fix3", "fix1", "base"]);
+        assert!(!t.path("fix2.txt").exists());
+        assert_eq!(t.repo.recent_commits(1).unwrap()[0].author, "Test User");
+        assert!(t.repo.sequence_state().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_revert_newest_first_with_message_edit() {
+        let (t, _, fixes) = release_branch();
+        t.repo.checkout_branch("release", BranchKind::Local).unwrap();
+        let outcome = t.repo.revert(&[fixes[1], fixes[2]], PickOptions { edit: true }).unwrap();
+        let SequenceOutcome::Stopped { oid, message } = outcome else { panic!("expected a stop") };
+        assert_eq!(oid, fixes[2]);
+        assert_eq!(message, revert_message(fixes[2], "fix3"));
+        // The stop is recorded where git records it.
+        assert!(t.path(".git/REVERT_HEAD").exists());
+        assert_eq!(t.git().state(), git2::RepositoryState::RevertSequence);
+        let todo = std::fs::read_to_string(t.path(".git/sequencer/todo")).unwrap();
+        assert_eq!(todo, format!("revert {} fix3\nrevert {} fix2\n", fixes[2], fixes[1]));
+
+        let outcome = t.repo.sequence_continue(Some("Back out fix3\n")).unwrap();
+        assert!(matches!(outcome, SequenceOutcome::Stopped { oid, .. } if oid == fixes[1]));
+        assert_eq!(t.repo.sequence_continue(None).unwrap(), SequenceOutcome::Finished(2));
+        assert_eq!(subjects(&t)[..2], ["Revert \"fix2\"", "Back out fix3"]);
+        assert!(!t.path("fix2.txt").exists() && !t.path("fix3.txt").exists());
+        assert!(!t.path(".git/sequencer").exists());
+        assert_eq!(t.git().state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_conflict_continue_and_abort() {
+        let t = TestRepo::new();
+        t.write("f.txt", "base\n");
+        let base = t.commit_all("base");
+        let main = t.repo.current_branch().unwrap();
+        t.repo.create_branch("topic", base).unwrap();
+        t.repo.checkout_branch("topic", BranchKind::Local).unwrap();
+        t.write("f.txt", "topic\n");
+        let topic = t.commit_all("topic");
+        t.write("g.txt", "g\n");
+        let second = t.commit_all("second");
+        t.repo.checkout_branch(&main, BranchKind::Local).unwrap();
+        t.write("f.txt", "main\n");
+        let tip = t.commit_all("main");
+
+        t.write("dirty.txt", "x\n");
+        t.repo.stage_path("dirty.txt").unwrap();
+        assert!(matches!(t.repo.cherry_pick(&[topic], PickOptions::default()), Err(GitzError::InvalidInput(_))));
+        t.repo.unstage_path("dirty.txt").unwrap();
+        std::fs::remove_file(t.path("dirty.txt")).unwrap();
+
+        let result = t.repo.cherry_pick(&[topic, second], PickOptions::default());
+        assert!(matches!(result, Err(GitzError::MergeConflict)));
+        assert!(t.path(".git/CHERRY_PICK_HEAD").exists());
+        assert_eq!(t.git().state(), git2::RepositoryState::CherryPickSequence);
+        assert!(matches!(t.repo.sequence_continue(None), Err(GitzError::MergeConflict)));
+        assert!(matches!(t.repo.cherry_pick(&[second], PickOptions::default()), Err(GitzError::InvalidInput(_))));
+
+        // Abort puts everything back.
+        t.repo.sequence_abort().unwrap();
+        assert_eq!(t.repo.recent_commits(1).unwrap()[0].oid, tip);
+        assert_eq!(t.read("f.txt"), "main\n");
+        assert!(t.repo.sequence_state().unwrap().is_none());
+        assert_eq!(t.git().state(), git2::RepositoryState::Clean);
+
+        // Resolving and continuing keeps the original author and message.
+        assert!(t.repo.cherry_pick(&[topic, second], PickOptions::default()).is_err());
+        t.write("f.txt", "resolved\n");
+        t.repo.stage_path("f.txt").unwrap();
+        assert_eq!(t.repo.sequence_continue(None).unwrap(), SequenceOutcome::Finished(2));
+        assert_eq!(subjects(&t)[..3], ["second", "topic", "main"]);
+        assert!(t.repo.status().unwrap().section(StatusSection::Staged).is_empty());
+    }
+}
@@ -0,0 +1,46 @@
+//! Serialize `git2::Oid` as its hex string, for `#[serde(with = "...")]`.
+
+use git2::Oid;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(oid: &Oid, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&oid.to_string())
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Oid, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    Oid::from_str(&hex).map_err(serde::de::Error::custom)
+}
+
+/// A list of oids as hex strings.
+pub mod list {
+    use git2::Oid;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(oids: &[Oid], serializer: S) -> Result<S::Ok, S::Error> {
+        oids.iter().map(Oid::to_string).collect::<Vec<_>>().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Oid>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|hex| Oid::from_str(hex).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+/// An optional oid as a hex string or `null`.
+pub mod option {
+    use git2::Oid;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(oid: &Option<Oid>, serializer: S) -> Result<S::Ok, S::Error> {
+        oid.map(|o| o.to_string()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Oid>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|hex| Oid::from_str(&hex).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
@@ -0,0 +1,11 @@
+//! Serialize seconds since the epoch as an RFC 3339 UTC timestamp, for
+//! `#[serde(with = "...")]`.
+
+use chrono::{DateTime, SecondsFormat};
+use serde::Serializer;
+
+pub fn serialize<S: Serializer>(time: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+    let date = DateTime::from_timestamp(*time, 0)
+        .ok_or_else(|| serde::ser::Error::custom(format!("time out of range: {}", time)))?;
+    serializer.serialize_str(&date.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
@@ -0,0 +1,211 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::diff::collect_file_diffs;
+use crate::git::{FileDiff, Repository};
+use git2::{DiffOptions, Oid};
+
+/// One entry of the stash list, `stash@{index}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashInfo {
+    pub index: usize,
+    pub oid: Oid,
+    /// Full reflog message, e.g. `On main: wip`.
+    pub message: String,
+    /// Branch the stash was created on, when the message records it.
+    pub branch: Option<String>,
+    pub time: i64, // seconds since epoch
+}
+
+impl StashInfo {
+    pub fn new(index: usize, oid: Oid, message: impl Into<String>, time: i64) -> Self {
+        let message = message.into();
+        let branch = parse_stash_branch(&message);
+        Self { index, oid, message, branch, time }
+    }
+
+    /// Reference name as accepted by git, e.g. `stash@{0}`.
+    pub fn refname(&self) -> String {
+        format!("stash@{{{}}}", self.index)
+    }
+
+    /// Message without the `On <branch>:` / `WIP on <branch>:` prefix.
+    pub fn description(&self) -> &str {
+        match self.message.split_once(": ") {
+            Some((prefix, rest)) if prefix.starts_with("On ") || prefix.starts_with("WIP on ") => rest,
+            _ => &self.message,
+        }
+    }
+}
+
+/// Options for creating a stash.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StashOptions {
+    pub message: Option<String>,
+    /// Also stash (and remove) untracked files.
+    pub include_untracked: bool,
+    /// Leave staged changes in the index.
+    pub keep_index: bool,
+}
+
+/// Branch name from a stash message written by git (`On main: msg`,
+/// `WIP on main: abc123 subject`).
+fn parse_stash_branch(message: &str) -> Option<String> {
+    let (prefix, _) = message.split_once(": ")?;
+    let branch = prefix.strip_prefix("WIP on ").or_else(|| prefix.strip_prefix("On "))?;
+    Some(branch.to_string())
+}
+
+impl Repository {
+    /// Stash list, newest first, read from the `refs/stash` reflog.
+    pub fn stashes(&self) -> Result<Vec<StashInfo>, GitzError> {
+        let reflog = match self.inner.reflog("refs/stash") {
+            Ok(reflog) => reflog,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(reflog
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                StashInfo::new(index, entry.id_new(), entry.message().unwrap_or(""), entry.committer().when().seconds())
+            })
+            .collect())
+    }
+
+    /// Stash local changes and reset the work tree to HEAD.
+    pub fn stash_save(&mut self, opts: &StashOptions) -> Result<Oid, GitzError> {
+        let sig = self.inner.signature()?;
+        let mut flags = git2::StashFlags::DEFAULT;
+        if opts.include_untracked {
+            flags |= git2::StashFlags::INCLUDE_UNTRACKED;
+        }
+        if opts.keep_index {
+            flags |= git2::StashFlags::KEEP_INDEX;
+        }
+        let message = opts.message.as_deref().filter(|m| !m.trim().is_empty());
+        Ok(self.inner.stash_save2(&sig, message, Some(flags))?)
+    }
+
+    /// Apply `stash@{index}` and keep it in the list.
+    pub fn stash_apply(&mut self, index: usize) -> Result<(), GitzError> {
+        Ok(self.inner.stash_apply(index, None)?)
+    }
+
+    /// Apply `stash@{index}` and drop it if it applied cleanly.
+    pub fn stash_pop(&mut self, index: usize) -> Result<(), GitzError> {
+        Ok(self.inner.stash_pop(index, None)?)
+    }
+
+    /// Remove `stash@{index}` without applying it.
+    pub fn stash_drop(&mut self, index: usize) -> Result<(), GitzError> {
+        Ok(self.inner.stash_drop(index)?)
+    }
+
+    /// Changes recorded in `stash@{index}`: tracked changes relative to the
+    /// commit it was created on, followed by any stashed untracked files.
+    pub fn stash_diff(&self, index: usize, context_lines: u32) -> Result<Vec<FileDiff>, GitzError> {
+        let stash = self
+            .stashes()?
+            .into_iter()
+            .find(|s| s.index == index)
+            .ok_or_else(|| GitzError::InvalidInput(format!("no stash entry stash@{{{}}}", index)))?;
+        let commit = self.inner.find_commit(stash.oid)?;
+        let base = commit.parent(0)?.tree()?;
+        let mut opts = DiffOptions::new();
+        opts.context_lines(context_lines);
+        let diff = self.inner.diff_tree_to_tree(Some(&base), Some(&commit.tree()?), Some(&mut opts))?;
+        let mut files = collect_file_diffs(&diff)?;
+        if let Ok(untracked) = commit.parent(2) {
+            let diff = self.inner.diff_tree_to_tree(None, Some(&untracked.tree()?), Some(&mut opts))?;
+            files.extend(collect_file_diffs(&diff)?);
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    #[test]
+    fn test_parse_stash_message() {
+        let stash = StashInfo::new(0, Oid::zero(), "WIP on main: 1a2b3c4 initial", 0);
+        assert_eq!(stash.branch.as_deref(), Some("main"));
+        assert_eq!(stash.description(), "1a2b3c4 initial");
+        assert_eq!(stash.refname(), "stash@{0}");
+
+        let stash = StashInfo::new(2, Oid::zero(), "On feature/x: half done", 0);
+        assert_eq!(stash.branch.as_deref(), Some("feature/x"));
+        assert_eq!(stash.description(), "half done");
+
+        let stash = StashInfo::new(1, Oid::zero(), "custom", 0);
+        assert_eq!(stash.branch, None);
+        assert_eq!(stash.description(), "custom");
+    }
+
+    #[test]
+    fn test_stash_lifecycle() {
+        let t = TestRepo::new();
+        t.write("f.txt", "base\n");
+        t.commit_all("base");
+        let mut repo = t.repo.clone();
+        let branch = repo.current_branch().unwrap();
+
+        t.write("f.txt", "changed\n");
+        t.write("new.txt", "untracked\n");
+        let opts = StashOptions { message: Some("first".into()), include_untracked: true, keep_index: false };
+        repo.stash_save(&opts).unwrap();
+        assert_eq!(t.read("f.txt"), "base\n");
+        assert!(!t.path("new.txt").exists());
+
+        t.write("f.txt", "second\n");
+        repo.stash_save(&StashOptions::default()).unwrap();
+
+        let stashes = repo.stashes().unwrap();
+        assert_eq!(stashes.len(), 2);
+        assert_eq!(stashes[0].index, 0);
+        assert_eq!(stashes[0].branch.as_deref(), Some(branch.as_str()));
+        assert_eq!(stashes[1].description(), "first");
+
+        let files = repo.stash_diff(1, 3).unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path().to_string()).collect();
+        assert_eq!(paths, ["f.txt", "new.txt"]);
+
+        repo.stash_drop(0).unwrap();
+        repo.stash_pop(0).unwrap();
+        assert_eq!(t.read("f.txt"), "changed\n");
+        assert_eq!(t.read("new.txt"), "untracked\n");
+        assert!(repo.stashes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stash_keep_index_and_apply() {
+        let t = TestRepo::new();
+        t.write("f.txt", "base\n");
+        t.commit_all("base");
+        let mut repo = t.repo.clone();
+
+        t.write("f.txt", "staged\n");
+        repo.add_all().unwrap();
+        let opts = StashOptions { keep_index: true, ..StashOptions::default() };
+        repo.stash_save(&opts).unwrap();
+        assert_eq!(t.index_contents("f.txt").as_deref(), Some("staged\n"));
+
+        t.write("f.txt", "base\n");
+        repo.add_all().unwrap();
+        repo.stash_apply(0).unwrap();
+        assert_eq!(t.read("f.txt"), "staged\n");
+        assert_eq!(repo.stashes().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_stash_without_changes_fails() {
+        let t = TestRepo::new();
+        t.write("f.txt", "base\n");
+        t.commit_all("base");
+        let mut repo = t.repo.clone();
+        assert!(repo.stash_save(&StashOptions::default()).is_err());
+    }
+}
@@ -1,10 +1,154 @@
 #![allow(dead_code)]
 
-#[derive(Debug, Clone, Default)]
+use crate::errors::GitzError;
+use crate::git::{ChangeKind, Repository};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A change on one side of a file: HEAD→index or index→work tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileChange {
+    pub kind: ChangeKind,
+    /// Previous path when the change is a rename.
+    pub renamed_from: Option<String>,
+}
+
+impl FileChange {
+    pub fn new(kind: ChangeKind) -> Self {
+        Self { kind, renamed_from: None }
+    }
+
+    pub fn renamed(from: impl Into<String>) -> Self {
+        Self { kind: ChangeKind::Renamed, renamed_from: Some(from.into()) }
+    }
+}
+
+/// Which conflict stages are present in the index for an unmerged path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct ConflictInfo {
+    /// Stage 1: common ancestor.
+    pub ancestor: bool,
+    /// Stage 2: our side.
+    pub ours: bool,
+    /// Stage 3: their side.
+    pub theirs: bool,
+}
+
+impl ConflictInfo {
+    /// Description in the wording of `git status`.
+    pub fn description(&self) -> &'static str {
+        match (self.ancestor, self.ours, self.theirs) {
+            (true, true, true) | (false, false, false) => "both modified",
+            (false, true, true) => "both added",
+            (true, false, false) => "both deleted",
+            (true, false, true) => "deleted by us",
+            (true, true, false) => "deleted by them",
+            (false, true, false) => "added by us",
+            (false, false, true) => "added by them",
+        }
+    }
+}
+
+/// Index and work tree state of one path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileStatus {
+    pub path: String,
+    /// Change staged in the index relative to HEAD.
+    pub index: Option<FileChange>,
+    /// Change in the work tree relative to the index.
+    pub worktree: Option<FileChange>,
+    /// Present when the path is unmerged.
+    pub conflict: Option<ConflictInfo>,
+    /// The path is a submodule; its changes are to the recorded commit or
+    /// inside the submodule's own work tree.
+    pub submodule: bool,
+}
+
+impl FileStatus {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into(), index: None, worktree: None, conflict: None, submodule: false }
+    }
+
+    pub fn with_index(mut self, change: FileChange) -> Self {
+        self.index = Some(change);
+        self
+    }
+
+    pub fn with_worktree(mut self, change: FileChange) -> Self {
+        self.worktree = Some(change);
+        self
+    }
+
+    pub fn with_conflict(mut self, conflict: ConflictInfo) -> Self {
+        self.conflict = Some(conflict);
+        self
+    }
+
+    pub fn with_submodule(mut self) -> Self {
+        self.submodule = true;
+        self
+    }
+
+    /// The change this file shows in `section`, if it belongs there.
+    pub fn change_in(&self, section: StatusSection) -> Option<&FileChange> {
+        if self.conflict.is_some() {
+            return None;
+        }
+        match section {
+            StatusSection::Staged => self.index.as_ref(),
+            StatusSection::Unstaged => self.worktree.as_ref().filter(|c| c.kind != ChangeKind::Untracked),
+            StatusSection::Untracked => self.worktree.as_ref().filter(|c| c.kind == ChangeKind::Untracked),
+            StatusSection::Conflicts => None,
+        }
+    }
+
+    /// Whether the file is listed in `section`.
+    pub fn in_section(&self, section: StatusSection) -> bool {
+        match section {
+            StatusSection::Conflicts => self.conflict.is_some(),
+            _ => self.change_in(section).is_some(),
+        }
+    }
+}
+
+/// Groups shown in the file list, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusSection {
+    Staged,
+    Unstaged,
+    Untracked,
+    Conflicts,
+}
+
+impl StatusSection {
+    pub fn all() -> [StatusSection; 4] {
+        [StatusSection::Staged, StatusSection::Unstaged, StatusSection::Untracked, StatusSection::Conflicts]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            StatusSection::Staged => "Staged",
+            StatusSection::Unstaged => "Unstaged",
+            StatusSection::Untracked => "Untracked",
+            StatusSection::Conflicts => "Conflicts",
+        }
+    }
+}
+
+/// One selectable row of the file list: a file as it appears in one section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusEntry {
+    pub section: StatusSection,
+    pub path: String,
+    /// `None` for conflicted entries.
+    pub change: Option<FileChange>,
+    pub conflict: Option<ConflictInfo>,
+    pub submodule: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct RepoStatus {
-    pub modified: Vec<String>,
-    pub added: Vec<String>,
-    pub deleted: Vec<String>,
+    pub files: Vec<FileStatus>,
 }
 
 impl RepoStatus {
@@ -15,25 +159,33 @@ impl RepoStatus {
 
     /// Check if the repository is clean (no changes)
     pub fn is_clean(&self) -> bool {
-        self.modified.is_empty() && self.added.is_empty() && self.deleted.is_empty()
+        self.files.is_empty()
+    }
+
+    /// Files belonging to a section.
+    pub fn section(&self, section: StatusSection) -> Vec<&FileStatus> {
+        self.files.iter().filter(|f| f.in_section(section)).collect()
     }
 
-    /// Total number of changed files
+    /// Total number of entries across all sections (a file staged and
+    /// modified again counts once per section).
     pub fn total_changes(&self) -> usize {
-        self.modified.len() + self.added.len() + self.deleted.len()
+        StatusSection::all().iter().map(|s| self.section(*s).len()).sum()
     }
 
     /// Human-readable summary
     pub fn summary(&self) -> String {
         let mut parts = Vec::new();
-        if !self.modified.is_empty() {
-            parts.push(format!("{} modified", self.modified.len()));
-        }
-        if !self.added.is_empty() {
-            parts.push(format!("{} added", self.added.len()));
-        }
-        if !self.deleted.is_empty() {
-            parts.push(format!("{} deleted", self.deleted.len()));
+        for (section, word) in [
+            (StatusSection::Staged, "staged"),
+            (StatusSection::Unstaged, "unstaged"),
+            (StatusSection::Untracked, "untracked"),
+            (StatusSection::Conflicts, "conflicted"),
+        ] {
+            let count = self.section(section).len();
+            if count > 0 {
+                parts.push(format!("{} {}", count, word));
+            }
         }
         if parts.is_empty() {
             "clean".into()
@@ -42,26 +194,83 @@ impl RepoStatus {
         }
     }
 
-    /// Get all changed files as a single list
+    /// All entries in display order, one per file and section.
+    pub fn entries(&self) -> Vec<StatusEntry> {
+        let mut entries = Vec::new();
+        for section in StatusSection::all() {
+            for file in self.section(section) {
+                entries.push(StatusEntry {
+                    section,
+                    path: file.path.clone(),
+                    change: file.change_in(section).cloned(),
+                    conflict: file.conflict,
+                    submodule: file.submodule,
+                });
+            }
+        }
+        entries
+    }
+
+    /// Get all changed paths, each listed once
     pub fn all_files(&self) -> Vec<String> {
-        let mut all = Vec::new();
-        all.extend(self.modified.clone());
-        all.extend(self.added.clone());
-        all.extend(self.deleted.clone());
-        all
+        self.files.iter().map(|f| f.path.clone()).collect()
+    }
+
+    /// Look up a file's status by path
+    pub fn file(&self, path: &str) -> Option<&FileStatus> {
+        self.files.iter().find(|f| f.path == path)
     }
 
     /// Check if a specific file has changes
     pub fn has_file(&self, path: &str) -> bool {
-        self.modified.contains(&path.to_string())
-            || self.added.contains(&path.to_string())
-            || self.deleted.contains(&path.to_string())
+        self.file(path).is_some()
+    }
+}
+
+impl Repository {
+    /// Conflict stages present in the index, keyed by path.
+    pub(super) fn conflict_stages(&self) -> Result<HashMap<String, ConflictInfo>, GitzError> {
+        let index = self.inner.index()?;
+        let mut stages = HashMap::new();
+        if !index.has_conflicts() {
+            return Ok(stages);
+        }
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let path = [&conflict.our, &conflict.their, &conflict.ancestor]
+                .into_iter()
+                .flatten()
+                .next()
+                .map(|e| String::from_utf8_lossy(&e.path).into_owned());
+            if let Some(path) = path {
+                stages.insert(path, ConflictInfo {
+                    ancestor: conflict.ancestor.is_some(),
+                    ours: conflict.our.is_some(),
+                    theirs: conflict.their.is_some(),
+                });
+            }
+        }
+        Ok(stages)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::testing::TestRepo;
+
+    fn sample() -> RepoStatus {
+        RepoStatus {
+            files: vec![
+                FileStatus::new("both.rs")
+                    .with_index(FileChange::new(ChangeKind::Modified))
+                    .with_worktree(FileChange::new(ChangeKind::Modified)),
+                FileStatus::new("new.rs").with_worktree(FileChange::new(ChangeKind::Untracked)),
+                FileStatus::new("moved.rs").with_index(FileChange::renamed("old.rs")),
+                FileStatus::new("clash.rs").with_conflict(ConflictInfo { ancestor: true, ours: true, theirs: true }),
+            ],
+        }
+    }
 
     #[test]
     fn test_empty_status_is_clean() {
@@ -72,41 +281,92 @@ mod tests {
 
     #[test]
     fn test_status_with_changes() {
-        let status = RepoStatus {
-            modified: vec!["file1.rs".to_string()],
-            added: vec!["file2.rs".to_string(), "file3.rs".to_string()],
-            deleted: vec![],
-        };
-        
+        let status = sample();
         assert!(!status.is_clean());
-        assert_eq!(status.total_changes(), 3);
-        assert_eq!(status.summary(), "1 modified, 2 added");
+        assert_eq!(status.total_changes(), 5);
+        assert_eq!(status.summary(), "2 staged, 1 unstaged, 1 untracked, 1 conflicted");
     }
 
     #[test]
     fn test_has_file() {
-        let status = RepoStatus {
-            modified: vec!["file1.rs".to_string()],
-            added: vec![],
-            deleted: vec![],
-        };
-        
-        assert!(status.has_file("file1.rs"));
-        assert!(!status.has_file("file2.rs"));
+        let status = sample();
+        assert!(status.has_file("both.rs"));
+        assert!(!status.has_file("old.rs"));
+    }
+
+    #[test]
+    fn test_entries_follow_section_order() {
+        let entries = sample().entries();
+        let rows: Vec<_> = entries.iter().map(|e| (e.section, e.path.as_str())).collect();
+        assert_eq!(
+            rows,
+            vec![
+                (StatusSection::Staged, "both.rs"),
+                (StatusSection::Staged, "moved.rs"),
+                (StatusSection::Unstaged, "both.rs"),
+                (StatusSection::Untracked, "new.rs"),
+                (StatusSection::Conflicts, "clash.rs"),
+            ]
+        );
+        assert_eq!(entries[1].change.as_ref().unwrap().renamed_from.as_deref(), Some("old.rs"));
+        assert_eq!(entries[4].conflict.unwrap().description(), "both modified");
     }
 
     #[test]
-    fn test_all_files() {
-        let status = RepoStatus {
-            modified: vec!["mod.rs".to_string()],
-            added: vec!["new.rs".to_string()],
-            deleted: vec!["old.rs".to_string()],
-        };
-        
-        let all = status.all_files();
-        assert_eq!(all.len(), 3);
-        assert!(all.contains(&"mod.rs".to_string()));
-        assert!(all.contains(&"new.rs".to_string()));
-        assert!(all.contains(&"old.rs".to_string()));
+    fn test_repository_status_separates_index_and_worktree() {
+        let t = TestRepo::new();
+        t.write("kept.txt", "one\n");
+        t.write("old.txt", "some longer content so rename detection has something to match\n");
+        t.commit_all("initial");
+
+        t.write("kept.txt", "two\n");
+        t.repo.add_all().unwrap();
+        t.write("kept.txt", "three\n");
+        std::fs::rename(t.path("old.txt"), t.path("new.txt")).unwrap();
+        let git = t.git();
+        let mut index = git.index().unwrap();
+        index.remove_path(std::path::Path::new("old.txt")).unwrap();
+        index.add_path(std::path::Path::new("new.txt")).unwrap();
+        index.write().unwrap();
+        t.write("fresh.txt", "x\n");
+
+        let status = t.repo.status().unwrap();
+        let kept = status.file("kept.txt").unwrap();
+        assert_eq!(kept.index, Some(FileChange::new(ChangeKind::Modified)));
+        assert_eq!(kept.worktree, Some(FileChange::new(ChangeKind::Modified)));
+        assert_eq!(status.file("new.txt").unwrap().index, Some(FileChange::renamed("old.txt")));
+        assert_eq!(
+            status.file("fresh.txt").unwrap().worktree,
+            Some(FileChange::new(ChangeKind::Untracked))
+        );
+        assert!(!status.has_file("old.txt"));
+    }
+
+    #[test]
+    fn test_repository_status_reports_conflicts() {
+        let t = TestRepo::new();
+        t.write("f.txt", "base\n");
+        let base = t.commit_all("base");
+        let git = t.git();
+
+        t.write("f.txt", "ours\n");
+        let ours = t.commit_all("ours");
+        git.branch("other", &git.find_commit(base).unwrap(), false).unwrap();
+        git.set_head("refs/heads/other").unwrap();
+        git.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+        t.write("f.txt", "theirs\n");
+        t.commit_all("theirs");
+
+        git.set_head_detached(ours).unwrap();
+        git.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+        let theirs = git.find_annotated_commit(git.refname_to_id("refs/heads/other").unwrap()).unwrap();
+        git.merge(&[&theirs], None, None).unwrap();
+
+        let status = t.repo.status().unwrap();
+        let conflicts = status.section(StatusSection::Conflicts);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].path, "f.txt");
+        assert_eq!(conflicts[0].conflict.unwrap().description(), "both modified");
+        assert!(status.section(StatusSection::Staged).is_empty());
     }
 }
@@ -0,0 +1,199 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::remote::network_error;
+use crate::git::{RemoteProgress, Repository};
+use git2::Oid;
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+/// A submodule as configured in `.gitmodules` and found in the work tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmoduleInfo {
+    pub name: String,
+    /// Path relative to the work tree root.
+    pub path: String,
+    pub url: Option<String>,
+    /// Commit recorded in HEAD of the parent repository.
+    pub recorded: Option<Oid>,
+    /// Commit checked out in the submodule's work tree.
+    pub checked_out: Option<Oid>,
+    /// Registered in `.git/config` (`git submodule init`).
+    pub initialized: bool,
+    /// The submodule's work tree has modified or untracked files.
+    pub dirty: bool,
+}
+
+impl SubmoduleInfo {
+    /// Whether the checked-out commit differs from the recorded one.
+    pub fn out_of_date(&self) -> bool {
+        self.checked_out.is_some() && self.recorded != self.checked_out
+    }
+
+    /// Short state shown next to the submodule, in the spirit of `git submodule status`.
+    pub fn state(&self) -> &'static str {
+        match (self.initialized, self.checked_out.is_some()) {
+            (false, _) => "not initialized",
+            (true, false) => "not checked out",
+            _ if self.out_of_date() => "new commits",
+            _ if self.dirty => "modified",
+            _ => "up to date",
+        }
+    }
+}
+
+impl Repository {
+    /// Submodules of the work tree, sorted by path.
+    pub fn submodules(&self) -> Result<Vec<SubmoduleInfo>, GitzError> {
+        let config = self.inner.config()?;
+        let mut submodules = Vec::new();
+        for sm in self.inner.submodules()? {
+            let name = sm.name().unwrap_or_default().to_string();
+            let status = self.inner.submodule_status(&name, git2::SubmoduleIgnore::None)?;
+            let dirty = git2::SubmoduleStatus::WD_INDEX_MODIFIED
+                | git2::SubmoduleStatus::WD_WD_MODIFIED
+                | git2::SubmoduleStatus::WD_UNTRACKED;
+            submodules.push(SubmoduleInfo {
+                path: sm.path().to_string_lossy().into_owned(),
+                url: sm.url().map(str::to_string),
+                recorded: sm.head_id(),
+                checked_out: sm.workdir_id(),
+                // `.gitmodules` alone does not count; `git submodule init` copies the URL.
+                initialized: config.get_string(&format!("submodule.{}.url", name)).is_ok(),
+                dirty: status.intersects(dirty),
+                name,
+            });
+        }
+        submodules.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(submodules)
+    }
+
+    /// Copy a submodule's URL from `.gitmodules` into `.git/config`.
+    pub fn submodule_init(&self, path: &str) -> Result<(), GitzError> {
+        self.inner.find_submodule(path)?.init(false)?;
+        Ok(())
+    }
+
+    /// Update the remote URL of a checked-out submodule from `.gitmodules`.
+    pub fn submodule_sync(&self, path: &str) -> Result<(), GitzError> {
+        self.inner.find_submodule(path)?.sync()?;
+        Ok(())
+    }
+
+    /// Clone the submodule if needed, fetch, and check out the commit
+    /// recorded in the parent.
+    pub fn submodule_update(&self, path: &str, progress: &mut dyn FnMut(RemoteProgress)) -> Result<(), GitzError> {
+        let progress = RefCell::new(progress);
+        let mut fetch = git2::FetchOptions::new();
+        fetch.remote_callbacks(self.remote_callbacks(&progress));
+        let mut opts = git2::SubmoduleUpdateOptions::new();
+        opts.fetch(fetch);
+        self.inner.find_submodule(path)?.update(true, Some(&mut opts)).map_err(network_error)?;
+        Ok(())
+    }
+
+    /// Work tree of a submodule, to open it as a repository of its own.
+    pub fn submodule_workdir(&self, path: &str) -> Result<PathBuf, GitzError> {
+        let sm = self.inner.find_submodule(path)?;
+        let workdir = self
+            .inner
+            .workdir()
+            .ok_or_else(|| GitzError::InvalidInput("a bare repository has no submodules".into()))?;
+        let dir = workdir.join(sm.path());
+        if sm.open().is_err() {
+            return Err(GitzError::InvalidInput(format!("{} is not checked out", path)));
+        }
+        Ok(dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::testing::TestRepo;
+    use std::path::Path;
+
+    /// A repository with `lib` added as a submodule at `vendor/lib`, and the
+    /// library's first commit.
+    fn with_submodule(lib: &TestRepo) -> TestRepo {
+        let t = TestRepo::new();
+        t.write("README", "parent\n");
+        t.commit_all("init");
+        let git = t.git();
+        let url = format!("file://{}", lib.root().display());
+        let mut sm = git.submodule(&url, Path::new("vendor/lib"), true).unwrap();
+        sm.clone(None).unwrap();
+        sm.add_finalize().unwrap();
+        drop(sm);
+        drop(git);
+        t.commit_all("add lib");
+        t
+    }
+
+    #[test]
+    fn test_list_and_status_of_submodule() {
+        let lib = TestRepo::new();
+        lib.write("lib.txt", "v1\n");
+        let v1 = lib.commit_all("v1");
+        let t = with_submodule(&lib);
+
+        let subs = t.repo.submodules().unwrap();
+        assert_eq!(subs.len(), 1);
+        assert_eq!(subs[0].path, "vendor/lib");
+        assert_eq!((subs[0].recorded, subs[0].checked_out), (Some(v1), Some(v1)));
+        assert_eq!(subs[0].state(), "up to date");
+        assert!(t.repo.status().unwrap().is_clean());
+
+        // A change inside the submodule shows up as a dirty submodule entry.
+        t.write("vendor/lib/lib.txt", "local\n");
+        let subs = t.repo.submodules().unwrap();
+        assert_eq!(subs[0].state(), "modified");
+        let status = t.repo.status().unwrap();
+        assert_eq!(status.files.len(), 1);
+        assert!(status.files[0].submodule);
+        assert_eq!(t.repo.submodule_workdir("vendor/lib").unwrap(), t.path("vendor/lib"));
+    }
+
+    #[test]
+    fn test_update_checks_out_recorded_commit() {
+        let lib = TestRepo::new();
+        lib.write("lib.txt", "v1\n");
+        lib.commit_all("v1");
+        let t = with_submodule(&lib);
+        lib.write("lib.txt", "v2\n");
+        let v2 = lib.commit_all("v2");
+
+        // Move the submodule to v2 and record it in the parent.
+        let sub = git2::Repository::open(t.path("vendor/lib")).unwrap();
+        sub.find_remote("origin").unwrap().fetch(&["refs/heads/*:refs/remotes/origin/*"], None, None).unwrap();
+        let commit = sub.find_commit(v2).unwrap();
+        sub.checkout_tree(commit.as_object(), None).unwrap();
+        sub.set_head_detached(v2).unwrap();
+        t.commit_all("bump lib");
+
+        // Back in an older state, update restores the recorded commit.
+        let old = sub.revparse_single("HEAD~1").unwrap();
+        sub.checkout_tree(&old, Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+        sub.set_head_detached(old.id()).unwrap();
+        assert!(t.repo.submodules().unwrap()[0].out_of_date());
+
+        t.repo.submodule_sync("vendor/lib").unwrap();
+        t.repo.submodule_update("vendor/lib", &mut |_| {}).unwrap();
+        let subs = t.repo.submodules().unwrap();
+        assert_eq!(subs[0].checked_out, Some(v2));
+        assert_eq!(t.read("vendor/lib/lib.txt"), "v2\n");
+    }
+
+    #[test]
+    fn test_init_registers_url() {
+        let lib = TestRepo::new();
+        lib.write("lib.txt", "v1\n");
+        lib.commit_all("v1");
+        let t = with_submodule(&lib);
+        let mut cfg = t.git().config().unwrap();
+        cfg.remove("submodule.vendor/lib.url").unwrap();
+        assert!(!t.repo.submodules().unwrap()[0].initialized);
+
+        t.repo.submodule_init("vendor/lib").unwrap();
+        assert!(t.repo.submodules().unwrap()[0].initialized);
+    }
+}
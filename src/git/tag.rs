@@ -0,0 +1,158 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::{RemoteProgress, Repository};
+use git2::Oid;
+
+/// A lightweight or annotated tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagInfo {
+    pub name: String,
+    /// The commit the tag points at, with annotated tags peeled.
+    pub target: Oid,
+    /// Summary of the target commit.
+    pub summary: String,
+    pub annotated: bool,
+    /// Tagger, date and message of an annotated tag.
+    pub tagger: Option<String>,
+    pub time: Option<i64>,
+    pub message: Option<String>,
+}
+
+impl TagInfo {
+    /// Abbreviated id of the target commit.
+    pub fn short_target(&self) -> String {
+        self.target.to_string()[..7].to_string()
+    }
+
+    pub fn kind(&self) -> &'static str {
+        if self.annotated {
+            "annotated"
+        } else {
+            "lightweight"
+        }
+    }
+}
+
+impl Repository {
+    /// All tags, sorted by name.
+    pub fn tags(&self) -> Result<Vec<TagInfo>, GitzError> {
+        let mut tags = Vec::new();
+        for name in self.inner.tag_names(None)?.iter().flatten() {
+            let reference = self.inner.find_reference(&format!("refs/tags/{}", name))?;
+            // Tags of trees or blobs have no commit to show.
+            let Ok(commit) = reference.peel_to_commit() else {
+                continue;
+            };
+            let annotation = reference.target().and_then(|oid| self.inner.find_tag(oid).ok());
+            let tagger = annotation.as_ref().and_then(|tag| tag.tagger());
+            tags.push(TagInfo {
+                name: name.to_string(),
+                target: commit.id(),
+                summary: commit.summary().unwrap_or("").to_string(),
+                annotated: annotation.is_some(),
+                tagger: tagger.as_ref().map(|sig| sig.name().unwrap_or("<unknown>").to_string()),
+                time: tagger.as_ref().map(|sig| sig.when().seconds()),
+                message: annotation.as_ref().and_then(|tag| tag.message()).map(|m| m.trim_end().to_string()),
+            });
+        }
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tags)
+    }
+
+    /// Tag `target`: annotated when a message is given, lightweight otherwise.
+    pub fn create_tag(&self, name: &str, target: Oid, message: Option<&str>) -> Result<(), GitzError> {
+        if name.is_empty() || !git2::Reference::is_valid_name(&format!("refs/tags/{}", name)) {
+            return Err(GitzError::InvalidInput(format!("`{}` is not a valid tag name", name)));
+        }
+        if self.inner.find_reference(&format!("refs/tags/{}", name)).is_ok() {
+            return Err(GitzError::InvalidInput(format!("tag {} already exists", name)));
+        }
+        let object = self.inner.find_object(target, None)?;
+        match message.map(str::trim).filter(|m| !m.is_empty()) {
+            Some(message) => {
+                let sig = self.inner.signature()?;
+                self.inner.tag(name, &object, &sig, message, false)?;
+            }
+            None => {
+                self.inner.tag_lightweight(name, &object, false)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete a local tag.
+    pub fn delete_tag(&self, name: &str) -> Result<(), GitzError> {
+        self.inner.tag_delete(name)?;
+        Ok(())
+    }
+
+    /// Push a tag to `remote`.
+    pub fn push_tag(&self, remote: &str, name: &str, progress: &mut dyn FnMut(RemoteProgress)) -> Result<(), GitzError> {
+        self.push_refspec(remote, &format!("refs/tags/{0}:refs/tags/{0}", name), progress)
+    }
+
+    /// Delete a tag on `remote`, keeping the local one.
+    pub fn delete_remote_tag(&self, remote: &str, name: &str, progress: &mut dyn FnMut(RemoteProgress)) -> Result<(), GitzError> {
+        self.push_refspec(remote, &format!(":refs/tags/{}", name), progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::GitzError;
+    use crate::git::testing::TestRepo;
+    use crate::git::RefKind;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_list_and_delete_tags() {
+        let t = TestRepo::new();
+        t.write("f.txt", "one\n");
+        let first = t.commit_all("first");
+        t.write("f.txt", "two\n");
+        let second = t.commit_all("second");
+
+        t.repo.create_tag("v1.0", first, None).unwrap();
+        t.repo.create_tag("v2.0", second, Some("Release 2.0\n\nNotes")).unwrap();
+        assert!(matches!(t.repo.create_tag("v1.0", second, None), Err(GitzError::InvalidInput(_))));
+        assert!(matches!(t.repo.create_tag("bad name", second, None), Err(GitzError::InvalidInput(_))));
+
+        let tags = t.repo.tags().unwrap();
+        let names: Vec<(&str, bool)> = tags.iter().map(|t| (t.name.as_str(), t.annotated)).collect();
+        assert_eq!(names, [("v1.0", false), ("v2.0", true)]);
+        assert_eq!((tags[0].target, tags[0].summary.as_str()), (first, "first"));
+        assert_eq!(tags[1].target, second);
+        assert_eq!(tags[1].message.as_deref(), Some("Release 2.0\n\nNotes"));
+        assert_eq!(tags[1].tagger.as_deref(), Some("Test User"));
+
+        // Annotated tags decorate the commit they point at.
+        let labels = t.repo.decorations().unwrap();
+        assert!(labels[&second].iter().any(|d| d.kind == RefKind::Tag && d.name == "v2.0"));
+
+        t.repo.delete_tag("v1.0").unwrap();
+        assert_eq!(t.repo.tags().unwrap().len(), 1);
+        assert!(t.repo.delete_tag("v1.0").is_err());
+    }
+
+    #[test]
+    fn test_push_and_delete_remote_tag() {
+        let bare = TempDir::new().unwrap();
+        let remote = git2::Repository::init_bare(bare.path()).unwrap();
+        let t = TestRepo::new();
+        t.repo.add_remote("origin", &format!("file://{}", bare.path().display())).unwrap();
+        t.write("f.txt", "one\n");
+        let oid = t.commit_all("first");
+        t.repo.create_tag("v1.0", oid, Some("First")).unwrap();
+
+        let mut ignore = |_| {};
+        t.repo.push_tag("origin", "v1.0", &mut ignore).unwrap();
+        let pushed = remote.find_reference("refs/tags/v1.0").unwrap();
+        assert_eq!(pushed.peel_to_commit().unwrap().id(), oid);
+
+        t.repo.delete_remote_tag("origin", "v1.0", &mut ignore).unwrap();
+        assert!(remote.find_reference("refs/tags/v1.0").is_err());
+        // The local tag is kept.
+        assert_eq!(t.repo.tags().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,69 @@
+//! Helpers for tests that need a real repository on disk.
+
+use crate::git::Repository;
+use git2::Oid;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A freshly initialised repository in a temporary directory.
+pub struct TestRepo {
+    pub dir: TempDir,
+    pub repo: Repository,
+}
+
+impl TestRepo {
+    /// Initialise an empty repository with a committer identity configured.
+    pub fn new() -> Self {
+        let dir = TempDir::new().expect("create temp dir");
+        let repo = Repository::init(dir.path()).expect("init repository");
+        let git = git2::Repository::open(dir.path()).expect("open repository");
+        let mut cfg = git.config().expect("open config");
+        cfg.set_str("user.name", "Test User").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+        Self { dir, repo }
+    }
+
+    /// Absolute path of a file inside the work tree.
+    pub fn path(&self, rel: &str) -> PathBuf {
+        self.dir.path().join(rel)
+    }
+
+    /// Root of the work tree.
+    pub fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Write a file relative to the work tree, creating parent directories.
+    pub fn write(&self, rel: &str, contents: &str) {
+        let path = self.path(rel);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(path, contents).unwrap();
+    }
+
+    /// Read a file relative to the work tree.
+    pub fn read(&self, rel: &str) -> String {
+        std::fs::read_to_string(self.path(rel)).unwrap()
+    }
+
+    /// Contents of a path as recorded in the index, if present.
+    pub fn index_contents(&self, rel: &str) -> Option<String> {
+        let git = self.git();
+        let index = git.index().unwrap();
+        let entry = index.get_path(Path::new(rel), 0)?;
+        let blob = git.find_blob(entry.id).unwrap();
+        Some(String::from_utf8_lossy(blob.content()).into_owned())
+    }
+
+    /// Stage everything and commit it.
+    pub fn commit_all(&self, message: &str) -> Oid {
+        self.repo.add_all().unwrap();
+        self.repo.commit(message).unwrap()
+    }
+
+    /// A separate raw libgit2 handle for assertions.
+    pub fn git(&self) -> git2::Repository {
+        git2::Repository::open(self.dir.path()).unwrap()
+    }
+}
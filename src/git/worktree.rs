@@ -0,0 +1,126 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::Repository;
+use git2::{Oid, Repository as Git2Repo};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A work tree of the repository: the main one or a linked one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorktreeInfo {
+    /// Name of a linked work tree; `None` for the main one.
+    pub name: Option<String>,
+    pub path: PathBuf,
+    /// Short name of the checked-out branch; `None` when HEAD is detached.
+    pub branch: Option<String>,
+    /// Commit checked out; `None` on an unborn branch.
+    #[serde(with = "crate::git::serde_oid::option")]
+    pub head: Option<Oid>,
+    pub locked: bool,
+    /// The work tree's directory is gone (`git worktree prune` would remove it).
+    pub prunable: bool,
+}
+
+impl Repository {
+    /// The git directory shared by all work trees: a linked work tree's git
+    /// directory names it in `commondir`.
+    pub(super) fn common_dir(&self) -> PathBuf {
+        match std::fs::read_to_string(self.git_dir.join("commondir")) {
+            Ok(dir) => self.git_dir.join(dir.trim()),
+            Err(_) => self.git_dir.clone(),
+        }
+    }
+
+    /// Git directories of the main checkout and of every linked work tree.
+    pub(super) fn checkout_git_dirs(&self) -> Vec<PathBuf> {
+        let common = self.common_dir();
+        let mut linked: Vec<PathBuf> = match std::fs::read_dir(common.join("worktrees")) {
+            Ok(entries) => entries.flatten().map(|e| e.path()).collect(),
+            Err(_) => Vec::new(),
+        };
+        linked.sort();
+        let mut dirs = vec![common];
+        dirs.extend(linked);
+        dirs
+    }
+
+    /// The main work tree (unless the repository is bare) followed by the
+    /// linked ones, sorted by name.
+    pub fn worktrees(&self) -> Result<Vec<WorktreeInfo>, GitzError> {
+        fn checkout(repo: &Git2Repo) -> (Option<String>, Option<Oid>) {
+            let branch = repo
+                .find_reference("HEAD")
+                .ok()
+                .and_then(|head| head.symbolic_target().map(str::to_string))
+                .and_then(|target| target.strip_prefix("refs/heads/").map(str::to_string));
+            let head = repo.head().ok().and_then(|head| head.target());
+            (branch, head)
+        }
+
+        let mut result = Vec::new();
+        let main = if self.inner.is_worktree() {
+            Git2Repo::open(self.common_dir()).ok()
+        } else {
+            None
+        };
+        let main = main.as_ref().unwrap_or(&self.inner);
+        if let Some(workdir) = main.workdir() {
+            let (branch, head) = checkout(main);
+            result.push(WorktreeInfo {
+                name: None,
+                path: workdir.to_path_buf(),
+                branch,
+                head,
+                locked: false,
+                prunable: false,
+            });
+        }
+        let mut names: Vec<String> = self.inner.worktrees()?.iter().flatten().map(str::to_string).collect();
+        names.sort();
+        for name in names {
+            let worktree = self.inner.find_worktree(&name)?;
+            let prunable = worktree.validate().is_err();
+            let (branch, head) = match Git2Repo::open_from_worktree(&worktree) {
+                Ok(repo) if !prunable => checkout(&repo),
+                _ => (None, None),
+            };
+            result.push(WorktreeInfo {
+                path: worktree.path().to_path_buf(),
+                locked: !matches!(worktree.is_locked()?, git2::WorktreeLockStatus::Unlocked),
+                name: Some(name),
+                branch,
+                head,
+                prunable,
+            });
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::testing::TestRepo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_worktrees_lists_main_and_linked() {
+        let t = TestRepo::new();
+        t.write("f.txt", "x\n");
+        let head = t.commit_all("initial");
+        let branch = t.repo.current_branch().unwrap();
+        t.repo.create_branch("topic", head).unwrap();
+        let dir = TempDir::new().unwrap();
+        let linked = dir.path().join("topic");
+        t.repo.create_worktree(&linked, "topic").unwrap();
+
+        let worktrees = t.repo.worktrees().unwrap();
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0].name, None);
+        assert_eq!(worktrees[0].branch.as_deref(), Some(branch.as_str()));
+        assert_eq!(worktrees[1].name.as_deref(), Some("topic"));
+        assert_eq!(worktrees[1].branch.as_deref(), Some("topic"));
+        assert_eq!(worktrees[1].head, Some(head));
+        assert!(!worktrees[1].locked && !worktrees[1].prunable);
+    }
+}
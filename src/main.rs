@@ -11,14 +11,23 @@ mod event;
 mod git;
 mod commands;
 mod ui;
+mod utils;
 
 use crate::app::App;
+use crate::commands::cli::Command;
 use crate::config::Config;
+use crate::errors::GitzError;
+use crate::git::Repository;
+use std::io::{BufRead, IsTerminal, Write};
 
 /// CLI arguments for gitz.
 #[derive(Parser, Debug)]
-#[command(name = "gitz", version, about = "⚡ Git, but zippier")]
+#[command(name = "gitz", version, about = "⚡ Git, but zippier", args_conflicts_with_subcommands = true)]
 struct Cli {
+    /// Run a command instead of the interactive UI.
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the repository (defaults to current directory).
     #[arg(default_value = ".")]
     repo_path: String,
@@ -32,6 +41,19 @@ struct Cli {
     log_level: String,
 }
 
+/// Ask on the terminal whether to create a repository at `path`; `false`
+/// without asking when stdin is not a terminal.
+fn confirm_init(path: &str) -> Result<bool, std::io::Error> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    eprint!("No git repository found at or above `{}`. Create one there? [y/N] ", path);
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     // Initialise logger based on RUST_LOG or the supplied level.
@@ -41,25 +63,26 @@ async fn main() -> Result<(), anyhow::Error> {
     let cli = Cli::parse();
     let cfg = Config::load(cli.config.as_deref())?;
 
-    // Check if repo_path is current directory and if we have write permissions
-    if cli.repo_path == "." {
-        let current_dir = std::env::current_dir()?;
-        if !current_dir.join(".git").exists() {
-            // Try to create a test file to check write permissions
-            let test_file = current_dir.join(".gitz_test");
-            if std::fs::File::create(&test_file).is_err() {
-                eprintln!("Error: Cannot initialize git repository in current directory due to insufficient permissions.");
-                eprintln!("Please run gitz in a directory where you have write permissions, or specify a repository path:");
-                eprintln!("  gitz /path/to/repo");
-                std::process::exit(1);
-            } else {
-                // Clean up test file
-                let _ = std::fs::remove_file(test_file);
-            }
+    if let Some(command) = cli.command {
+        if let Err(e) = command.run(&mut std::io::stdout()) {
+            eprintln!("error: {}", e);
+            std::process::exit(e.exit_code());
         }
+        return Ok(());
     }
 
-    let mut app = App::new(cli.repo_path, cfg).await?;
+    let repo = match Repository::discover(&cli.repo_path) {
+        Ok(repo) => repo,
+        Err(GitzError::RepoNotFound(_)) if confirm_init(&cli.repo_path)? => commands::init::init(&cli.repo_path)?,
+        Err(e @ GitzError::RepoNotFound(_)) => {
+            eprintln!("error: {}", e);
+            eprintln!("Run `gitpulse init` to create one.");
+            std::process::exit(e.exit_code());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut app = App::new(repo, cfg).await?;
     app.run().await?;
     Ok(())
 }
@@ -0,0 +1,25 @@
+#![allow(dead_code)]
+
+use crate::git::{Decoration, RefKind};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+
+/// Ref labels shown next to a commit in a list, e.g. `(main) (tag: v1.0)`.
+pub fn decoration_spans(labels: &[Decoration]) -> Vec<Span<'static>> {
+    labels
+        .iter()
+        .map(|d| {
+            let color = match d.kind {
+                RefKind::Head => Color::Cyan,
+                RefKind::LocalBranch => Color::Green,
+                RefKind::RemoteBranch => Color::Red,
+                RefKind::Tag => Color::Yellow,
+            };
+            let text = match d.kind {
+                RefKind::Tag => format!("tag: {}", d.name),
+                _ => d.name.clone(),
+            };
+            Span::styled(format!("({}) ", text), Style::default().fg(color).add_modifier(Modifier::BOLD))
+        })
+        .collect()
+}
@@ -0,0 +1,106 @@
+#![allow(dead_code)]
+
+use crate::git::{DiffLine, DiffLineKind, FileDiff};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+/// Header line shown above each file.
+pub fn file_header_line(file: &FileDiff) -> Line<'static> {
+    let name = match (&file.old_path, &file.new_path) {
+        (Some(old), Some(new)) if old != new => format!("{} → {}", old, new),
+        _ => file.path().to_string(),
+    };
+    Line::from(vec![
+        Span::styled(name, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" ({})", file.change.label()), Style::default().fg(Color::DarkGray)),
+        Span::styled(format!(" +{}", file.additions()), Style::default().fg(Color::Green)),
+        Span::styled(format!(" -{}", file.deletions()), Style::default().fg(Color::Red)),
+    ])
+}
+
+/// Hunk header (`@@ -a,b +c,d @@ ...`).
+pub fn hunk_header_line(header: &str) -> Line<'static> {
+    Line::from(Span::styled(header.to_string(), Style::default().fg(Color::Cyan)))
+}
+
+/// A single diff line with an optional old/new line number gutter.
+pub fn diff_line(line: &DiffLine, show_line_numbers: bool) -> Line<'static> {
+    let style = match line.kind {
+        DiffLineKind::Addition => Style::default().fg(Color::Green),
+        DiffLineKind::Deletion => Style::default().fg(Color::Red),
+        DiffLineKind::Context => Style::default().fg(Color::Gray),
+    };
+    let mut spans = Vec::with_capacity(3);
+    if show_line_numbers {
+        let num = |n: Option<u32>| n.map(|n| format!("{:>4}", n)).unwrap_or_else(|| "    ".into());
+        spans.push(Span::styled(
+            format!("{} {} │", num(line.old_lineno), num(line.new_lineno)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    spans.push(Span::styled(format!("{}{}", line.kind.prefix(), line.content), style));
+    if !line.has_newline {
+        spans.push(Span::styled("  \\ no newline at end of file", Style::default().fg(Color::DarkGray)));
+    }
+    Line::from(spans)
+}
+
+/// A rendered diff line together with the part of the model it shows.
+#[derive(Debug, Clone)]
+pub struct DiffRow {
+    pub line: Line<'static>,
+    /// Index of the file in the rendered slice, `None` for spacer rows.
+    pub file: Option<usize>,
+    /// Index of the hunk in the file, `None` for file headers.
+    pub hunk: Option<usize>,
+    /// Index of the line in the hunk, `None` for hunk headers.
+    pub line_index: Option<usize>,
+}
+
+impl DiffRow {
+    fn new(line: Line<'static>, file: Option<usize>, hunk: Option<usize>, line_index: Option<usize>) -> Self {
+        Self { line, file, hunk, line_index }
+    }
+}
+
+/// Render a set of file diffs into rows that remember their origin.
+pub fn diff_rows(files: &[FileDiff], show_line_numbers: bool) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    for (file_idx, file) in files.iter().enumerate() {
+        rows.push(DiffRow::new(file_header_line(file), Some(file_idx), None, None));
+        if file.binary {
+            let note = Line::from(Span::styled("Binary file differs", Style::default().fg(Color::DarkGray)));
+            rows.push(DiffRow::new(note, Some(file_idx), None, None));
+            continue;
+        }
+        for (hunk_idx, hunk) in file.hunks.iter().enumerate() {
+            rows.push(DiffRow::new(hunk_header_line(&hunk.header), Some(file_idx), Some(hunk_idx), None));
+            for (line_idx, line) in hunk.lines.iter().enumerate() {
+                rows.push(DiffRow::new(
+                    diff_line(line, show_line_numbers),
+                    Some(file_idx),
+                    Some(hunk_idx),
+                    Some(line_idx),
+                ));
+            }
+        }
+        rows.push(DiffRow::new(Line::default(), None, None, None));
+    }
+    rows
+}
+
+/// Render a set of file diffs into styled lines.
+pub fn diff_lines(files: &[FileDiff], show_line_numbers: bool) -> Vec<Line<'static>> {
+    diff_rows(files, show_line_numbers).into_iter().map(|r| r.line).collect()
+}
+
+/// Draw pre-rendered diff lines in a bordered, vertically scrolled pane.
+pub fn draw_diff(f: &mut Frame, area: Rect, title: &str, lines: Vec<Line<'static>>, scroll: u16) {
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().title(title.to_string()).borders(Borders::ALL))
+        .scroll((scroll, 0));
+    f.render_widget(paragraph, area);
+}
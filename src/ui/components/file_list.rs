@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use crate::git::RepoStatus;
+use crate::git::{ChangeKind, RepoStatus, StatusEntry, StatusSection};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};  // GEÄNDERT: Spans -> Line
@@ -10,13 +10,52 @@ use ratatui::Frame;
 /// Render the list of changed files.
 pub fn draw_file_list(
     f: &mut Frame,  // GEÄNDERT: Kein <B: Backend> mehr
-    area: Rect, 
+    area: Rect,
     status: &RepoStatus
 ) {
     draw_file_list_with_selection(f, area, status, None);
 }
 
+/// Icon and colour for a change kind.
+fn change_marker(kind: ChangeKind) -> (&'static str, Color) {
+    match kind {
+        ChangeKind::Added => ("✚ ", Color::Green),
+        ChangeKind::Modified => ("● ", Color::Yellow),
+        ChangeKind::Deleted => ("✖ ", Color::Red),
+        ChangeKind::Renamed | ChangeKind::Copied => ("➜ ", Color::Cyan),
+        ChangeKind::Typechange => ("◆ ", Color::Magenta),
+        ChangeKind::Untracked => ("? ", Color::Green),
+        ChangeKind::Unreadable => ("! ", Color::DarkGray),
+    }
+}
+
+/// One list row for a status entry.
+fn entry_line(entry: &StatusEntry) -> Line<'static> {
+    if let Some(conflict) = entry.conflict {
+        return Line::from(vec![
+            Span::styled("⚠ ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::raw(entry.path.clone()),
+            Span::styled(format!(" ({})", conflict.description()), Style::default().fg(Color::DarkGray)),
+        ]);
+    }
+    let Some(change) = &entry.change else {
+        return Line::from(Span::raw(entry.path.clone()));
+    };
+    let (icon, color) = change_marker(change.kind);
+    let name = match &change.renamed_from {
+        Some(from) => format!("{} → {}", from, entry.path),
+        None => entry.path.clone(),
+    };
+    let mut spans = vec![Span::styled(icon, Style::default().fg(color)), Span::raw(name)];
+    if entry.submodule {
+        spans.push(Span::styled(" [submodule]", Style::default().fg(Color::Magenta)));
+    }
+    Line::from(spans)
+}
+
 /// Render the list with optional selection highlighting.
+///
+/// `selected_index` counts status entries only; section headers are skipped.
 pub fn draw_file_list_with_selection(
     f: &mut Frame,
     area: Rect,
@@ -24,32 +63,35 @@ pub fn draw_file_list_with_selection(
     selected_index: Option<usize>,
 ) {
     let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_row = None;
+    let mut entry_index = 0;
 
-    // Modified files (yellow)
-    for file in &status.modified {
-        let line = Line::from(vec![
-            Span::styled("● ", Style::default().fg(Color::Yellow)),
-            Span::raw(file),
-        ]);
-        items.push(ListItem::new(line));
-    }
-
-    // Added files (green)
-    for file in &status.added {
-        let line = Line::from(vec![
-            Span::styled("✚ ", Style::default().fg(Color::Green)),
-            Span::raw(file),
-        ]);
-        items.push(ListItem::new(line));
-    }
-
-    // Deleted files (red)
-    for file in &status.deleted {
-        let line = Line::from(vec![
-            Span::styled("✖ ", Style::default().fg(Color::Red)),
-            Span::raw(file),
-        ]);
-        items.push(ListItem::new(line));
+    for section in StatusSection::all() {
+        let entries: Vec<StatusEntry> = status
+            .entries()
+            .into_iter()
+            .filter(|e| e.section == section)
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        let header_color = match section {
+            StatusSection::Staged => Color::Green,
+            StatusSection::Unstaged => Color::Yellow,
+            StatusSection::Untracked => Color::Gray,
+            StatusSection::Conflicts => Color::Red,
+        };
+        items.push(ListItem::new(Line::from(Span::styled(
+            format!("{} ({})", section.title(), entries.len()),
+            Style::default().fg(header_color).add_modifier(Modifier::BOLD),
+        ))));
+        for entry in &entries {
+            if selected_index == Some(entry_index) {
+                selected_row = Some(items.len());
+            }
+            items.push(ListItem::new(entry_line(entry)));
+            entry_index += 1;
+        }
     }
 
     // If no changes, show a message
@@ -76,37 +118,41 @@ pub fn draw_file_list_with_selection(
         )
         .highlight_symbol("» ");
 
-    // If we have a selected index, use stateful rendering
-    if let Some(index) = selected_index {
+    // If we have a selected entry, use stateful rendering
+    if let Some(row) = selected_row {
         let mut state = ListState::default();
-        state.select(Some(index));
+        state.select(Some(row));
         f.render_stateful_widget(list, area, &mut state);
     } else {
         f.render_widget(list, area);
     }
 }
 
-/// Helper to get the file at a given index across all categories
-pub fn get_file_at_index(status: &RepoStatus, index: usize) -> Option<String> {
-    let all_files = status.all_files();
-    all_files.get(index).cloned()
+/// Helper to get the entry at a given index across all sections
+pub fn get_file_at_index(status: &RepoStatus, index: usize) -> Option<StatusEntry> {
+    status.entries().into_iter().nth(index)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::{FileChange, FileStatus};
 
     #[test]
     fn test_get_file_at_index() {
         let status = RepoStatus {
-            modified: vec!["mod.rs".to_string()],
-            added: vec!["new.rs".to_string()],
-            deleted: vec!["old.rs".to_string()],
+            files: vec![
+                FileStatus::new("mod.rs")
+                    .with_index(FileChange::new(ChangeKind::Modified))
+                    .with_worktree(FileChange::new(ChangeKind::Modified)),
+                FileStatus::new("new.rs").with_worktree(FileChange::new(ChangeKind::Untracked)),
+            ],
         };
 
-        assert_eq!(get_file_at_index(&status, 0), Some("mod.rs".to_string()));
-        assert_eq!(get_file_at_index(&status, 1), Some("new.rs".to_string()));
-        assert_eq!(get_file_at_index(&status, 2), Some("old.rs".to_string()));
-        assert_eq!(get_file_at_index(&status, 3), None);
+        let at = |i| get_file_at_index(&status, i).map(|e| (e.section, e.path));
+        assert_eq!(at(0), Some((StatusSection::Staged, "mod.rs".to_string())));
+        assert_eq!(at(1), Some((StatusSection::Unstaged, "mod.rs".to_string())));
+        assert_eq!(at(2), Some((StatusSection::Untracked, "new.rs".to_string())));
+        assert_eq!(at(3), None);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,136 @@
+#![allow(dead_code)]
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+/// Outcome of feeding a key to a text input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputResult {
+    /// The input is still being edited.
+    Pending,
+    /// Enter was pressed; carries the entered text.
+    Submit(String),
+    /// Esc was pressed.
+    Cancel,
+}
+
+/// Single-line text prompt drawn as a popup.
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    title: String,
+    value: String,
+    /// Cursor position in characters.
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn new(title: impl Into<String>, initial: impl Into<String>) -> Self {
+        let value = initial.into();
+        let cursor = value.chars().count();
+        Self { title: title.into(), value, cursor }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn byte_offset(&self, chars: usize) -> usize {
+        self.value.char_indices().nth(chars).map_or(self.value.len(), |(i, _)| i)
+    }
+
+    /// Apply a key press.
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputResult {
+        match key.code {
+            KeyCode::Enter => return InputResult::Submit(self.value.clone()),
+            KeyCode::Esc => return InputResult::Cancel,
+            KeyCode::Char(c) => {
+                let at = self.byte_offset(self.cursor);
+                self.value.insert(at, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let at = self.byte_offset(self.cursor);
+                self.value.remove(at);
+            }
+            KeyCode::Delete if self.cursor < self.value.chars().count() => {
+                let at = self.byte_offset(self.cursor);
+                self.value.remove(at);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.value.chars().count()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.chars().count(),
+            _ => {}
+        }
+        InputResult::Pending
+    }
+
+    /// Draw the prompt centred over `area`.
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let popup = centered_rect(60, 3, area);
+        let (before, after) = self.value.split_at(self.byte_offset(self.cursor));
+        let mut after_chars = after.chars();
+        let at_cursor = after_chars.next().map(String::from).unwrap_or_else(|| " ".into());
+        let line = Line::from(vec![
+            Span::raw(before.to_string()),
+            Span::styled(at_cursor, Style::default().bg(Color::White).fg(Color::Black)),
+            Span::raw(after_chars.as_str().to_string()),
+        ]);
+        let widget = Paragraph::new(line).block(
+            Block::default()
+                .title(format!("{} (Enter to confirm, Esc to cancel)", self.title))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(widget, popup);
+    }
+}
+
+/// A rectangle `percent_x` wide and `height` rows tall, centred in `area`.
+pub fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(height.min(area.height)),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_editing_and_submit() {
+        let mut input = TextInput::new("Branch", "fea");
+        input.handle_key(key(KeyCode::Char('t')));
+        input.handle_key(key(KeyCode::Left));
+        input.handle_key(key(KeyCode::Backspace));
+        assert_eq!(input.value(), "fet");
+        input.handle_key(key(KeyCode::Home));
+        input.handle_key(key(KeyCode::Delete));
+        assert_eq!(input.handle_key(key(KeyCode::Enter)), InputResult::Submit("et".into()));
+        assert_eq!(input.handle_key(key(KeyCode::Esc)), InputResult::Cancel);
+    }
+}
@@ -1,2 +1,7 @@
+pub mod decorations;
+pub mod diff_view;
 pub mod file_list;
+pub mod input;
+pub mod reset_prompt;
 pub mod status_bar;
+pub mod text_area;
@@ -0,0 +1,161 @@
+#![allow(dead_code)]
+
+use crate::errors::GitzError;
+use crate::git::{HardResetChoice, Repository, ResetMode, StatusEntry};
+use crate::ui::components::input::centered_rect;
+use crossterm::event::{KeyCode, KeyEvent};
+use git2::Oid;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+/// Rows of the hard reset popup besides the losses: borders, heading,
+/// blank lines and keys.
+const LOSS_CHROME: u16 = 6;
+
+/// Popup that resets the current branch to a commit: asks for the mode,
+/// and before a hard reset lists the changes it would lose.
+#[derive(Debug, Clone)]
+pub struct ResetPrompt {
+    target: Oid,
+    /// What is reset to, e.g. `abc1234 Fix typo`.
+    label: String,
+    /// Set once a hard reset was asked for and would lose these changes.
+    losses: Option<Vec<StatusEntry>>,
+}
+
+impl ResetPrompt {
+    pub fn new(target: Oid, label: impl Into<String>) -> Self {
+        Self { target, label: label.into(), losses: None }
+    }
+
+    /// Apply a key press. Returns the status line once the prompt is done.
+    pub fn handle_key(&mut self, key: KeyEvent, repo: &mut Repository) -> Option<String> {
+        let short = self.target.to_string()[..7].to_string();
+        let result = match (&self.losses, key.code) {
+            (None, KeyCode::Char('s')) => self.run(repo, ResetMode::Soft, HardResetChoice::Cancel),
+            (None, KeyCode::Char('m')) => self.run(repo, ResetMode::Mixed, HardResetChoice::Cancel),
+            (None, KeyCode::Char('h')) => match repo.reset_losses(self.target) {
+                Ok(losses) if !losses.is_empty() => {
+                    self.losses = Some(losses);
+                    return None;
+                }
+                Ok(_) => self.run(repo, ResetMode::Hard, HardResetChoice::Cancel),
+                Err(e) => Err(e),
+            },
+            (Some(_), KeyCode::Char('s')) => self.run(repo, ResetMode::Hard, HardResetChoice::StashFirst),
+            (Some(_), KeyCode::Char('d')) => self.run(repo, ResetMode::Hard, HardResetChoice::Discard),
+            (Some(_), _) => self.run(repo, ResetMode::Hard, HardResetChoice::Cancel),
+            (None, _) => Err(GitzError::Cancelled),
+        };
+        Some(match result {
+            Ok((mode, Some(stash))) => format!(
+                "Stashed changes as {}, then reset ({}) to {}",
+                &stash.to_string()[..7],
+                mode.label(),
+                short
+            ),
+            Ok((mode, None)) => format!("Reset ({}) to {}", mode.label(), short),
+            Err(GitzError::Cancelled) => "Reset cancelled".to_string(),
+            Err(e) => format!("Error: {}", e),
+        })
+    }
+
+    fn run(&self, repo: &mut Repository, mode: ResetMode, choice: HardResetChoice) -> Result<(ResetMode, Option<Oid>), GitzError> {
+        repo.reset(self.target, mode, &mut |_| choice).map(|stash| (mode, stash))
+    }
+
+    fn loss_line(entry: &StatusEntry) -> Line<'static> {
+        let change = match (&entry.change, entry.conflict) {
+            (_, Some(conflict)) => conflict.description().to_string(),
+            (Some(change), None) => change.kind.label().to_string(),
+            (None, None) => String::new(),
+        };
+        Line::from(vec![
+            Span::styled(format!("  {:<10}", entry.section.title()), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{:<16}", change), Style::default().fg(Color::Yellow)),
+            Span::raw(entry.path.clone()),
+        ])
+    }
+
+    /// Draw the popup centred over `area`.
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let (lines, height) = match &self.losses {
+            None => (vec![Line::from("[s]oft  [m]ixed  [h]ard  (any other key cancels)")], 3),
+            Some(losses) => {
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        format!("A hard reset would lose {} uncommitted changes:", losses.len()),
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(""),
+                ];
+                // Keep the popup on screen; losses that don't fit are counted instead.
+                let room = area.height.saturating_sub(LOSS_CHROME).max(1) as usize;
+                let shown = if losses.len() > room { room - 1 } else { losses.len() };
+                lines.extend(losses[..shown].iter().map(Self::loss_line));
+                if shown < losses.len() {
+                    lines.push(Line::from(Span::styled(
+                        format!("  …and {} more", losses.len() - shown),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+                lines.push(Line::from(""));
+                lines.push(Line::from("[s]tash them first  [d]iscard them  (any other key cancels)"));
+                let height = (lines.len() as u16 + 2).min(area.height);
+                (lines, height)
+            }
+        };
+        let popup = centered_rect(70, height, area);
+        let widget = Paragraph::new(lines).block(
+            Block::default()
+                .title(format!("Reset to {}", self.label))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(widget, popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{ChangeKind, FileChange, StatusSection};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn test_long_loss_list_fits_the_screen() {
+        let mut prompt = ResetPrompt::new(Oid::zero(), "0000000 base");
+        prompt.losses = Some(
+            (0..30)
+                .map(|i| StatusEntry {
+                    section: StatusSection::Unstaged,
+                    path: format!("file{}.txt", i),
+                    change: Some(FileChange::new(ChangeKind::Modified)),
+                    conflict: None,
+                    submodule: false,
+                })
+                .collect(),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(100, 12)).unwrap();
+        terminal.draw(|f| prompt.draw(f, f.area())).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect();
+        assert!(rows[0].contains("Reset to 0000000 base"));
+        assert!(rows[1].contains("A hard reset would lose 30 uncommitted changes:"));
+        assert!(rows[3].contains("file0.txt"));
+        assert!(rows[7].contains("file4.txt"));
+        assert!(rows[8].contains("…and 25 more"));
+        assert!(rows[10].contains("[s]tash them first"));
+        assert!(rows[11].contains('└'));
+    }
+}
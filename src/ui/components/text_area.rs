@@ -0,0 +1,197 @@
+#![allow(dead_code)]
+
+use crate::ui::components::input::InputResult;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+/// Multi-line text editor. Enter inserts a newline, Ctrl+S submits, Esc cancels.
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    lines: Vec<String>,
+    /// Cursor row.
+    row: usize,
+    /// Cursor column in characters.
+    col: usize,
+}
+
+impl TextArea {
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+        // A trailing newline should not leave the cursor on an extra empty line.
+        if lines.len() > 1 && lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        Self { lines, row: 0, col: 0 }
+    }
+
+    /// The whole text, lines joined with `\n`.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    fn byte_offset(&self, row: usize, chars: usize) -> usize {
+        let line = &self.lines[row];
+        line.char_indices().nth(chars).map_or(line.len(), |(i, _)| i)
+    }
+
+    /// Apply a key press.
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputResult {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('s') if ctrl => return InputResult::Submit(self.text()),
+            KeyCode::Esc => return InputResult::Cancel,
+            KeyCode::Char(c) => {
+                let at = self.byte_offset(self.row, self.col);
+                self.lines[self.row].insert(at, c);
+                self.col += 1;
+            }
+            KeyCode::Tab => {
+                let at = self.byte_offset(self.row, self.col);
+                self.lines[self.row].insert_str(at, "    ");
+                self.col += 4;
+            }
+            KeyCode::Enter => {
+                let at = self.byte_offset(self.row, self.col);
+                let rest = self.lines[self.row].split_off(at);
+                self.row += 1;
+                self.col = 0;
+                self.lines.insert(self.row, rest);
+            }
+            KeyCode::Backspace => {
+                if self.col > 0 {
+                    self.col -= 1;
+                    let at = self.byte_offset(self.row, self.col);
+                    self.lines[self.row].remove(at);
+                } else if self.row > 0 {
+                    let line = self.lines.remove(self.row);
+                    self.row -= 1;
+                    self.col = self.line_len(self.row);
+                    self.lines[self.row].push_str(&line);
+                }
+            }
+            KeyCode::Delete => {
+                if self.col < self.line_len(self.row) {
+                    let at = self.byte_offset(self.row, self.col);
+                    self.lines[self.row].remove(at);
+                } else if self.row + 1 < self.lines.len() {
+                    let next = self.lines.remove(self.row + 1);
+                    self.lines[self.row].push_str(&next);
+                }
+            }
+            KeyCode::Left => {
+                if self.col > 0 {
+                    self.col -= 1;
+                } else if self.row > 0 {
+                    self.row -= 1;
+                    self.col = self.line_len(self.row);
+                }
+            }
+            KeyCode::Right => {
+                if self.col < self.line_len(self.row) {
+                    self.col += 1;
+                } else if self.row + 1 < self.lines.len() {
+                    self.row += 1;
+                    self.col = 0;
+                }
+            }
+            KeyCode::Up if self.row > 0 => {
+                self.row -= 1;
+                self.col = self.col.min(self.line_len(self.row));
+            }
+            KeyCode::Down if self.row + 1 < self.lines.len() => {
+                self.row += 1;
+                self.col = self.col.min(self.line_len(self.row));
+            }
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.line_len(self.row),
+            _ => {}
+        }
+        InputResult::Pending
+    }
+
+    /// Draw the editor inside a bordered block, scrolled to keep the cursor visible.
+    pub fn draw(&self, f: &mut Frame, area: Rect, title: &str) {
+        let height = area.height.saturating_sub(2) as usize;
+        let scroll = (self.row + 1).saturating_sub(height);
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(row, text)| {
+                if row != self.row {
+                    return Line::from(text.clone());
+                }
+                let (before, after) = text.split_at(self.byte_offset(row, self.col));
+                let mut after_chars = after.chars();
+                let at_cursor = after_chars.next().map(String::from).unwrap_or_else(|| " ".into());
+                Line::from(vec![
+                    Span::raw(before.to_string()),
+                    Span::styled(at_cursor, Style::default().bg(Color::White).fg(Color::Black)),
+                    Span::raw(after_chars.as_str().to_string()),
+                ])
+            })
+            .collect();
+        let widget = Paragraph::new(lines).scroll((scroll as u16, 0)).block(
+            Block::default()
+                .title(title.to_string())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(widget, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_str(area: &mut TextArea, text: &str) {
+        for c in text.chars() {
+            area.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_multiline_editing() {
+        let mut area = TextArea::new("");
+        type_str(&mut area, "subject");
+        area.handle_key(key(KeyCode::Enter));
+        area.handle_key(key(KeyCode::Enter));
+        type_str(&mut area, "body");
+        assert_eq!(area.text(), "subject\n\nbody");
+        assert_eq!(area.cursor(), (2, 4));
+
+        area.handle_key(key(KeyCode::Home));
+        area.handle_key(key(KeyCode::Backspace));
+        assert_eq!(area.text(), "subject\nbody");
+        area.handle_key(key(KeyCode::Up));
+        area.handle_key(key(KeyCode::End));
+        area.handle_key(key(KeyCode::Delete));
+        assert_eq!(area.text(), "subjectbody");
+
+        let submit = area.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert_eq!(submit, InputResult::Submit("subjectbody".into()));
+        assert_eq!(area.handle_key(key(KeyCode::Esc)), InputResult::Cancel);
+    }
+
+    #[test]
+    fn test_new_drops_single_trailing_newline() {
+        assert_eq!(TextArea::new("a\n# b\n").text(), "a\n# b");
+    }
+}
@@ -0,0 +1,326 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::{AppEvent, TaskOrigin};
+use crate::git::{BisectMark, BisectState, BisectStep, CommitInfo, Repository};
+use crate::ui::components::input::{InputResult, TextInput};
+use crate::ui::components::status_bar;
+use crate::utils::relative_time;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+use tokio::sync::mpsc::Sender;
+
+fn mark_color(mark: BisectMark) -> Color {
+    match mark {
+        BisectMark::Good => Color::Green,
+        BisectMark::Bad => Color::Red,
+        BisectMark::Skip => Color::DarkGray,
+    }
+}
+
+/// The bisect view – walks a bisect to the first bad commit, marking each
+/// checked-out commit by hand or with a test command.
+pub struct BisectView {
+    state: Option<BisectState>,
+    step: Option<BisectStep>,
+    /// Marked commits: the bad one, then good ones, then skipped ones.
+    marks: Vec<(BisectMark, CommitInfo)>,
+    /// The commit being tested, or the first bad commit once found.
+    current: Option<CommitInfo>,
+    /// Test command from the config, or the one last run.
+    command: Option<String>,
+    command_prompt: Option<TextInput>,
+    /// Description of the running test command, if any.
+    running: Option<String>,
+    status_message: String,
+    event_tx: Sender<AppEvent>,
+}
+
+impl BisectView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            state: None,
+            step: None,
+            marks: Vec::new(),
+            current: None,
+            command: None,
+            command_prompt: None,
+            running: None,
+            status_message: "Ready".to_string(),
+            event_tx,
+        }
+    }
+
+    /// Reload the bisect state, e.g. after `git bisect` ran elsewhere.
+    pub fn refresh(&mut self, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        if self.command.is_none() {
+            self.command = cfg.git.bisect_command.clone();
+        }
+        self.load(repo)?;
+        self.status_message = match &self.step {
+            Some(step) => step.describe(),
+            None => "No bisect in progress".to_string(),
+        };
+        Ok(())
+    }
+
+    fn load(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.state = repo.bisect_state()?;
+        self.marks.clear();
+        self.current = None;
+        self.step = match &self.state {
+            Some(state) => {
+                let marked = state.bad.iter().map(|oid| (BisectMark::Bad, *oid))
+                    .chain(state.good.iter().map(|oid| (BisectMark::Good, *oid)))
+                    .chain(state.skipped.iter().map(|oid| (BisectMark::Skip, *oid)));
+                for (mark, oid) in marked {
+                    self.marks.push((mark, repo.find_commit(oid)?));
+                }
+                Some(repo.bisect_step(state)?)
+            }
+            None => None,
+        };
+        self.current = match &self.step {
+            Some(BisectStep::Testing { commit, .. } | BisectStep::Found(commit)) => Some(repo.find_commit(*commit)?),
+            _ => None,
+        };
+        Ok(())
+    }
+
+    /// Called when a test command run has finished.
+    pub fn task_finished(&mut self, repo: &Repository, cfg: &Config, result: Result<String, String>) -> Result<(), GitzError> {
+        self.running = None;
+        self.refresh(repo, cfg)?;
+        self.status_message = match result {
+            Ok(summary) => summary,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (a prompt is open).
+    pub fn captures_input(&self) -> bool {
+        self.command_prompt.is_some()
+    }
+
+    /// Mark the checked-out commit, starting a bisect first if needed.
+    fn mark_head(&self, repo: &Repository, mark: BisectMark) -> Result<BisectStep, GitzError> {
+        let head = repo
+            .head_oid()
+            .ok_or_else(|| GitzError::InvalidInput("Nothing to bisect: no commits yet".into()))?;
+        if self.state.is_none() {
+            if mark == BisectMark::Skip {
+                return Err(GitzError::InvalidInput("No bisect in progress".into()));
+            }
+            repo.bisect_start()?;
+        }
+        repo.bisect_mark(head, mark)
+    }
+
+    fn mark(&mut self, repo: &Repository, mark: BisectMark) -> Result<(), GitzError> {
+        let result = self.mark_head(repo, mark);
+        self.load(repo)?;
+        self.status_message = match result {
+            Ok(step) => step.describe(),
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Run `command` at each step on a blocking thread until the bisect is over.
+    fn start_run(&mut self, repo: &Repository, command: String) {
+        if !matches!(self.step, Some(BisectStep::Testing { .. })) {
+            self.status_message = "Mark a bad and a good commit before running a test command".to_string();
+            return;
+        }
+        self.command = Some(command.clone());
+        let label = format!("Running `{}`", command);
+        self.status_message = format!("{}...", label);
+        self.running = Some(label);
+
+        let repo = repo.clone();
+        let tx = self.event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut tested = 0;
+            let result = repo
+                .bisect_run(&command, &mut |_, _| tested += 1)
+                .map(|step| format!("Tested {} commits: {}", tested, step.describe()))
+                .map_err(|e| e.to_string());
+            let _ = tx.blocking_send(AppEvent::TaskDone { origin: TaskOrigin::Bisect, result });
+        });
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if let Some(mut input) = self.command_prompt.take() {
+            match input.handle_key(key) {
+                InputResult::Pending => self.command_prompt = Some(input),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(command) if command.trim().is_empty() => {
+                    self.status_message = "No test command given".to_string();
+                }
+                InputResult::Submit(command) => self.start_run(repo, command.trim().to_string()),
+            }
+            return Ok(false);
+        }
+        if let Some(running) = &self.running {
+            if key.code != KeyCode::Char('q') {
+                self.status_message = format!("Busy: {}", running);
+                return Ok(false);
+            }
+        }
+
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => self.refresh(repo, cfg)?,
+            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Char('g') => self.mark(repo, BisectMark::Good)?,
+            KeyCode::Char('b') => self.mark(repo, BisectMark::Bad)?,
+            KeyCode::Char('s') => self.mark(repo, BisectMark::Skip)?,
+            KeyCode::Char('a') => {
+                let current = self.command.clone().unwrap_or_default();
+                self.command_prompt = Some(TextInput::new("Test command (exit 0 = good, 125 = skip, else bad)", current));
+            }
+            KeyCode::Char('R') if self.state.is_some() => {
+                let result = repo.bisect_reset();
+                self.load(repo)?;
+                self.status_message = match result {
+                    Ok(()) => "Bisect reset".to_string(),
+                    Err(e) => format!("Error: {}", e),
+                };
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn commit_line(label: &str, color: Color, commit: &CommitInfo) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("{:<6}", label), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{} ", commit.short_id()), Style::default().fg(Color::Yellow)),
+            Span::raw(commit.summary().to_string()),
+        ])
+    }
+
+    fn step_lines(&self, repo: &Repository) -> Vec<Line<'static>> {
+        let Some(step) = &self.step else {
+            return vec![
+                Line::from("No bisect in progress."),
+                Line::from(""),
+                Line::from("Check out a commit that has the bug and press [b], then one without it and press [g];"),
+                Line::from("or mark commits with [m] in the Commits tab."),
+            ];
+        };
+        let mut lines = vec![
+            Line::from(Span::styled(step.describe(), Style::default().add_modifier(Modifier::BOLD))),
+            Line::from(""),
+        ];
+        match step {
+            BisectStep::NeedMarks => {
+                lines.push(Line::from("Check out a commit and mark it [g]ood or [b]ad."));
+            }
+            BisectStep::OnlySkipped(commits) => {
+                for oid in commits {
+                    if let Ok(commit) = repo.find_commit(*oid) {
+                        lines.push(Self::commit_line("", Color::White, &commit));
+                    }
+                }
+            }
+            BisectStep::Testing { .. } | BisectStep::Found(_) => {
+                if let Some(commit) = &self.current {
+                    lines.push(Line::from(format!("Commit: {}", commit.oid)));
+                    lines.push(Line::from(format!("Author: {} <{}>", commit.author, commit.email)));
+                    lines.push(Line::from(format!("Date:   {}", relative_time(commit.time))));
+                    lines.push(Line::from(""));
+                    lines.extend(commit.message.trim_end().lines().map(|l| Line::from(format!("    {}", l))));
+                }
+            }
+        }
+        lines
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let mut top_text = format!("gitz - Branch: {}", branch_name);
+        if let Some(state) = &self.state {
+            top_text.push_str(&format!("   Bisecting (started on {})", state.start));
+        }
+        if let Some(command) = &self.command {
+            top_text.push_str(&format!("   Test: {}", command));
+        }
+        if let Some(running) = &self.running {
+            top_text.push_str(&format!("   {}...", running));
+        }
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Bisect"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.marks.is_empty() {
+            vec![ListItem::new(Span::styled("No marked commits", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.marks
+                .iter()
+                .map(|(mark, commit)| ListItem::new(Self::commit_line(mark.label(), mark_color(*mark), commit)))
+                .collect()
+        };
+        let list = List::new(items).block(Block::default().borders(Borders::ALL).title("Marked"));
+        f.render_widget(list, main_chunks[0]);
+
+        let title = match &self.step {
+            Some(BisectStep::Found(_)) => "First bad commit",
+            Some(BisectStep::OnlySkipped(_)) => "Possible first bad commits",
+            Some(BisectStep::Testing { .. }) => "Testing",
+            _ => "Bisect",
+        };
+        let details = Paragraph::new(self.step_lines(repo))
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(details, main_chunks[1]);
+
+        let help_text = format!(
+            "{} | [g]ood [b]ad [s]kip [a]uto-run test [R]eset bisect [r]efresh [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        if let Some(input) = &self.command_prompt {
+            input.draw(f, size);
+        }
+
+        Ok(())
+    }
+}
+
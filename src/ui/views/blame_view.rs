@@ -0,0 +1,280 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::AppEvent;
+use crate::git::{Blame, BlameLine, CommitInfo, FileDiff, Repository};
+use crate::ui::components::{diff_view, status_bar};
+use crate::utils::relative_time;
+use crossterm::event::{KeyCode, KeyEvent};
+use git2::Oid;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use tokio::sync::mpsc::Sender;
+
+/// Lines moved by PageUp/PageDown.
+const PAGE: usize = 20;
+
+/// Width of the author column.
+const AUTHOR_WIDTH: usize = 14;
+
+/// Colors from oldest to newest, indexed by `Blame::recency`.
+const RECENCY_COLORS: [Color; 4] = [Color::DarkGray, Color::Gray, Color::Yellow, Color::LightGreen];
+
+/// The commit behind the selected line, with its changes to the file.
+struct BlameDetails {
+    commit: CommitInfo,
+    files: Vec<FileDiff>,
+    scroll: u16,
+}
+
+/// The blame view – who last touched each line of a file, and when.
+pub struct BlameView {
+    blame: Option<Blame>,
+    selected_index: usize,
+    /// Earlier blames to return to, with their selected line.
+    history: Vec<(Blame, usize)>,
+    details: Option<BlameDetails>,
+    status_message: String,
+    context_lines: u32,
+    show_line_numbers: bool,
+    event_tx: Sender<AppEvent>,
+}
+
+impl BlameView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            blame: None,
+            selected_index: 0,
+            history: Vec::new(),
+            details: None,
+            status_message: "Ready".to_string(),
+            context_lines: 3,
+            show_line_numbers: true,
+            event_tx,
+        }
+    }
+
+    /// Blame `path` at `at` (HEAD when `None`), starting a fresh history.
+    pub fn open(&mut self, repo: &Repository, cfg: &Config, path: &str, at: Option<Oid>) -> Result<(), GitzError> {
+        self.context_lines = cfg.ui.diff_context_lines as u32;
+        self.show_line_numbers = cfg.ui.show_line_numbers;
+        self.history.clear();
+        self.blame = None;
+        self.details = None;
+        self.load(repo, path, at, 0);
+        Ok(())
+    }
+
+    /// Replace the shown blame, keeping the current one in the history.
+    fn load(&mut self, repo: &Repository, path: &str, at: Option<Oid>, line: usize) {
+        match repo.blame(path, at) {
+            Ok(blame) => {
+                if let Some(current) = self.blame.take() {
+                    self.history.push((current, self.selected_index));
+                }
+                self.selected_index = line.min(blame.lines.len().saturating_sub(1));
+                self.status_message = format!("{} lines", blame.lines.len());
+                self.blame = Some(blame);
+                self.details = None;
+            }
+            Err(e) => self.status_message = format!("Error: {}", e),
+        }
+    }
+
+    fn selected(&self) -> Option<&BlameLine> {
+        self.blame.as_ref().and_then(|b| b.lines.get(self.selected_index))
+    }
+
+    fn open_details(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        let Some(line) = self.selected().cloned() else { return Ok(()) };
+        let commit = repo.find_commit(line.oid)?;
+        let files = repo
+            .commit_diff(line.oid, self.context_lines)?
+            .into_iter()
+            .filter(|f| f.path() == line.orig_path)
+            .collect();
+        self.details = Some(BlameDetails { commit, files, scroll: 0 });
+        Ok(())
+    }
+
+    /// Blame the selected line's file as it was just before its commit.
+    fn blame_parent(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        let Some(line) = self.selected().cloned() else { return Ok(()) };
+        match repo.find_commit(line.oid)?.parents.first() {
+            Some(parent) => self.load(repo, &line.orig_path, Some(*parent), line.orig_line.saturating_sub(1)),
+            None => self.status_message = format!("{} is the root commit", line.short_id()),
+        }
+        Ok(())
+    }
+
+    fn move_selection(&mut self, repo: &Repository, delta: isize) -> Result<(), GitzError> {
+        let len = self.blame.as_ref().map_or(0, |b| b.lines.len());
+        if len == 0 {
+            return Ok(());
+        }
+        self.selected_index = self.selected_index.saturating_add_signed(delta).min(len - 1);
+        // Keep an opened details pane in sync with the cursor.
+        if self.details.as_ref().is_some_and(|d| Some(d.commit.oid) != self.selected().map(|l| l.oid)) {
+            self.open_details(repo)?;
+        }
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(repo, 1)?,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(repo, -1)?,
+            KeyCode::PageDown => self.move_selection(repo, PAGE as isize)?,
+            KeyCode::PageUp => self.move_selection(repo, -(PAGE as isize))?,
+            KeyCode::Home | KeyCode::Char('g') => self.move_selection(repo, isize::MIN)?,
+            KeyCode::End | KeyCode::Char('G') => self.move_selection(repo, isize::MAX)?,
+            KeyCode::Enter => {
+                if self.details.is_some() {
+                    self.details = None;
+                } else {
+                    self.open_details(repo)?;
+                }
+            }
+            KeyCode::Char('p') => self.blame_parent(repo)?,
+            KeyCode::Char('H') => {
+                if let Some(blame) = &self.blame {
+                    let _ = self.event_tx.try_send(AppEvent::OpenFileHistory(blame.path.clone()));
+                }
+            }
+            KeyCode::Backspace | KeyCode::Char('h') => match self.history.pop() {
+                Some((blame, selected)) => {
+                    self.blame = Some(blame);
+                    self.selected_index = selected;
+                    self.details = None;
+                }
+                None => self.status_message = "Already at the first blame".to_string(),
+            },
+            KeyCode::Char('J') => {
+                if let Some(details) = &mut self.details {
+                    details.scroll = details.scroll.saturating_add(1);
+                }
+            }
+            KeyCode::Char('K') => {
+                if let Some(details) = &mut self.details {
+                    details.scroll = details.scroll.saturating_sub(1);
+                }
+            }
+            KeyCode::Esc if self.details.is_some() => self.details = None,
+            KeyCode::Esc => {
+                let _ = self.event_tx.try_send(AppEvent::Back);
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn blame_line(blame: &Blame, idx: usize, line: &BlameLine) -> Line<'static> {
+        let color = RECENCY_COLORS[blame.recency(line) as usize];
+        // Only the first line of a run from the same commit carries its details.
+        let first_of_run = idx == 0 || blame.lines[idx - 1].oid != line.oid;
+        let meta = if first_of_run {
+            let author: String = line.author.chars().take(AUTHOR_WIDTH).collect();
+            format!("{} {:<width$} {:>14}", line.short_id(), author, relative_time(line.time), width = AUTHOR_WIDTH)
+        } else {
+            " ".repeat(7 + 1 + AUTHOR_WIDTH + 1 + 14)
+        };
+        Line::from(vec![
+            Span::styled(meta, Style::default().fg(color)),
+            Span::styled(format!(" {:>5} │ ", idx + 1), Style::default().fg(Color::DarkGray)),
+            Span::raw(line.content.clone()),
+        ])
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        _repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let top_text = match &self.blame {
+            Some(blame) => format!(
+                "gitz - Blame: {} @ {}   Depth: {}",
+                blame.path,
+                blame.at.map_or("HEAD".to_string(), |oid| oid.to_string()[..7].to_string()),
+                self.history.len()
+            ),
+            None => "gitz - Blame".to_string(),
+        };
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Blame"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(if self.details.is_some() {
+                [Constraint::Percentage(55), Constraint::Percentage(45)]
+            } else {
+                [Constraint::Percentage(100), Constraint::Percentage(0)]
+            })
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = match &self.blame {
+            Some(blame) => blame
+                .lines
+                .iter()
+                .enumerate()
+                .map(|(idx, line)| ListItem::new(Self::blame_line(blame, idx, line)))
+                .collect(),
+            None => vec![ListItem::new(Span::styled("Nothing blamed", Style::default().fg(Color::DarkGray)))],
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Lines"))
+            .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD));
+        let mut state = ListState::default();
+        if self.selected().is_some() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        if let Some(details) = &self.details {
+            let commit = &details.commit;
+            let mut lines = vec![
+                Line::from(Span::styled(format!("commit {}", commit.oid), Style::default().fg(Color::Yellow))),
+                Line::from(format!("Author: {} <{}>", commit.author, commit.email)),
+                Line::from(format!("Date:   {}", relative_time(commit.time))),
+                Line::from(""),
+            ];
+            lines.extend(commit.message.trim_end().lines().map(|l| Line::from(format!("    {}", l))));
+            lines.push(Line::from(""));
+            lines.extend(diff_view::diff_lines(&details.files, self.show_line_numbers));
+            diff_view::draw_diff(f, main_chunks[1], &commit.short_id(), lines, details.scroll);
+        }
+
+        let help_text = format!(
+            "{} | [j/k]move [Enter]commit details [p]arent blame [h]back [H]istory [J/K]scroll [Esc]close [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        Ok(())
+    }
+}
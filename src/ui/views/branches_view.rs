@@ -0,0 +1,374 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::AppEvent;
+use crate::git::{BranchInfo, BranchKind, MergeMode, Repository};
+use crate::ui::components::input::{InputResult, TextInput};
+use crate::ui::components::status_bar;
+use crate::utils::relative_time;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use tokio::sync::mpsc::Sender;
+
+/// What the open text prompt is asking for.
+#[derive(Debug, Clone)]
+enum Prompt {
+    Create { target: git2::Oid },
+    Rename { from: String },
+    Upstream { branch: String },
+}
+
+/// A delete waiting for confirmation; `force` once the branch is known to be unmerged.
+#[derive(Debug, Clone)]
+struct PendingDelete {
+    name: String,
+    kind: BranchKind,
+    force: bool,
+}
+
+/// The branches view – lists local and remote-tracking branches.
+pub struct BranchesView {
+    branches: Vec<BranchInfo>,
+    selected_index: usize,
+    status_message: String,
+    prompt: Option<(Prompt, TextInput)>,
+    pending_delete: Option<PendingDelete>,
+    /// Branch waiting for a merge mode to be picked.
+    pending_merge: Option<BranchInfo>,
+    event_tx: Sender<AppEvent>,
+}
+
+impl BranchesView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            branches: Vec::new(),
+            selected_index: 0,
+            status_message: "Ready".to_string(),
+            prompt: None,
+            pending_delete: None,
+            pending_merge: None,
+            event_tx,
+        }
+    }
+
+    /// Refresh the view data from the repository.
+    pub fn refresh(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.branches = repo.branches()?;
+        if self.selected_index >= self.branches.len() {
+            self.selected_index = self.branches.len().saturating_sub(1);
+        }
+        self.status_message = format!("Refreshed: {} branches", self.branches.len());
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (a prompt is open).
+    pub fn captures_input(&self) -> bool {
+        self.prompt.is_some()
+    }
+
+    fn selected(&self) -> Option<&BranchInfo> {
+        self.branches.get(self.selected_index)
+    }
+
+    /// Refresh and report the outcome of an operation.
+    fn finish(&mut self, repo: &Repository, result: Result<(), GitzError>, success: String) -> Result<(), GitzError> {
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(()) => success,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    fn submit_prompt(&mut self, prompt: Prompt, value: String, repo: &Repository) -> Result<(), GitzError> {
+        let value = value.trim().to_string();
+        match prompt {
+            Prompt::Create { target } => {
+                let result = repo.create_branch(&value, target);
+                self.finish(repo, result, format!("Created branch {}", value))?;
+                if let Some(idx) = self.branches.iter().position(|b| b.name == value && b.kind == BranchKind::Local) {
+                    self.selected_index = idx;
+                }
+            }
+            Prompt::Rename { from } => {
+                let result = repo.rename_branch(&from, &value);
+                self.finish(repo, result, format!("Renamed {} to {}", from, value))?;
+            }
+            Prompt::Upstream { branch } => {
+                let result = repo.set_upstream(&branch, Some(&value));
+                self.finish(repo, result, format!("{} now tracks {}", branch, value))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn confirm_delete(&mut self, pending: PendingDelete, repo: &Repository) -> Result<(), GitzError> {
+        match repo.delete_branch(&pending.name, pending.kind, pending.force) {
+            Err(GitzError::UnmergedBranch(name)) => {
+                self.status_message = format!("{} is not fully merged. Force delete? [y/N]", name);
+                self.pending_delete = Some(PendingDelete { force: true, ..pending });
+                Ok(())
+            }
+            result => self.finish(repo, result, format!("Deleted branch {}", pending.name)),
+        }
+    }
+
+    fn merge(&mut self, branch: BranchInfo, mode: MergeMode, repo: &Repository) -> Result<(), GitzError> {
+        let result = repo.merge_branch(&branch.name, branch.kind, mode);
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(outcome) => outcome.describe(),
+            Err(GitzError::MergeConflict) => {
+                // The conflicts tab takes it from here.
+                let _ = self.event_tx.try_send(AppEvent::OpenConflicts);
+                format!("Merging {} stopped on conflicts", branch.name)
+            }
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if let Some((prompt, mut input)) = self.prompt.take() {
+            match input.handle_key(key) {
+                InputResult::Pending => self.prompt = Some((prompt, input)),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(value) => self.submit_prompt(prompt, value, repo)?,
+            }
+            return Ok(false);
+        }
+
+        if let Some(pending) = self.pending_delete.take() {
+            if key.code == KeyCode::Char('y') {
+                self.confirm_delete(pending, repo)?;
+            } else {
+                self.status_message = "Delete cancelled".to_string();
+            }
+            return Ok(false);
+        }
+
+        if let Some(branch) = self.pending_merge.take() {
+            let mode = match key.code {
+                KeyCode::Char('f') => MergeMode::FastForward,
+                KeyCode::Char('n') => MergeMode::NoFastForward,
+                KeyCode::Char('s') => MergeMode::Squash,
+                _ => {
+                    self.status_message = "Merge cancelled".to_string();
+                    return Ok(false);
+                }
+            };
+            self.merge(branch, mode, repo)?;
+            return Ok(false);
+        }
+
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => {
+                // Refresh manually
+                self.refresh(repo)?;
+            }
+            KeyCode::Char('q') => {
+                return Ok(true); // Signal to quit
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.selected_index + 1 < self.branches.len() => {
+                self.selected_index += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_index > 0 => {
+                self.selected_index -= 1;
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.selected_index = 0;
+            }
+            KeyCode::End | KeyCode::Char('G') if !self.branches.is_empty() => {
+                self.selected_index = self.branches.len() - 1;
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if let Some(branch) = self.selected().cloned() {
+                    let result = repo.checkout_branch(&branch.name, branch.kind);
+                    self.finish(repo, result, format!("Checked out {}", branch.name))?;
+                }
+            }
+            KeyCode::Char('n') => {
+                if let Some(branch) = self.selected() {
+                    let title = format!("New branch from {}", branch.name);
+                    self.prompt = Some((Prompt::Create { target: branch.target }, TextInput::new(title, "")));
+                }
+            }
+            KeyCode::Char('R') => match self.selected().cloned() {
+                Some(branch) if branch.kind == BranchKind::Local => {
+                    let from = branch.name;
+                    let input = TextInput::new(format!("Rename {}", from), from.clone());
+                    self.prompt = Some((Prompt::Rename { from }, input));
+                }
+                Some(_) => self.status_message = "Only local branches can be renamed".to_string(),
+                None => {}
+            },
+            KeyCode::Char('d') => {
+                if let Some(branch) = self.selected().cloned() {
+                    self.status_message = format!("Delete branch {}? [y/N]", branch.name);
+                    self.pending_delete = Some(PendingDelete {
+                        name: branch.name,
+                        kind: branch.kind,
+                        force: false,
+                    });
+                }
+            }
+            KeyCode::Char('m') => match self.selected().cloned() {
+                Some(branch) if branch.is_head => self.status_message = "Cannot merge a branch into itself".to_string(),
+                Some(branch) => {
+                    let current = repo.current_branch().unwrap_or_else(|_| "HEAD".to_string());
+                    self.status_message = format!(
+                        "Merge {} into {}: [f]ast-forward if possible, [n]o-ff, [s]quash, any other key cancels",
+                        branch.name, current
+                    );
+                    self.pending_merge = Some(branch);
+                }
+                None => {}
+            },
+            KeyCode::Char('u') => match self.selected().cloned() {
+                Some(branch) if branch.kind == BranchKind::Local => {
+                    let initial = branch.upstream.unwrap_or_else(|| format!("origin/{}", branch.name));
+                    let input = TextInput::new(format!("Upstream for {}", branch.name), initial);
+                    self.prompt = Some((Prompt::Upstream { branch: branch.name }, input));
+                }
+                Some(_) => self.status_message = "Only local branches have an upstream".to_string(),
+                None => {}
+            },
+            KeyCode::Char('U') => {
+                if let Some(branch) = self.selected().filter(|b| b.kind == BranchKind::Local).cloned() {
+                    let result = repo.set_upstream(&branch.name, None);
+                    self.finish(repo, result, format!("Unset upstream of {}", branch.name))?;
+                }
+            }
+            KeyCode::Char('L') => match self.selected() {
+                Some(branch) if branch.kind == BranchKind::Local => {
+                    let refname = format!("refs/heads/{}", branch.name);
+                    let _ = self.event_tx.try_send(AppEvent::OpenReflog(refname));
+                }
+                Some(_) => self.status_message = "Only local branches have a reflog".to_string(),
+                None => {}
+            },
+            _ => {}
+        }
+        Ok(false) // Continue running
+    }
+
+    fn branch_line(branch: &BranchInfo) -> Line<'static> {
+        let (marker, name_style) = match (branch.is_head, branch.kind) {
+            (true, _) => ("* ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            (false, BranchKind::Local) => ("  ", Style::default().fg(Color::White)),
+            (false, BranchKind::Remote) => ("  ", Style::default().fg(Color::Red)),
+        };
+        let mut spans = vec![Span::raw(marker), Span::styled(branch.name.clone(), name_style)];
+        let tracking = branch.tracking_label();
+        if !tracking.is_empty() {
+            spans.push(Span::styled(format!(" {}", tracking), Style::default().fg(Color::Yellow)));
+        }
+        spans.push(Span::styled(format!("  {}", branch.summary), Style::default().fg(Color::Gray)));
+        Line::from(spans)
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let local = self.branches.iter().filter(|b| b.kind == BranchKind::Local).count();
+        let top_text = format!(
+            "gitz - Repository: {}   Branch: {}   Local: {}   Remote: {}",
+            repo.root().display(),
+            branch_name,
+            local,
+            self.branches.len() - local
+        );
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Branches"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(60),
+                Constraint::Percentage(40)
+            ])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.branches.is_empty() {
+            vec![ListItem::new(Span::styled("No branches", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.branches.iter().map(|b| ListItem::new(Self::branch_line(b))).collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Branches"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut state = ListState::default();
+        if !self.branches.is_empty() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        let details = match self.selected() {
+            Some(branch) => {
+                let kind = match branch.kind {
+                    BranchKind::Local => "local",
+                    BranchKind::Remote => "remote-tracking",
+                };
+                let upstream = match &branch.upstream {
+                    Some(up) => format!("{} {}", up, branch.tracking_label()),
+                    None => "none".to_string(),
+                };
+                let text = format!(
+                    "Branch:   {}\nType:     {}\nUpstream: {}\nCommit:   {}\nDate:     {}\n\n{}",
+                    branch.name,
+                    kind,
+                    upstream.trim_end(),
+                    &branch.target.to_string()[..7],
+                    relative_time(branch.time),
+                    branch.summary
+                );
+                Paragraph::new(text).style(Style::default().fg(Color::White))
+            }
+            None => Paragraph::new("No branch selected").style(Style::default().fg(Color::DarkGray)),
+        };
+        f.render_widget(details.block(Block::default().borders(Borders::ALL).title("Details")), main_chunks[1]);
+
+        let help_text = format!(
+            "{} | [Enter]checkout [n]ew [R]ename [d]elete [m]erge [u/U]set/unset upstream re[L]og [r]efresh [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        if let Some((_, input)) = &self.prompt {
+            input.draw(f, size);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,687 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::AppEvent;
+use crate::git::{BisectMark, BisectStep, CommitInfo, Decoration, FileDiff, LogCursor, PickKind, PickOptions, Repository, SequenceOutcome, SequenceState, SequenceStop};
+use crate::commands;
+use crate::ui::components::input::{centered_rect, InputResult, TextInput};
+use crate::ui::components::reset_prompt::ResetPrompt;
+use crate::ui::components::text_area::TextArea;
+use crate::ui::components::decorations::decoration_spans;
+use crate::ui::components::{diff_view, status_bar};
+use crate::utils::relative_time;
+use crossterm::event::{KeyCode, KeyEvent};
+use git2::Oid;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use std::collections::HashMap;
+use tokio::sync::mpsc::Sender;
+
+/// Load the next page once the cursor gets this close to the end of the list.
+const PREFETCH_MARGIN: usize = 10;
+
+/// Lines scrolled by PageUp/PageDown in the diff pane.
+const DIFF_PAGE: u16 = 10;
+
+/// The opened commit: its changed files and which one is shown.
+struct CommitDetails {
+    oid: Oid,
+    files: Vec<FileDiff>,
+    selected_file: usize,
+    scroll: u16,
+}
+
+/// What the message editor is open for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditPurpose {
+    Reword(Oid),
+    /// Body of a `squash!` commit targeting the given commit.
+    Squash(Oid),
+    /// Message of the commit a cherry-pick or revert paused on.
+    Sequence,
+}
+
+/// Tagging a commit: its name first, then an optional message.
+#[derive(Debug, Clone)]
+enum TagPrompt {
+    Name(Oid),
+    Message { target: Oid, name: String },
+}
+
+/// The commits view – a paginated log of the history reachable from HEAD.
+pub struct CommitsView {
+    commits: Vec<CommitInfo>,
+    decorations: HashMap<Oid, Vec<Decoration>>,
+    selected_index: usize,
+    /// Commits fetched per page (`performance.max_commits_to_load`).
+    page_size: usize,
+    /// The rest of the history after the loaded commits.
+    cursor: LogCursor,
+    details: Option<CommitDetails>,
+    editor: Option<(EditPurpose, TextArea)>,
+    /// Revision the log starts from; `None` for HEAD.
+    log_ref: Option<String>,
+    ref_prompt: Option<TextInput>,
+    tag_prompt: Option<(TagPrompt, TextInput)>,
+    reset_prompt: Option<ResetPrompt>,
+    /// Commit about to be marked good or bad for bisecting, waiting for `g` or `b`.
+    bisect_target: Option<Oid>,
+    /// Commits marked for cherry-pick or revert, in the order marked.
+    marked: Vec<Oid>,
+    /// Pause for the message of each picked or reverted commit.
+    edit_messages: bool,
+    sequence: Option<SequenceState>,
+    status_message: String,
+    context_lines: u32,
+    show_line_numbers: bool,
+    event_tx: Sender<AppEvent>,
+}
+
+impl CommitsView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            commits: Vec::new(),
+            decorations: HashMap::new(),
+            selected_index: 0,
+            page_size: 1,
+            cursor: LogCursor::default(),
+            details: None,
+            editor: None,
+            log_ref: None,
+            ref_prompt: None,
+            tag_prompt: None,
+            reset_prompt: None,
+            bisect_target: None,
+            marked: Vec::new(),
+            edit_messages: false,
+            sequence: None,
+            status_message: "Ready".to_string(),
+            context_lines: 3,
+            show_line_numbers: true,
+            event_tx,
+        }
+    }
+
+    /// Reload the history, keeping as many commits as were already loaded.
+    pub fn refresh(&mut self, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        self.page_size = cfg.performance.max_commits_to_load.max(1);
+        self.context_lines = cfg.ui.diff_context_lines as u32;
+        self.show_line_numbers = cfg.ui.show_line_numbers;
+
+        let limit = self.commits.len().max(self.page_size);
+        self.cursor = repo.log_cursor(self.log_ref.as_deref())?;
+        self.commits = repo.log_next(&mut self.cursor, limit)?;
+        self.decorations = repo.decorations()?;
+        self.sequence = repo.sequence_state()?;
+        if self.selected_index >= self.commits.len() {
+            self.selected_index = self.commits.len().saturating_sub(1);
+        }
+        if let Some(details) = &self.details {
+            let oid = details.oid;
+            self.details = None;
+            if self.commits.iter().any(|c| c.oid == oid) {
+                self.open_details(repo, oid)?;
+            }
+        }
+        self.status_message = format!("Loaded {} commits", self.commits.len());
+        Ok(())
+    }
+
+    /// Fetch the next page if the cursor is near the end of what is loaded.
+    fn load_more_if_needed(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        if self.cursor.is_done() || self.selected_index + PREFETCH_MARGIN < self.commits.len() {
+            return Ok(());
+        }
+        let page = repo.log_next(&mut self.cursor, self.page_size)?;
+        self.commits.extend(page);
+        self.status_message = format!("Loaded {} commits", self.commits.len());
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (the editor is open).
+    pub fn captures_input(&self) -> bool {
+        self.editor.is_some() || self.ref_prompt.is_some() || self.tag_prompt.is_some() || self.reset_prompt.is_some()
+    }
+
+    /// Show the log of `spec`, or of HEAD when empty.
+    fn browse(&mut self, spec: String, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        let spec = spec.trim().to_string();
+        let log_ref = (!spec.is_empty()).then_some(spec);
+        if let Some(spec) = &log_ref {
+            if let Err(e) = repo.log_cursor(Some(spec)) {
+                self.status_message = format!("Error: {}", e);
+                return Ok(());
+            }
+        }
+        self.log_ref = log_ref;
+        self.commits.clear();
+        self.selected_index = 0;
+        self.details = None;
+        self.marked.clear();
+        self.refresh(repo, cfg)
+    }
+
+    fn submit_tag_prompt(&mut self, prompt: TagPrompt, value: String, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        let value = value.trim().to_string();
+        match prompt {
+            TagPrompt::Name(target) => {
+                let title = format!("Message for {} (empty for a lightweight tag)", value);
+                self.tag_prompt = Some((TagPrompt::Message { target, name: value }, TextInput::new(title, "")));
+            }
+            TagPrompt::Message { target, name } => match repo.create_tag(&name, target, Some(&value)) {
+                Ok(()) => {
+                    self.refresh(repo, cfg)?;
+                    self.status_message = format!("Tagged {} as {}", &target.to_string()[..7], name);
+                }
+                Err(e) => self.status_message = format!("Error: {}", e),
+            },
+        }
+        Ok(())
+    }
+
+    /// Cherry-pick or revert the marked commits, or the selected one.
+    fn start_sequence(&mut self, kind: PickKind, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        let oids = if self.marked.is_empty() {
+            self.selected().map(|c| c.oid).into_iter().collect()
+        } else {
+            self.marked.clone()
+        };
+        let opts = PickOptions { edit: self.edit_messages };
+        let result = match kind {
+            PickKind::CherryPick => repo.cherry_pick(&oids, opts),
+            PickKind::Revert => repo.revert(&oids, opts),
+        };
+        if !matches!(result, Err(GitzError::InvalidInput(_))) {
+            self.marked.clear();
+        }
+        self.finish_sequence(result, repo, cfg)
+    }
+
+    /// Report how far a sequence got, opening the editor when it paused for a message.
+    fn finish_sequence(&mut self, result: Result<SequenceOutcome, GitzError>, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        self.refresh(repo, cfg)?;
+        self.status_message = match result {
+            Ok(SequenceOutcome::Finished(created)) => format!("Created {} commits", created),
+            Ok(SequenceOutcome::Stopped { oid, message }) => {
+                self.editor = Some((EditPurpose::Sequence, TextArea::new(&message)));
+                format!("Editing message for {}", &oid.to_string()[..7])
+            }
+            Err(GitzError::MergeConflict) => {
+                "Conflict - resolve and stage in the Files tab, then [c]ontinue or [a]bort".to_string()
+            }
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Keys while a cherry-pick or revert is paused: continue or abort.
+    fn handle_sequence_key(&mut self, key: KeyEvent, repo: &Repository, cfg: &Config) -> Result<bool, GitzError> {
+        let Some(state) = &self.sequence else { return Ok(false) };
+        match key.code {
+            KeyCode::Char('c') => {
+                if state.edit && state.stopped == Some(SequenceStop::Conflict) && state.message.is_some() {
+                    let message = state.message.clone().unwrap_or_default();
+                    self.editor = Some((EditPurpose::Sequence, TextArea::new(&message)));
+                } else {
+                    let result = repo.sequence_continue(None);
+                    self.finish_sequence(result, repo, cfg)?;
+                }
+            }
+            KeyCode::Char('a') => {
+                self.status_message = match repo.sequence_abort() {
+                    Ok(()) => format!("{} aborted", state.kind.label()),
+                    Err(e) => format!("Error: {}", e),
+                };
+                self.refresh(repo, cfg)?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn submit_editor(&mut self, purpose: EditPurpose, text: String, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        let message = commands::commit::cleanup_message(&text);
+        let result = match purpose {
+            EditPurpose::Reword(oid) => commands::commit::reword(repo, oid, &message).map(|_| "Reworded commit".to_string()),
+            EditPurpose::Squash(oid) => repo
+                .commit_fixup(oid, true, Some(&message))
+                .map(|new| format!("Created squash commit {}", &new.to_string()[..7])),
+            EditPurpose::Sequence => {
+                let result = repo.sequence_continue(Some(&message));
+                return self.finish_sequence(result, repo, cfg);
+            }
+        };
+        match result {
+            Ok(summary) => {
+                self.refresh(repo, cfg)?;
+                self.status_message = summary;
+            }
+            Err(e) => self.status_message = format!("Error: {}", e),
+        }
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&CommitInfo> {
+        self.commits.get(self.selected_index)
+    }
+
+    fn open_details(&mut self, repo: &Repository, oid: Oid) -> Result<(), GitzError> {
+        let files = repo.commit_diff(oid, self.context_lines)?;
+        self.details = Some(CommitDetails { oid, files, selected_file: 0, scroll: 0 });
+        Ok(())
+    }
+
+    fn move_selection(&mut self, repo: &Repository, down: bool) -> Result<(), GitzError> {
+        if down && self.selected_index + 1 < self.commits.len() {
+            self.selected_index += 1;
+        } else if !down && self.selected_index > 0 {
+            self.selected_index -= 1;
+        } else {
+            return Ok(());
+        }
+        self.load_more_if_needed(repo)?;
+        // Keep an opened details pane in sync with the cursor.
+        if self.details.is_some() {
+            if let Some(oid) = self.selected().map(|c| c.oid) {
+                self.open_details(repo, oid)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Mark `oid` for bisecting, starting a bisect if there is none. Once a
+    /// commit is checked out for testing the bisect view takes over.
+    fn mark_for_bisect(&mut self, oid: Oid, mark: BisectMark, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        let result = match repo.bisect_state() {
+            Ok(Some(_)) => repo.bisect_mark(oid, mark),
+            Ok(None) => repo.bisect_start().and_then(|()| repo.bisect_mark(oid, mark)),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(BisectStep::NeedMarks) => {
+                let other = if mark == BisectMark::Good { "bad" } else { "good" };
+                self.status_message = format!("Marked {} as {}; now mark a {} commit", &oid.to_string()[..7], mark.label(), other);
+            }
+            Ok(_) => {
+                self.refresh(repo, cfg)?;
+                let _ = self.event_tx.try_send(AppEvent::OpenBisect);
+            }
+            Err(e) => self.status_message = format!("Error: {}", e),
+        }
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &mut Repository,
+        cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if let Some((purpose, mut editor)) = self.editor.take() {
+            match editor.handle_key(key) {
+                InputResult::Pending => self.editor = Some((purpose, editor)),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(text) => self.submit_editor(purpose, text, repo, cfg)?,
+            }
+            return Ok(false);
+        }
+        if let Some(mut input) = self.ref_prompt.take() {
+            match input.handle_key(key) {
+                InputResult::Pending => self.ref_prompt = Some(input),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(spec) => self.browse(spec, repo, cfg)?,
+            }
+            return Ok(false);
+        }
+        if let Some(mut prompt) = self.reset_prompt.take() {
+            match prompt.handle_key(key, repo) {
+                Some(message) => {
+                    self.refresh(repo, cfg)?;
+                    self.status_message = message;
+                }
+                None => self.reset_prompt = Some(prompt),
+            }
+            return Ok(false);
+        }
+        if let Some((prompt, mut input)) = self.tag_prompt.take() {
+            match input.handle_key(key) {
+                InputResult::Pending => self.tag_prompt = Some((prompt, input)),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(value) => self.submit_tag_prompt(prompt, value, repo, cfg)?,
+            }
+            return Ok(false);
+        }
+        if let Some(oid) = self.bisect_target.take() {
+            match key.code {
+                KeyCode::Char('g') => self.mark_for_bisect(oid, BisectMark::Good, repo, cfg)?,
+                KeyCode::Char('b') => self.mark_for_bisect(oid, BisectMark::Bad, repo, cfg)?,
+                _ => self.status_message = "Cancelled".to_string(),
+            }
+            return Ok(false);
+        }
+        if self.handle_sequence_key(key, repo, cfg)? {
+            return Ok(false);
+        }
+
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => {
+                // Refresh manually
+                self.refresh(repo, cfg)?;
+            }
+            KeyCode::Char('q') => {
+                return Ok(true); // Signal to quit
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(repo, true)?,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(repo, false)?,
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.selected_index = 0;
+                self.details = None;
+            }
+            KeyCode::End | KeyCode::Char('G') if !self.commits.is_empty() => {
+                self.selected_index = self.commits.len() - 1;
+                self.details = None;
+                self.load_more_if_needed(repo)?;
+            }
+            KeyCode::Enter => {
+                if let Some(oid) = self.selected().map(|c| c.oid) {
+                    self.open_details(repo, oid)?;
+                }
+            }
+            KeyCode::Esc => {
+                self.details = None;
+            }
+            KeyCode::Char('w') => {
+                if let Some(commit) = self.selected() {
+                    self.editor = Some((EditPurpose::Reword(commit.oid), TextArea::new(&commit.message)));
+                }
+            }
+            KeyCode::Char('F') => {
+                if let Some(oid) = self.selected().map(|c| c.oid) {
+                    match repo.commit_fixup(oid, false, None) {
+                        Ok(new) => {
+                            self.refresh(repo, cfg)?;
+                            self.status_message = format!("Created fixup commit {}", &new.to_string()[..7]);
+                        }
+                        Err(e) => self.status_message = format!("Error: {}", e),
+                    }
+                }
+            }
+            KeyCode::Char('S') => {
+                if let Some(oid) = self.selected().map(|c| c.oid) {
+                    self.editor = Some((EditPurpose::Squash(oid), TextArea::new("")));
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Some(oid) = self.selected().map(|c| c.oid) {
+                    match self.marked.iter().position(|m| *m == oid) {
+                        Some(pos) => {
+                            self.marked.remove(pos);
+                        }
+                        None => self.marked.push(oid),
+                    }
+                    self.move_selection(repo, true)?;
+                }
+            }
+            KeyCode::Char('c') => self.start_sequence(PickKind::CherryPick, repo, cfg)?,
+            KeyCode::Char('v') => self.start_sequence(PickKind::Revert, repo, cfg)?,
+            KeyCode::Char('e') => {
+                self.edit_messages = !self.edit_messages;
+                self.status_message = format!("Edit messages: {}", if self.edit_messages { "on" } else { "off" });
+            }
+            KeyCode::Char('b') => {
+                let current = self.log_ref.clone().unwrap_or_default();
+                self.ref_prompt = Some(TextInput::new("Show log of (branch, tag or revision; empty for HEAD)", current));
+            }
+            KeyCode::Char('t') => {
+                if let Some(commit) = self.selected() {
+                    let input = TextInput::new(format!("Tag {} as", commit.short_id()), "");
+                    self.tag_prompt = Some((TagPrompt::Name(commit.oid), input));
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some(commit) = self.selected() {
+                    let label = format!("{} {}", commit.short_id(), commit.summary());
+                    self.reset_prompt = Some(ResetPrompt::new(commit.oid, label));
+                }
+            }
+            KeyCode::Char('m') => {
+                if let Some((oid, short)) = self.selected().map(|c| (c.oid, c.short_id())) {
+                    self.status_message = format!("Bisect: mark {} as [g]ood or [b]ad?", short);
+                    self.bisect_target = Some(oid);
+                }
+            }
+            KeyCode::Char('i') => {
+                if let Some(oid) = self.selected().map(|c| c.oid) {
+                    // The app owns view switching; hand the commit over to the rebase view.
+                    let _ = self.event_tx.try_send(AppEvent::OpenRebase(oid));
+                }
+            }
+            KeyCode::Char('B') => {
+                if let Some(details) = &self.details {
+                    if let Some(file) = details.files.get(details.selected_file) {
+                        let path = file.path().to_string();
+                        let _ = self.event_tx.try_send(AppEvent::OpenBlame { path, at: Some(details.oid) });
+                    }
+                }
+            }
+            KeyCode::Char('H') => {
+                if let Some(file) = self.details.as_ref().and_then(|d| d.files.get(d.selected_file)) {
+                    let _ = self.event_tx.try_send(AppEvent::OpenFileHistory(file.path().to_string()));
+                }
+            }
+            KeyCode::Char(']') | KeyCode::Char('n') => {
+                if let Some(details) = &mut self.details {
+                    if details.selected_file + 1 < details.files.len() {
+                        details.selected_file += 1;
+                        details.scroll = 0;
+                    }
+                }
+            }
+            KeyCode::Char('[') | KeyCode::Char('p') => {
+                if let Some(details) = &mut self.details {
+                    if details.selected_file > 0 {
+                        details.selected_file -= 1;
+                        details.scroll = 0;
+                    }
+                }
+            }
+            KeyCode::PageDown | KeyCode::Char('J') => {
+                if let Some(details) = &mut self.details {
+                    details.scroll = details.scroll.saturating_add(DIFF_PAGE);
+                }
+            }
+            KeyCode::PageUp | KeyCode::Char('K') => {
+                if let Some(details) = &mut self.details {
+                    details.scroll = details.scroll.saturating_sub(DIFF_PAGE);
+                }
+            }
+            _ => {}
+        }
+        Ok(false) // Continue running
+    }
+
+    fn commit_line(&self, commit: &CommitInfo) -> Line<'static> {
+        let mark = if self.marked.contains(&commit.oid) { "✓ " } else { "  " };
+        let mut spans = vec![
+            Span::styled(mark, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{} ", commit.short_id()), Style::default().fg(Color::Yellow)),
+        ];
+        if let Some(labels) = self.decorations.get(&commit.oid) {
+            spans.extend(decoration_spans(labels));
+        }
+        spans.push(Span::raw(commit.summary().to_string()));
+        spans.push(Span::styled(
+            format!("  {} · {}", commit.author, relative_time(commit.time)),
+            Style::default().fg(Color::DarkGray),
+        ));
+        Line::from(spans)
+    }
+
+    fn details_lines(&self, commit: &CommitInfo, details: &CommitDetails) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("commit ", Style::default().fg(Color::Yellow)),
+                Span::styled(commit.oid.to_string(), Style::default().fg(Color::Yellow)),
+            ]),
+            Line::from(format!("Author: {} <{}>", commit.author, commit.email)),
+            Line::from(format!("Date:   {}", relative_time(commit.time))),
+        ];
+        if let Some(labels) = self.decorations.get(&commit.oid) {
+            let mut spans = vec![Span::raw("Refs:   ")];
+            spans.extend(decoration_spans(labels));
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+        lines.extend(commit.message.trim_end().lines().map(|l| Line::from(format!("    {}", l))));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("{} files changed", details.files.len()),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        for (idx, file) in details.files.iter().enumerate() {
+            let marker = if idx == details.selected_file { "» " } else { "  " };
+            let style = if idx == details.selected_file {
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}{} {}", marker, file.change.label(), file.path()), style),
+                Span::styled(format!(" +{}", file.additions()), Style::default().fg(Color::Green)),
+                Span::styled(format!(" -{}", file.deletions()), Style::default().fg(Color::Red)),
+            ]));
+        }
+        lines
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let loaded = if !self.cursor.is_done() {
+            format!("{}+", self.commits.len())
+        } else {
+            self.commits.len().to_string()
+        };
+        let mut top_text = format!(
+            "gitz - Repository: {}   Branch: {}   Commits: {}",
+            repo.root().display(),
+            branch_name,
+            loaded
+        );
+        if let Some(log_ref) = &self.log_ref {
+            top_text.push_str(&format!("   Log of: {}", log_ref));
+        }
+        if !self.marked.is_empty() {
+            top_text.push_str(&format!("   Marked: {}", self.marked.len()));
+        }
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Commits"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(if self.details.is_some() {
+                [Constraint::Percentage(45), Constraint::Percentage(55)]
+            } else {
+                [Constraint::Percentage(100), Constraint::Percentage(0)]
+            })
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.commits.is_empty() {
+            vec![ListItem::new(Span::styled("No commits yet", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.commits.iter().map(|c| ListItem::new(self.commit_line(c))).collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Log"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut state = ListState::default();
+        if !self.commits.is_empty() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        if let (Some(details), Some(commit)) = (&self.details, self.selected()) {
+            let info = self.details_lines(commit, details);
+            let detail_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length((info.len() as u16 + 2).min(chunks[1].height / 2)),
+                    Constraint::Min(0),
+                ])
+                .split(main_chunks[1]);
+            let info = Paragraph::new(info)
+                .block(Block::default().borders(Borders::ALL).title("Commit"));
+            f.render_widget(info, detail_chunks[0]);
+
+            let files = details.files.get(details.selected_file).map(std::slice::from_ref).unwrap_or_default();
+            let title = files.first().map(|file| file.path().to_string()).unwrap_or_else(|| "Diff".to_string());
+            let lines = diff_view::diff_lines(files, self.show_line_numbers);
+            diff_view::draw_diff(f, detail_chunks[1], &title, lines, details.scroll);
+        }
+
+        let help_text = if let Some(state) = &self.sequence {
+            format!(
+                "{} | {} paused ({} left): [c]ontinue [a]bort [j/k]move [q]uit",
+                self.status_message,
+                state.kind.label(),
+                state.todo.len()
+            )
+        } else if self.details.is_some() {
+            format!("{} | [j/k]commit [n/p]file [B]lame file [H]istory [J/K]scroll [Esc]close [r]efresh [q]uit", self.status_message)
+        } else {
+            format!(
+                "{} | [j/k]move [Enter]open [Space]mark [c]herry-pick re[v]ert [e]dit msgs: {} [b]rowse ref [t]ag [x]reset [m]bisect [w]reword [F]ixup [S]quash [i]rebase [r]efresh [q]uit",
+                self.status_message,
+                if self.edit_messages { "on" } else { "off" }
+            )
+        };
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        if let Some((purpose, editor)) = &self.editor {
+            let title = match purpose {
+                EditPurpose::Reword(oid) => format!("Reword {} (Ctrl+S save, Esc cancel)", &oid.to_string()[..7]),
+                EditPurpose::Squash(_) => "squash! message body (Ctrl+S commit, Esc cancel)".to_string(),
+                EditPurpose::Sequence => "Commit message (Ctrl+S commit, Esc cancel)".to_string(),
+            };
+            let popup = centered_rect(70, size.height.saturating_sub(10), size);
+            f.render_widget(Clear, popup);
+            editor.draw(f, popup, &title);
+        }
+        if let Some(input) = &self.ref_prompt {
+            input.draw(f, size);
+        }
+        if let Some((_, input)) = &self.tag_prompt {
+            input.draw(f, size);
+        }
+        if let Some(prompt) = &self.reset_prompt {
+            prompt.draw(f, size);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+#![allow(dead_code)]
+
+use crate::commands::doctor::{sections, verdict};
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::AppEvent;
+use crate::git::{DoctorReport, Repository, DEFAULT_LARGE_BLOB_BYTES};
+use crate::ui::components::status_bar;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use std::path::PathBuf;
+use tokio::sync::mpsc::Sender;
+
+/// The doctor view – checks the repository's health in the background and
+/// shows the report.
+pub struct DoctorView {
+    report: Option<DoctorReport>,
+    /// Git directory of the repository the report (or running check) is for.
+    checked: Option<PathBuf>,
+    running: bool,
+    scroll: u16,
+    status_message: String,
+    event_tx: Sender<AppEvent>,
+}
+
+impl DoctorView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            report: None,
+            checked: None,
+            running: false,
+            scroll: 0,
+            status_message: "Ready".to_string(),
+            event_tx,
+        }
+    }
+
+    /// Check the repository unless it was already checked; the scan walks
+    /// the whole history, so it only reruns on request.
+    pub fn refresh(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        if self.checked.as_deref() != Some(repo.git_dir()) {
+            self.report = None;
+            self.start_check(repo);
+        }
+        Ok(())
+    }
+
+    /// Run the checks on a blocking thread.
+    fn start_check(&mut self, repo: &Repository) {
+        if self.running {
+            self.status_message = "Busy: checking the repository".to_string();
+            return;
+        }
+        self.running = true;
+        self.checked = Some(repo.git_dir().to_path_buf());
+        self.status_message = "Checking the repository...".to_string();
+
+        let repo = repo.clone();
+        let tx = self.event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = repo.doctor(DEFAULT_LARGE_BLOB_BYTES).map_err(|e| e.to_string());
+            let _ = tx.blocking_send(AppEvent::DoctorDone(result));
+        });
+    }
+
+    /// Called when the background check has finished.
+    pub fn task_finished(&mut self, result: Result<DoctorReport, String>) {
+        self.running = false;
+        self.scroll = 0;
+        match result {
+            Ok(report) => {
+                self.status_message = verdict(&report);
+                self.report = Some(report);
+            }
+            Err(e) => self.status_message = format!("Error: {}", e),
+        }
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => self.start_check(repo),
+            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char('J') => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp | KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(10),
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn report_lines(report: &DoctorReport) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for section in sections(report, DEFAULT_LARGE_BLOB_BYTES) {
+            let color = if section.ok { Color::Green } else { Color::Red };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}: ", section.title), Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(section.summary, Style::default().fg(color)),
+            ]));
+            lines.extend(section.lines.into_iter().map(|l| Line::from(format!("  {}", l))));
+            lines.push(Line::from(""));
+        }
+        lines
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let mut top_text = format!("gitz - Repository: {}", repo.root().display());
+        if let Some(report) = &self.report {
+            top_text.push_str(&format!("   {}", verdict(report)));
+        }
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Doctor"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let lines = match &self.report {
+            Some(report) => Self::report_lines(report),
+            None if self.running => vec![Line::from("Checking objects, refs and config...")],
+            None => vec![Line::from("No report yet; press [r] to check the repository.")],
+        };
+        let report = Paragraph::new(lines)
+            .scroll((self.scroll, 0))
+            .block(Block::default().borders(Borders::ALL).title("Health report"));
+        f.render_widget(report, chunks[1]);
+
+        let help_text = format!("{} | [j/k]scroll [r]erun checks [q]uit", self.status_message);
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        Ok(())
+    }
+}
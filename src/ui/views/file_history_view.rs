@@ -0,0 +1,205 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::AppEvent;
+use crate::git::{ChangeKind, Decoration, FileDiff, FileRevision, Repository};
+use crate::ui::components::decorations::decoration_spans;
+use crate::ui::components::{diff_view, status_bar};
+use crate::utils::relative_time;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use git2::Oid;
+use std::collections::HashMap;
+use tokio::sync::mpsc::Sender;
+
+/// Lines scrolled by PageUp/PageDown in the diff pane.
+const DIFF_PAGE: u16 = 10;
+
+/// The file history view – every commit that touched one file, across renames.
+pub struct FileHistoryView {
+    path: String,
+    revisions: Vec<FileRevision>,
+    decorations: HashMap<Oid, Vec<Decoration>>,
+    selected_index: usize,
+    /// Changes of the selected revision to the file.
+    diff: Vec<FileDiff>,
+    scroll: u16,
+    status_message: String,
+    context_lines: u32,
+    show_line_numbers: bool,
+    event_tx: Sender<AppEvent>,
+}
+
+impl FileHistoryView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            path: String::new(),
+            revisions: Vec::new(),
+            decorations: HashMap::new(),
+            selected_index: 0,
+            diff: Vec::new(),
+            scroll: 0,
+            status_message: "Ready".to_string(),
+            context_lines: 3,
+            show_line_numbers: true,
+            event_tx,
+        }
+    }
+
+    /// Load the history of `path` as of HEAD.
+    pub fn open(&mut self, repo: &Repository, cfg: &Config, path: &str) -> Result<(), GitzError> {
+        self.context_lines = cfg.ui.diff_context_lines as u32;
+        self.show_line_numbers = cfg.ui.show_line_numbers;
+        self.path = path.to_string();
+        self.selected_index = 0;
+        self.decorations = repo.decorations()?;
+        match repo.file_history(path, cfg.performance.max_commits_to_load.max(1)) {
+            Ok(revisions) => {
+                self.status_message = format!("{} commits touched {}", revisions.len(), path);
+                self.revisions = revisions;
+            }
+            Err(e) => {
+                self.revisions.clear();
+                self.status_message = format!("Error: {}", e);
+            }
+        }
+        self.load_diff(repo)
+    }
+
+    fn load_diff(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.scroll = 0;
+        self.diff = match self.revisions.get(self.selected_index) {
+            Some(revision) => repo.file_revision_diff(revision, self.context_lines)?,
+            None => Vec::new(),
+        };
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Down | KeyCode::Char('j') if self.selected_index + 1 < self.revisions.len() => {
+                self.selected_index += 1;
+                self.load_diff(repo)?;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_index > 0 => {
+                self.selected_index -= 1;
+                self.load_diff(repo)?;
+            }
+            KeyCode::PageDown | KeyCode::Char('J') => self.scroll = self.scroll.saturating_add(DIFF_PAGE),
+            KeyCode::PageUp | KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(DIFF_PAGE),
+            KeyCode::Char('b') => {
+                if let Some(revision) = self.revisions.get(self.selected_index) {
+                    if revision.change == ChangeKind::Deleted {
+                        self.status_message = "The file was deleted in this commit".to_string();
+                    } else {
+                        let path = revision.path.clone();
+                        let _ = self.event_tx.try_send(AppEvent::OpenBlame { path, at: Some(revision.commit.oid) });
+                    }
+                }
+            }
+            KeyCode::Esc => {
+                let _ = self.event_tx.try_send(AppEvent::Back);
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn revision_line(&self, revision: &FileRevision) -> Line<'static> {
+        let commit = &revision.commit;
+        let mut spans = vec![
+            Span::styled(format!("{} ", commit.short_id()), Style::default().fg(Color::Yellow)),
+        ];
+        if let Some(labels) = self.decorations.get(&commit.oid) {
+            spans.extend(decoration_spans(labels));
+        }
+        spans.push(Span::raw(commit.summary().to_string()));
+        spans.push(Span::styled(
+            format!("  {} · {}", commit.author, relative_time(commit.time)),
+            Style::default().fg(Color::DarkGray),
+        ));
+        match (&revision.renamed_from, revision.change) {
+            (Some(from), _) => spans.push(Span::styled(
+                format!("  ({} → {})", from, revision.path),
+                Style::default().fg(Color::Magenta),
+            )),
+            (None, ChangeKind::Added | ChangeKind::Deleted) => spans.push(Span::styled(
+                format!("  [{}]", revision.change.label()),
+                Style::default().fg(Color::Cyan),
+            )),
+            _ => {}
+        }
+        Line::from(spans)
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        _repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let top_bar = Paragraph::new(format!("gitz - History of {}   Commits: {}", self.path, self.revisions.len()))
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - File history"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.revisions.is_empty() {
+            vec![ListItem::new(Span::styled("No commits touch this file", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.revisions.iter().map(|r| ListItem::new(self.revision_line(r))).collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Commits"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut state = ListState::default();
+        if !self.revisions.is_empty() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        let title = self
+            .revisions
+            .get(self.selected_index)
+            .map_or_else(|| "Diff".to_string(), |r| format!("{} @ {}", r.path, r.commit.short_id()));
+        let lines = diff_view::diff_lines(&self.diff, self.show_line_numbers);
+        diff_view::draw_diff(f, main_chunks[1], &title, lines, self.scroll);
+
+        let help_text = format!(
+            "{} | [j/k]move [J/K]scroll diff [b]lame at commit [Esc]back [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        Ok(())
+    }
+}
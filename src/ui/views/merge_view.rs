@@ -0,0 +1,291 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::git::{ConflictHunk, ConflictInfo, ConflictSide, ConflictVersions, MergeState, Repository, StatusSection};
+use crate::ui::components::status_bar;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+
+/// Lines scrolled by PageUp/PageDown in the 3-way panes.
+const PANE_PAGE: u16 = 10;
+
+/// The conflicts view – resolve a merge file by file or hunk by hunk.
+pub struct MergeView {
+    state: Option<MergeState>,
+    files: Vec<(String, ConflictInfo)>,
+    selected_file: usize,
+    /// Stage versions of the selected file.
+    versions: ConflictVersions,
+    /// Conflict hunks left in the work tree copy of the selected file.
+    hunks: Vec<ConflictHunk>,
+    selected_hunk: usize,
+    scroll: u16,
+    /// Abort asked for, waiting for `y`.
+    confirm_abort: bool,
+    status_message: String,
+}
+
+impl MergeView {
+    pub fn new() -> Self {
+        Self {
+            state: None,
+            files: Vec::new(),
+            selected_file: 0,
+            versions: ConflictVersions::default(),
+            hunks: Vec::new(),
+            selected_hunk: 0,
+            scroll: 0,
+            confirm_abort: false,
+            status_message: "Ready".to_string(),
+        }
+    }
+
+    /// Reload the merge state and the conflicted files.
+    pub fn refresh(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.state = repo.merge_state()?;
+        let status = repo.status()?;
+        self.files = status
+            .section(StatusSection::Conflicts)
+            .into_iter()
+            .map(|f| (f.path.clone(), f.conflict.unwrap_or_default()))
+            .collect();
+        if self.selected_file >= self.files.len() {
+            self.selected_file = self.files.len().saturating_sub(1);
+        }
+        self.load_file(repo)
+    }
+
+    fn load_file(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        match self.files.get(self.selected_file) {
+            Some((path, _)) => {
+                self.versions = repo.conflict_versions(path)?;
+                self.hunks = repo.conflict_hunks(path)?.hunks().into_iter().cloned().collect();
+            }
+            None => {
+                self.versions = ConflictVersions::default();
+                self.hunks.clear();
+            }
+        }
+        if self.selected_hunk >= self.hunks.len() {
+            self.selected_hunk = self.hunks.len().saturating_sub(1);
+        }
+        self.scroll = 0;
+        Ok(())
+    }
+
+    fn selected_path(&self) -> Option<String> {
+        self.files.get(self.selected_file).map(|(p, _)| p.clone())
+    }
+
+    /// Refresh and report the outcome of an operation.
+    fn finish(&mut self, repo: &Repository, result: Result<(), GitzError>, success: String) -> Result<(), GitzError> {
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(()) => success,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if self.confirm_abort {
+            self.confirm_abort = false;
+            if key.code == KeyCode::Char('y') {
+                let result = repo.merge_abort();
+                self.finish(repo, result, "Merge aborted".to_string())?;
+            } else {
+                self.status_message = "Abort cancelled".to_string();
+            }
+            return Ok(false);
+        }
+
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => self.refresh(repo)?,
+            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Down | KeyCode::Char('j') if self.selected_file + 1 < self.files.len() => {
+                self.selected_file += 1;
+                self.selected_hunk = 0;
+                self.load_file(repo)?;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_file > 0 => {
+                self.selected_file -= 1;
+                self.selected_hunk = 0;
+                self.load_file(repo)?;
+            }
+            KeyCode::Char('n') if self.selected_hunk + 1 < self.hunks.len() => {
+                self.selected_hunk += 1;
+                self.scroll = 0;
+            }
+            KeyCode::Char('p') if self.selected_hunk > 0 => {
+                self.selected_hunk -= 1;
+                self.scroll = 0;
+            }
+            KeyCode::Char(c @ ('o' | 't')) => {
+                if let Some(path) = self.selected_path() {
+                    let side = if c == 'o' { ConflictSide::Ours } else { ConflictSide::Theirs };
+                    let result = repo.resolve_conflict_hunk(&path, self.selected_hunk, side);
+                    let message = format!("Took {} for hunk {} of {}", if c == 'o' { "ours" } else { "theirs" }, self.selected_hunk + 1, path);
+                    self.finish(repo, result, message)?;
+                }
+            }
+            KeyCode::Char(c @ ('O' | 'T')) => {
+                if let Some(path) = self.selected_path() {
+                    let side = if c == 'O' { ConflictSide::Ours } else { ConflictSide::Theirs };
+                    let result = repo.resolve_conflict_file(&path, side);
+                    let message = format!("Resolved {} with {}", path, if c == 'O' { "ours" } else { "theirs" });
+                    self.finish(repo, result, message)?;
+                }
+            }
+            KeyCode::Char('m') => {
+                if let Some(path) = self.selected_path() {
+                    let result = repo.mark_resolved(&path);
+                    self.finish(repo, result, format!("Marked {} as resolved", path))?;
+                }
+            }
+            KeyCode::Char('C') => {
+                let result = repo.merge_conclude();
+                self.refresh(repo)?;
+                self.status_message = match result {
+                    Ok(oid) => format!("Created merge commit {}", &oid.to_string()[..7]),
+                    Err(GitzError::MergeConflict) => "Resolve every file before concluding".to_string(),
+                    Err(e) => format!("Error: {}", e),
+                };
+            }
+            KeyCode::Char('A') if self.state.is_some() => {
+                self.confirm_abort = true;
+                self.status_message = "Abort the merge and discard its changes? [y/N]".to_string();
+            }
+            KeyCode::PageDown | KeyCode::Char('J') => self.scroll = self.scroll.saturating_add(PANE_PAGE),
+            KeyCode::PageUp | KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(PANE_PAGE),
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn draw_pane(&self, f: &mut ratatui::Frame, area: Rect, title: &str, text: Option<&str>, color: Color) {
+        let lines: Vec<Line> = match text {
+            Some(text) => text.lines().map(|l| Line::from(l.to_string())).collect(),
+            None => vec![Line::from(Span::styled("(absent)", Style::default().fg(Color::DarkGray)))],
+        };
+        let pane = Paragraph::new(lines)
+            .scroll((self.scroll, 0))
+            .block(Block::default().borders(Borders::ALL).title(title.to_string()).border_style(Style::default().fg(color)));
+        f.render_widget(pane, area);
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let top_text = match &self.state {
+            Some(state) => format!(
+                "gitz - {}   Branch: {}   Conflicted files: {}",
+                state.message.lines().next().unwrap_or("Merge in progress"),
+                branch_name,
+                self.files.len()
+            ),
+            None => format!("gitz - Branch: {}   No merge in progress", branch_name),
+        };
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Merge"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(25), Constraint::Percentage(75)])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.files.is_empty() {
+            let text = if self.state.is_some() { "All conflicts resolved - [C] to conclude" } else { "No conflicts" };
+            vec![ListItem::new(Span::styled(text, Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.files
+                .iter()
+                .map(|(path, info)| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(path.clone(), Style::default().fg(Color::Red)),
+                        Span::styled(format!("  {}", info.description()), Style::default().fg(Color::DarkGray)),
+                    ]))
+                })
+                .collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Conflicts"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut list_state = ListState::default();
+        if !self.files.is_empty() {
+            list_state.select(Some(self.selected_file));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut list_state);
+
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(main_chunks[1]);
+        let hunk_info = match self.hunks.len() {
+            0 if self.files.is_empty() => String::new(),
+            0 => "No conflict markers left - [m] to mark resolved, or take a whole side".to_string(),
+            n => format!("Hunk {}/{} - showing the hunk; [O/T] take a whole side", self.selected_hunk + 1, n),
+        };
+        f.render_widget(
+            Paragraph::new(hunk_info).block(Block::default().borders(Borders::ALL).title("Hunks")),
+            right[0],
+        );
+
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
+            .split(right[1]);
+        // A hunk shows its own sides; otherwise show the whole stage versions.
+        let (base, ours, theirs) = match self.hunks.get(self.selected_hunk) {
+            Some(hunk) => (hunk.base.as_deref(), Some(hunk.ours.as_str()), Some(hunk.theirs.as_str())),
+            None => (self.versions.base.as_deref(), self.versions.ours.as_deref(), self.versions.theirs.as_deref()),
+        };
+        self.draw_pane(f, panes[0], "Base", base, Color::DarkGray);
+        self.draw_pane(f, panes[1], "Ours", ours, Color::Green);
+        self.draw_pane(f, panes[2], "Theirs", theirs, Color::Magenta);
+
+        let help_text = format!(
+            "{} | [j/k]file [n/p]hunk [o/t]take ours/theirs [O/T]whole file [m]ark resolved [C]onclude [A]bort [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        Ok(())
+    }
+}
+
+impl Default for MergeView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
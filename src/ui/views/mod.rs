@@ -1,3 +1,16 @@
 pub mod repo_view;
 pub mod worktrees_view;
 pub mod workflow_view;
+pub mod branches_view;
+pub mod tags_view;
+pub mod commits_view;
+pub mod reflog_view;
+pub mod stashes_view;
+pub mod remotes_view;
+pub mod submodules_view;
+pub mod rebase_view;
+pub mod merge_view;
+pub mod bisect_view;
+pub mod blame_view;
+pub mod file_history_view;
+pub mod doctor_view;
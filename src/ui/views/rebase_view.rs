@@ -0,0 +1,318 @@
+#![allow(dead_code)]
+
+use crate::commands;
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::git::{RebaseAction, RebaseOutcome, RebasePlan, RebaseState, RebaseStep, RebaseStop, Repository};
+use crate::ui::components::input::{centered_rect, InputResult};
+use crate::ui::components::status_bar;
+use crate::ui::components::text_area::TextArea;
+use crossterm::event::{KeyCode, KeyEvent};
+use git2::Oid;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+/// The interactive rebase view: edit a todo list, then drive the rebase
+/// through conflicts and `edit` stops.
+pub struct RebaseView {
+    /// The todo list being edited, before the rebase starts.
+    plan: Option<RebasePlan>,
+    /// The rebase in progress, if any.
+    state: Option<RebaseState>,
+    selected_index: usize,
+    /// Message editor for a `reword` step, with the step index.
+    editor: Option<(usize, TextArea)>,
+    status_message: String,
+}
+
+impl RebaseView {
+    pub fn new() -> Self {
+        Self {
+            plan: None,
+            state: None,
+            selected_index: 0,
+            editor: None,
+            status_message: "Ready".to_string(),
+        }
+    }
+
+    /// Reload the state of a rebase in progress.
+    pub fn refresh(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.state = repo.rebase_state()?;
+        if self.state.is_some() {
+            self.plan = None;
+        }
+        Ok(())
+    }
+
+    /// Open a todo list for rebasing `from` and everything above it.
+    pub fn plan(&mut self, repo: &Repository, from: Oid) -> Result<(), GitzError> {
+        self.refresh(repo)?;
+        if self.state.is_some() {
+            self.status_message = "A rebase is already in progress".to_string();
+            return Ok(());
+        }
+        match repo.rebase_plan(from) {
+            Ok(plan) => {
+                self.status_message = format!("Rebasing {} commits", plan.steps.len());
+                self.plan = Some(plan);
+                self.selected_index = 0;
+            }
+            Err(e) => self.status_message = format!("Error: {}", e),
+        }
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (editor open).
+    pub fn captures_input(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    /// Report the result of a rebase step and reload the state.
+    fn finish(&mut self, repo: &Repository, result: Result<RebaseOutcome, GitzError>) -> Result<(), GitzError> {
+        self.status_message = match result {
+            Ok(RebaseOutcome::Finished { tip, emptied }) if emptied.is_empty() => {
+                format!("Rebase finished at {}", &tip.to_string()[..7])
+            }
+            Ok(RebaseOutcome::Finished { tip, emptied }) => {
+                let summaries: Vec<&str> = emptied.iter().map(|s| s.summary.as_str()).collect();
+                format!(
+                    "Rebase finished at {}; dropped {} that became empty: {}",
+                    &tip.to_string()[..7],
+                    emptied.len(),
+                    summaries.join(", ")
+                )
+            }
+            Ok(RebaseOutcome::Stopped(step)) => {
+                format!("Stopped at {} {} - amend it, then [c]ontinue", &step.oid.to_string()[..7], step.summary)
+            }
+            Err(GitzError::MergeConflict) => {
+                "Conflict - resolve and stage the files, then [c]ontinue, [s]kip or [a]bort".to_string()
+            }
+            Err(e) => format!("Error: {}", e),
+        };
+        self.selected_index = 0;
+        self.refresh(repo)
+    }
+
+    fn handle_plan_key(&mut self, key: KeyEvent, repo: &Repository) -> Result<(), GitzError> {
+        let Some(plan) = &mut self.plan else { return Ok(()) };
+        let action = match key.code {
+            KeyCode::Char('p') => Some(RebaseAction::Pick),
+            KeyCode::Char('e') => Some(RebaseAction::Edit),
+            KeyCode::Char('s') => Some(RebaseAction::Squash),
+            KeyCode::Char('f') => Some(RebaseAction::Fixup),
+            KeyCode::Char('d') => Some(RebaseAction::Drop),
+            _ => None,
+        };
+        if let Some(action) = action {
+            plan.set_action(self.selected_index, action);
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') if self.selected_index + 1 < plan.steps.len() => {
+                self.selected_index += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_index > 0 => {
+                self.selected_index -= 1;
+            }
+            KeyCode::Char('J') if plan.move_down(self.selected_index) => self.selected_index += 1,
+            KeyCode::Char('K') if plan.move_up(self.selected_index) => self.selected_index -= 1,
+            KeyCode::Char('r') => {
+                if let Some(step) = plan.steps.get(self.selected_index) {
+                    let message = match &step.message {
+                        Some(message) => message.clone(),
+                        None => repo.find_commit(step.oid)?.message,
+                    };
+                    self.editor = Some((self.selected_index, TextArea::new(&message)));
+                }
+            }
+            KeyCode::Enter => {
+                let plan = self.plan.take().expect("plan");
+                match repo.rebase_start(&plan) {
+                    Err(GitzError::InvalidInput(msg)) => {
+                        // Nothing was touched; keep editing.
+                        self.status_message = format!("Error: {}", msg);
+                        self.plan = Some(plan);
+                    }
+                    result => self.finish(repo, result)?,
+                }
+            }
+            KeyCode::Esc => {
+                self.plan = None;
+                self.status_message = "Rebase cancelled".to_string();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_running_key(&mut self, key: KeyEvent, repo: &Repository) -> Result<(), GitzError> {
+        match key.code {
+            KeyCode::Char('c') => {
+                let result = repo.rebase_continue();
+                self.finish(repo, result)?;
+            }
+            KeyCode::Char('s') => {
+                let result = repo.rebase_skip();
+                self.finish(repo, result)?;
+            }
+            KeyCode::Char('a') => {
+                self.status_message = match repo.rebase_abort() {
+                    Ok(()) => "Rebase aborted".to_string(),
+                    Err(e) => format!("Error: {}", e),
+                };
+                self.refresh(repo)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if let Some((index, mut editor)) = self.editor.take() {
+            match editor.handle_key(key) {
+                InputResult::Pending => self.editor = Some((index, editor)),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(text) => {
+                    let message = commands::commit::cleanup_message(&text);
+                    if message.trim().is_empty() {
+                        self.status_message = "Error: Commit message cannot be empty".to_string();
+                    } else if let Some(plan) = &mut self.plan {
+                        plan.set_action(index, RebaseAction::Reword);
+                        plan.steps[index].message = Some(message);
+                    }
+                }
+            }
+            return Ok(false);
+        }
+
+        if self.state.is_some() {
+            self.handle_running_key(key, repo)?;
+        } else if self.plan.is_some() {
+            self.handle_plan_key(key, repo)?;
+        }
+        Ok(false)
+    }
+
+    fn step_line(step: &RebaseStep, style: Style) -> Line<'static> {
+        let action_color = match step.action {
+            RebaseAction::Pick => Color::Green,
+            RebaseAction::Reword | RebaseAction::Edit => Color::Yellow,
+            RebaseAction::Squash | RebaseAction::Fixup => Color::Magenta,
+            RebaseAction::Drop => Color::Red,
+        };
+        Line::from(vec![
+            Span::styled(format!("{:<7}", step.action.label()), style.fg(action_color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{} ", &step.oid.to_string()[..7]), style.fg(Color::Yellow)),
+            Span::styled(step.summary.clone(), style),
+        ])
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let top_text = match (&self.plan, &self.state) {
+            (_, Some(state)) => format!(
+                "gitz - Rebasing {} onto {}   Step {}/{}",
+                state.head_name.as_deref().map_or("detached HEAD", |n| n.trim_start_matches("refs/heads/")),
+                &state.onto.to_string()[..7],
+                state.done.len(),
+                state.done.len() + state.todo.len()
+            ),
+            (Some(plan), None) => format!("gitz - Branch: {}   Onto: {}", branch_name, &plan.onto.to_string()[..7]),
+            (None, None) => format!("gitz - Branch: {}", branch_name),
+        };
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Interactive rebase"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let help_text = if let Some(state) = &self.state {
+            let list: Vec<ListItem> = state
+                .done
+                .iter()
+                .map(|s| ListItem::new(Self::step_line(s, Style::default().fg(Color::DarkGray))))
+                .chain(state.todo.iter().map(|s| ListItem::new(Self::step_line(s, Style::default()))))
+                .collect();
+            let title = match state.stopped {
+                Some(RebaseStop::Conflict) => "Todo - stopped on a conflict",
+                Some(RebaseStop::Edit) => "Todo - stopped for editing",
+                None => "Todo",
+            };
+            let list = List::new(list)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+                .highlight_symbol("» ");
+            let mut list_state = ListState::default();
+            list_state.select(state.done.len().checked_sub(1));
+            f.render_stateful_widget(list, chunks[1], &mut list_state);
+            format!("{} | [c]ontinue [s]kip [a]bort [q]uit", self.status_message)
+        } else if let Some(plan) = &self.plan {
+            let items: Vec<ListItem> = plan
+                .steps
+                .iter()
+                .map(|s| ListItem::new(Self::step_line(s, Style::default())))
+                .collect();
+            let list = List::new(items)
+                .block(Block::default().borders(Borders::ALL).title("Todo (oldest first)"))
+                .highlight_style(Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD))
+                .highlight_symbol("» ");
+            let mut list_state = ListState::default();
+            list_state.select(Some(self.selected_index));
+            f.render_stateful_widget(list, chunks[1], &mut list_state);
+            format!(
+                "{} | [p]ick [r]eword [e]dit [s]quash [f]ixup [d]rop [J/K]move [Enter]start [Esc]cancel",
+                self.status_message
+            )
+        } else {
+            let hint = Paragraph::new("No rebase in progress. Press [i] on a commit in the Commits tab to plan one.")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(Block::default().borders(Borders::ALL).title("Todo"));
+            f.render_widget(hint, chunks[1]);
+            format!("{} | [Tab]switch view [q]uit", self.status_message)
+        };
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        if let Some((_, editor)) = &self.editor {
+            let popup = centered_rect(70, size.height.saturating_sub(10), size);
+            f.render_widget(Clear, popup);
+            editor.draw(f, popup, "Reword message (Ctrl+S save, Esc cancel)");
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for RebaseView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
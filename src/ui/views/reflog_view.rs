@@ -0,0 +1,320 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::git::{CommitInfo, ReflogEntry, Repository};
+use crate::ui::components::input::{InputResult, TextInput};
+use crate::ui::components::reset_prompt::ResetPrompt;
+use crate::ui::components::status_bar;
+use crate::utils::relative_time;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+
+/// Lines moved by PageUp/PageDown.
+const PAGE: usize = 20;
+
+/// The reflog view – where HEAD and each branch pointed over time, and a
+/// way back to any of those states.
+pub struct ReflogView {
+    /// `HEAD` and the full names of local branches.
+    refs: Vec<String>,
+    selected_ref: usize,
+    entries: Vec<ReflogEntry>,
+    selected_index: usize,
+    /// Commit the selected entry moved the ref to.
+    commit: Option<CommitInfo>,
+    /// Checkout of the selected entry asked for, waiting for `y`.
+    confirm_checkout: bool,
+    reset_prompt: Option<ResetPrompt>,
+    branch_prompt: Option<TextInput>,
+    status_message: String,
+}
+
+impl ReflogView {
+    pub fn new() -> Self {
+        Self {
+            refs: vec!["HEAD".to_string()],
+            selected_ref: 0,
+            entries: Vec::new(),
+            selected_index: 0,
+            commit: None,
+            confirm_checkout: false,
+            reset_prompt: None,
+            branch_prompt: None,
+            status_message: "Ready".to_string(),
+        }
+    }
+
+    /// Reload the refs and the reflog of the shown one.
+    pub fn refresh(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        let current = self.refname().to_string();
+        self.refs = repo.reflog_refs()?;
+        self.selected_ref = self.refs.iter().position(|r| *r == current).unwrap_or(0);
+        self.load(repo)?;
+        self.status_message = format!("{} entries in the reflog of {}", self.entries.len(), self.ref_label());
+        Ok(())
+    }
+
+    /// Show the reflog of `refname` (`HEAD` or a full ref name).
+    pub fn show(&mut self, repo: &Repository, refname: &str) -> Result<(), GitzError> {
+        self.refs = repo.reflog_refs()?;
+        self.selected_ref = self.refs.iter().position(|r| r == refname).unwrap_or(0);
+        self.selected_index = 0;
+        self.load(repo)?;
+        self.status_message = format!("{} entries in the reflog of {}", self.entries.len(), self.ref_label());
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (a prompt is open).
+    pub fn captures_input(&self) -> bool {
+        self.branch_prompt.is_some() || self.reset_prompt.is_some()
+    }
+
+    fn refname(&self) -> &str {
+        self.refs.get(self.selected_ref).map_or("HEAD", String::as_str)
+    }
+
+    fn ref_label(&self) -> &str {
+        let refname = self.refname();
+        refname.strip_prefix("refs/heads/").unwrap_or(refname)
+    }
+
+    fn load(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.entries = repo.reflog(self.refname())?;
+        if self.selected_index >= self.entries.len() {
+            self.selected_index = self.entries.len().saturating_sub(1);
+        }
+        self.load_commit(repo);
+        Ok(())
+    }
+
+    fn load_commit(&mut self, repo: &Repository) {
+        // The commit may have been pruned since the entry was written.
+        self.commit = self.selected().and_then(|e| repo.find_commit(e.new).ok());
+    }
+
+    fn selected(&self) -> Option<&ReflogEntry> {
+        self.entries.get(self.selected_index)
+    }
+
+    fn move_selection(&mut self, repo: &Repository, delta: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        self.selected_index = self.selected_index.saturating_add_signed(delta).min(self.entries.len() - 1);
+        self.load_commit(repo);
+    }
+
+    fn switch_ref(&mut self, repo: &Repository, forward: bool) -> Result<(), GitzError> {
+        let len = self.refs.len().max(1);
+        self.selected_ref = if forward { (self.selected_ref + 1) % len } else { (self.selected_ref + len - 1) % len };
+        self.selected_index = 0;
+        self.load(repo)?;
+        self.status_message = format!("{} entries in the reflog of {}", self.entries.len(), self.ref_label());
+        Ok(())
+    }
+
+    /// Refresh and report the outcome of an operation.
+    fn finish(&mut self, repo: &Repository, result: Result<(), GitzError>, success: String) -> Result<(), GitzError> {
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(()) => success,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &mut Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if let Some(mut prompt) = self.reset_prompt.take() {
+            match prompt.handle_key(key, repo) {
+                Some(message) => {
+                    self.refresh(repo)?;
+                    self.status_message = message;
+                }
+                None => self.reset_prompt = Some(prompt),
+            }
+            return Ok(false);
+        }
+
+        if let Some(mut input) = self.branch_prompt.take() {
+            match input.handle_key(key) {
+                InputResult::Pending => self.branch_prompt = Some(input),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(name) => {
+                    if let Some(entry) = self.selected().cloned() {
+                        let name = name.trim().to_string();
+                        let result = repo.create_branch(&name, entry.new);
+                        self.finish(repo, result, format!("Created branch {} at {}", name, entry.short_new()))?;
+                    }
+                }
+            }
+            return Ok(false);
+        }
+
+        if std::mem::take(&mut self.confirm_checkout) {
+            if let (KeyCode::Char('y'), Some(entry)) = (key.code, self.selected().cloned()) {
+                let result = repo.checkout_detached(entry.new);
+                self.finish(repo, result, format!("HEAD detached at {}", entry.short_new()))?;
+            } else {
+                self.status_message = "Cancelled".to_string();
+            }
+            return Ok(false);
+        }
+
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => self.refresh(repo)?,
+            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(repo, 1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(repo, -1),
+            KeyCode::PageDown => self.move_selection(repo, PAGE as isize),
+            KeyCode::PageUp => self.move_selection(repo, -(PAGE as isize)),
+            KeyCode::Char(']') => self.switch_ref(repo, true)?,
+            KeyCode::Char('[') => self.switch_ref(repo, false)?,
+            KeyCode::Char('x') => {
+                if let Some(entry) = self.selected() {
+                    let label = format!("{} ({}@{{{}}}: {})", entry.short_new(), self.ref_label(), entry.index, entry.operation());
+                    self.reset_prompt = Some(ResetPrompt::new(entry.new, label));
+                }
+            }
+            KeyCode::Char('o') => {
+                if let Some(entry) = self.selected() {
+                    self.status_message = format!("Check out {} (detached HEAD)? [y/N]", entry.short_new());
+                    self.confirm_checkout = true;
+                }
+            }
+            KeyCode::Char('n') => {
+                if let Some(entry) = self.selected() {
+                    self.branch_prompt = Some(TextInput::new(format!("New branch at {}", entry.short_new()), ""));
+                }
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn entry_line(&self, entry: &ReflogEntry) -> Line<'static> {
+        let op_color = match entry.operation().split_whitespace().next().unwrap_or("") {
+            "reset" | "rebase" => Color::Magenta,
+            "checkout" => Color::Cyan,
+            "merge" | "pull" => Color::Green,
+            _ => Color::White,
+        };
+        Line::from(vec![
+            Span::styled(format!("{}@{{{}}} ", self.ref_label(), entry.index), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{} ", entry.short_new()), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{}: ", entry.operation()), Style::default().fg(op_color).add_modifier(Modifier::BOLD)),
+            Span::raw(entry.description().to_string()),
+            Span::styled(format!("  {}", relative_time(entry.time)), Style::default().fg(Color::DarkGray)),
+        ])
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let top_text = format!(
+            "gitz - Branch: {}   Reflog of: {} ({}/{})   Entries: {}",
+            branch_name,
+            self.ref_label(),
+            self.selected_ref + 1,
+            self.refs.len(),
+            self.entries.len()
+        );
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Reflog"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.entries.is_empty() {
+            vec![ListItem::new(Span::styled("No reflog entries", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.entries.iter().map(|e| ListItem::new(self.entry_line(e))).collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Reflog"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut state = ListState::default();
+        if !self.entries.is_empty() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        let mut lines = Vec::new();
+        if let Some(entry) = self.selected() {
+            lines.push(Line::from(Span::styled(entry.message.clone(), Style::default().add_modifier(Modifier::BOLD))));
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!("Old:  {}", entry.short_old())));
+            lines.push(Line::from(format!("New:  {}", entry.new)));
+            lines.push(Line::from(format!("By:   {}, {}", entry.committer, relative_time(entry.time))));
+            lines.push(Line::from(""));
+            match &self.commit {
+                Some(commit) => {
+                    lines.push(Line::from(format!("Author: {} <{}>", commit.author, commit.email)));
+                    lines.push(Line::from(""));
+                    lines.extend(commit.message.trim_end().lines().map(|l| Line::from(format!("    {}", l))));
+                }
+                None => lines.push(Line::from(Span::styled(
+                    "Commit no longer in the repository",
+                    Style::default().fg(Color::Red),
+                ))),
+            }
+        }
+        let details = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title("Entry"));
+        f.render_widget(details, main_chunks[1]);
+
+        let help_text = format!(
+            "{} | [j/k]move [ / ]switch ref [x]reset here [o] check out [n]ew branch [r]efresh [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        if let Some(input) = &self.branch_prompt {
+            input.draw(f, size);
+        }
+        if let Some(prompt) = &self.reset_prompt {
+            prompt.draw(f, size);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ReflogView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,383 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::{AppEvent, TaskOrigin};
+use crate::git::{BranchInfo, BranchKind, RemoteInfo, RemoteProgress, Repository};
+use crate::ui::components::input::{InputResult, TextInput};
+use crate::ui::components::status_bar;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::Sender;
+
+/// Minimum time between two transfer progress events sent to the UI.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Network operation run in the background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RemoteOp {
+    Fetch,
+    Pull,
+    Push,
+}
+
+impl RemoteOp {
+    fn verb(&self) -> &'static str {
+        match self {
+            RemoteOp::Fetch => "Fetching",
+            RemoteOp::Pull => "Pulling",
+            RemoteOp::Push => "Pushing",
+        }
+    }
+
+    fn run(&self, repo: &Repository, remote: &str, progress: &mut dyn FnMut(RemoteProgress)) -> Result<String, GitzError> {
+        match self {
+            RemoteOp::Fetch => repo.fetch(remote, progress).map(|_| format!("Fetched {}", remote)),
+            RemoteOp::Pull => repo.pull(remote, progress).map(|outcome| format!("Pull from {}: {}", remote, outcome.describe())),
+            RemoteOp::Push => repo.push(remote, progress).map(|_| format!("Pushed to {}", remote)),
+        }
+    }
+}
+
+/// What the open text prompt is asking for.
+#[derive(Debug, Clone)]
+enum Prompt {
+    AddName,
+    AddUrl { name: String },
+    Rename { from: String },
+    Url { name: String },
+}
+
+/// The remotes view – manages remotes and runs fetch, pull and push.
+pub struct RemotesView {
+    remotes: Vec<RemoteInfo>,
+    /// Remote-tracking branches, shown for the selected remote.
+    branches: Vec<BranchInfo>,
+    selected_index: usize,
+    status_message: String,
+    prompt: Option<(Prompt, TextInput)>,
+    pending_remove: Option<String>,
+    /// Description of the running operation, if any.
+    running: Option<String>,
+    /// Latest progress line of the running operation.
+    progress: String,
+    event_tx: Sender<AppEvent>,
+}
+
+impl RemotesView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            remotes: Vec::new(),
+            branches: Vec::new(),
+            selected_index: 0,
+            status_message: "Ready".to_string(),
+            prompt: None,
+            pending_remove: None,
+            running: None,
+            progress: String::new(),
+            event_tx,
+        }
+    }
+
+    /// Refresh the view data from the repository.
+    pub fn refresh(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.remotes = repo.remotes()?;
+        self.branches = repo
+            .branches()?
+            .into_iter()
+            .filter(|b| b.kind == BranchKind::Remote)
+            .collect();
+        if self.selected_index >= self.remotes.len() {
+            self.selected_index = self.remotes.len().saturating_sub(1);
+        }
+        self.status_message = format!("Refreshed: {} remotes", self.remotes.len());
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (a prompt is open).
+    pub fn captures_input(&self) -> bool {
+        self.prompt.is_some()
+    }
+
+    /// Show the latest progress line of the running operation.
+    pub fn set_progress(&mut self, line: String) {
+        self.progress = line;
+    }
+
+    /// Called when the background operation has finished.
+    pub fn task_finished(&mut self, repo: &Repository, result: Result<String, String>) -> Result<(), GitzError> {
+        self.running = None;
+        self.progress.clear();
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(summary) => summary,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&RemoteInfo> {
+        self.remotes.get(self.selected_index)
+    }
+
+    /// Refresh and report the outcome of an operation.
+    fn finish(&mut self, repo: &Repository, result: Result<(), GitzError>, success: String) -> Result<(), GitzError> {
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(()) => success,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Run a network operation on a blocking thread, reporting back through the event channel.
+    fn start(&mut self, repo: &Repository, op: RemoteOp) {
+        if let Some(running) = &self.running {
+            self.status_message = format!("Busy: {}", running);
+            return;
+        }
+        let Some(remote) = self.selected().map(|r| r.name.clone()) else {
+            return;
+        };
+        let label = format!("{} {}", op.verb(), remote);
+        self.status_message = format!("{}...", label);
+        self.running = Some(label);
+
+        let repo = repo.clone();
+        let tx = self.event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let progress_tx = tx.clone();
+            let mut last_sent: Option<Instant> = None;
+            let mut progress = move |p: RemoteProgress| {
+                if last_sent.is_some_and(|t| t.elapsed() < PROGRESS_INTERVAL) {
+                    return;
+                }
+                // Dropping a progress line when the channel is full is fine.
+                if progress_tx.try_send(AppEvent::Progress(p.describe())).is_ok() {
+                    last_sent = Some(Instant::now());
+                }
+            };
+            let result = op.run(&repo, &remote, &mut progress).map_err(|e| e.to_string());
+            let _ = tx.blocking_send(AppEvent::TaskDone { origin: TaskOrigin::Remotes, result });
+        });
+    }
+
+    fn submit_prompt(&mut self, prompt: Prompt, value: String, repo: &Repository) -> Result<(), GitzError> {
+        let value = value.trim().to_string();
+        match prompt {
+            Prompt::AddName => {
+                let title = format!("URL for {}", value);
+                self.prompt = Some((Prompt::AddUrl { name: value }, TextInput::new(title, "")));
+            }
+            Prompt::AddUrl { name } => {
+                let result = repo.add_remote(&name, &value);
+                self.finish(repo, result, format!("Added remote {}", name))?;
+                if let Some(idx) = self.remotes.iter().position(|r| r.name == name) {
+                    self.selected_index = idx;
+                }
+            }
+            Prompt::Rename { from } => {
+                let result = repo.rename_remote(&from, &value);
+                self.finish(repo, result, format!("Renamed {} to {}", from, value))?;
+            }
+            Prompt::Url { name } => {
+                let result = repo.set_remote_url(&name, &value);
+                self.finish(repo, result, format!("Updated URL of {}", name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if let Some((prompt, mut input)) = self.prompt.take() {
+            match input.handle_key(key) {
+                InputResult::Pending => self.prompt = Some((prompt, input)),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(value) => self.submit_prompt(prompt, value, repo)?,
+            }
+            return Ok(false);
+        }
+
+        if let Some(name) = self.pending_remove.take() {
+            if key.code == KeyCode::Char('y') {
+                let result = repo.remove_remote(&name);
+                self.finish(repo, result, format!("Removed remote {}", name))?;
+            } else {
+                self.status_message = "Remove cancelled".to_string();
+            }
+            return Ok(false);
+        }
+
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => {
+                // Refresh manually
+                self.refresh(repo)?;
+            }
+            KeyCode::Char('q') => {
+                return Ok(true); // Signal to quit
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.selected_index + 1 < self.remotes.len() => {
+                self.selected_index += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_index > 0 => {
+                self.selected_index -= 1;
+            }
+            KeyCode::Char('a') => {
+                self.prompt = Some((Prompt::AddName, TextInput::new("New remote name", "")));
+            }
+            KeyCode::Char('R') => {
+                if let Some(remote) = self.selected() {
+                    let from = remote.name.clone();
+                    let input = TextInput::new(format!("Rename {}", from), from.clone());
+                    self.prompt = Some((Prompt::Rename { from }, input));
+                }
+            }
+            KeyCode::Char('e') => {
+                if let Some(remote) = self.selected() {
+                    let input = TextInput::new(
+                        format!("URL for {}", remote.name),
+                        remote.fetch_url.clone().unwrap_or_default(),
+                    );
+                    self.prompt = Some((Prompt::Url { name: remote.name.clone() }, input));
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(name) = self.selected().map(|r| r.name.clone()) {
+                    self.status_message = format!("Remove remote {}? [y/N]", name);
+                    self.pending_remove = Some(name);
+                }
+            }
+            KeyCode::Char('f') => self.start(repo, RemoteOp::Fetch),
+            KeyCode::Char('p') => self.start(repo, RemoteOp::Pull),
+            KeyCode::Char('P') => self.start(repo, RemoteOp::Push),
+            _ => {}
+        }
+        Ok(false) // Continue running
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let top_text = format!(
+            "gitz - Repository: {}   Branch: {}   Remotes: {}",
+            repo.root().display(),
+            branch_name,
+            self.remotes.len()
+        );
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Remotes"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Percentage(60)
+            ])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.remotes.is_empty() {
+            vec![ListItem::new(Span::styled("No remotes", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.remotes
+                .iter()
+                .map(|r| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(r.name.clone(), Style::default().fg(Color::Green)),
+                        Span::styled(
+                            format!("  {}", r.fetch_url.as_deref().unwrap_or("")),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ]))
+                })
+                .collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Remotes"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut state = ListState::default();
+        if !self.remotes.is_empty() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        let detail_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(main_chunks[1]);
+
+        let mut lines = Vec::new();
+        if let Some(remote) = self.selected() {
+            let fetch = remote.fetch_url.as_deref().unwrap_or("-");
+            lines.push(Line::from(format!("Fetch URL: {}", fetch)));
+            lines.push(Line::from(format!("Push URL:  {}", remote.push_url.as_deref().unwrap_or(fetch))));
+            lines.push(Line::from(""));
+            let prefix = format!("{}/", remote.name);
+            let tracking: Vec<&BranchInfo> = self.branches.iter().filter(|b| b.name.starts_with(&prefix)).collect();
+            lines.push(Line::from(Span::styled(
+                format!("Remote-tracking branches ({})", tracking.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            for branch in tracking {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {}", branch.name), Style::default().fg(Color::Red)),
+                    Span::styled(format!("  {}", branch.summary), Style::default().fg(Color::Gray)),
+                ]));
+            }
+        }
+        let details = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Details"));
+        f.render_widget(details, detail_chunks[0]);
+
+        let progress_text = match &self.running {
+            Some(label) if self.progress.is_empty() => format!("{}...", label),
+            Some(label) => format!("{}: {}", label, self.progress),
+            None => "Idle".to_string(),
+        };
+        let progress = Paragraph::new(progress_text)
+            .style(Style::default().fg(if self.running.is_some() { Color::Yellow } else { Color::DarkGray }))
+            .block(Block::default().borders(Borders::ALL).title("Progress"));
+        f.render_widget(progress, detail_chunks[1]);
+
+        let help_text = format!(
+            "{} | [f]etch [p]ull [P]ush [a]dd [R]ename [e]dit URL [d]elete [r]efresh [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        if let Some((_, input)) = &self.prompt {
+            input.draw(f, size);
+        }
+
+        Ok(())
+    }
+}
@@ -1,65 +1,544 @@
 #![allow(dead_code)]
 
 
-use crate::git::{Repository, RepoStatus};
+use crate::git::{Repository, RepoStatus, DiffSelection, DiffTarget, FileDiff, StatusSection};
 use crate::config::Config;
+use crate::ui::components::diff_view::{self, DiffRow};
+use crate::ui::components::input::{centered_rect, InputResult};
+use crate::ui::components::text_area::TextArea;
 use crate::ui::components::{file_list, status_bar};
+use crate::commands;
+use crate::event::AppEvent;
 use crossterm::event::KeyEvent;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::{Color, Style};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use std::cell::Cell;
+use tokio::sync::mpsc::Sender;
 
-/// The main repository view – shows status and a placeholder for diff.
+/// Lines scrolled by PageUp/PageDown in the diff pane.
+const DIFF_PAGE: u16 = 10;
+
+/// Which pane receives navigation keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Files,
+    Diff,
+}
+
+/// A row of the diff pane and the side of the working copy it belongs to.
+struct PaneRow {
+    target: Option<DiffTarget>,
+    row: DiffRow,
+}
+
+impl PaneRow {
+    /// Rows the cursor may rest on: hunk headers and hunk lines.
+    fn is_selectable(&self) -> bool {
+        self.target.is_some() && self.row.hunk.is_some()
+    }
+
+    fn same_hunk(&self, other: &PaneRow) -> bool {
+        self.target == other.target && self.row.file == other.row.file && self.row.hunk == other.row.hunk
+    }
+}
+
+/// A discard waiting for confirmation.
+enum PendingDiscard {
+    Selection { file: FileDiff, selection: DiffSelection },
+    File { path: String },
+}
+
+/// What the commit message editor will do on submit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommitMode {
+    New,
+    /// Replace HEAD with the staged changes and the edited message.
+    Amend,
+}
+
+/// The main repository view – shows status and the diff of the selected file.
 pub struct RepoView {
     status: RepoStatus,
     selected_file_index: usize,
     status_message: String,
-    // In a full implementation we would keep selected file, diff view, etc.
+    staged_diff: Vec<FileDiff>,
+    unstaged_diff: Vec<FileDiff>,
+    rows: Vec<PaneRow>,
+    focus: Focus,
+    diff_cursor: usize,
+    diff_anchor: Option<usize>,
+    diff_scroll: u16,
+    diff_height: Cell<u16>,
+    pending_discard: Option<PendingDiscard>,
+    /// Open commit message editor.
+    commit_editor: Option<(CommitMode, TextArea)>,
+    /// Message kept from a cancelled editor session.
+    commit_draft: Option<String>,
+    context_lines: u32,
+    show_line_numbers: bool,
+    event_tx: Sender<AppEvent>,
 }
 
 impl RepoView {
-    pub fn new() -> Self {
-        Self { 
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
             status: RepoStatus::default(),
             selected_file_index: 0,
             status_message: "Ready".to_string(),
+            staged_diff: Vec::new(),
+            unstaged_diff: Vec::new(),
+            rows: Vec::new(),
+            focus: Focus::Files,
+            diff_cursor: 0,
+            diff_anchor: None,
+            diff_scroll: 0,
+            diff_height: Cell::new(0),
+            pending_discard: None,
+            commit_editor: None,
+            commit_draft: None,
+            context_lines: 3,
+            show_line_numbers: true,
+            event_tx,
         }
     }
 
     /// Refresh the view data from the repository.
-    pub fn refresh(&mut self, repo: &Repository) -> Result<(), crate::errors::GitzError> {
+    pub fn refresh(&mut self, repo: &Repository, cfg: &Config) -> Result<(), crate::errors::GitzError> {
+        self.context_lines = cfg.ui.diff_context_lines as u32;
+        self.show_line_numbers = cfg.ui.show_line_numbers;
         self.status = repo.status()?;
+        let total_files = self.status.total_changes();
+        if self.selected_file_index >= total_files {
+            self.selected_file_index = total_files.saturating_sub(1);
+        }
+        self.load_diff(repo)?;
         self.status_message = format!("Refreshed: {}", self.status.summary());
         Ok(())
     }
 
+    /// Reload the diff of the selected entry for the section it is listed in.
+    fn load_diff(&mut self, repo: &Repository) -> Result<(), crate::errors::GitzError> {
+        self.diff_scroll = 0;
+        self.diff_cursor = 0;
+        self.diff_anchor = None;
+        self.staged_diff.clear();
+        self.unstaged_diff.clear();
+        if let Some(entry) = file_list::get_file_at_index(&self.status, self.selected_file_index) {
+            match entry.section {
+                StatusSection::Staged => {
+                    // Include the old path so staged renames show as one delta.
+                    let old = entry.change.as_ref().and_then(|c| c.renamed_from.as_deref());
+                    let paths: Vec<&str> = std::iter::once(entry.path.as_str()).chain(old).collect();
+                    self.staged_diff = repo.diff(DiffTarget::Staged, &paths, self.context_lines)?;
+                }
+                StatusSection::Unstaged | StatusSection::Untracked => {
+                    self.unstaged_diff = repo.diff(DiffTarget::Unstaged, &[&entry.path], self.context_lines)?;
+                }
+                StatusSection::Conflicts => {}
+            }
+        }
+        self.build_rows();
+        Ok(())
+    }
+
+    /// Lay out the staged and unstaged sections of the diff pane.
+    fn build_rows(&mut self) {
+        self.rows.clear();
+        for (label, target, files) in [
+            ("Staged", DiffTarget::Staged, &self.staged_diff),
+            ("Unstaged", DiffTarget::Unstaged, &self.unstaged_diff),
+        ] {
+            if files.is_empty() {
+                continue;
+            }
+            let header = Line::from(Span::styled(
+                format!("── {} ──", label),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ));
+            self.rows.push(PaneRow {
+                target: None,
+                row: DiffRow { line: header, file: None, hunk: None, line_index: None },
+            });
+            self.rows.extend(
+                diff_view::diff_rows(files, self.show_line_numbers)
+                    .into_iter()
+                    .map(|row| PaneRow { target: Some(target), row }),
+            );
+        }
+    }
+
+    /// Move the diff cursor to the next selectable row in `direction` (+1/-1).
+    fn move_diff_cursor(&mut self, direction: isize) {
+        let mut idx = self.diff_cursor as isize;
+        loop {
+            idx += direction;
+            if idx < 0 || idx as usize >= self.rows.len() {
+                return;
+            }
+            if self.rows[idx as usize].is_selectable() {
+                self.diff_cursor = idx as usize;
+                self.scroll_to_cursor();
+                return;
+            }
+        }
+    }
+
+    /// Put the cursor on the first selectable row at or after `start`, falling back to before it.
+    fn snap_diff_cursor(&mut self, start: usize) -> bool {
+        let forward = (start..self.rows.len()).find(|&i| self.rows[i].is_selectable());
+        let backward = || (0..start.min(self.rows.len())).rev().find(|&i| self.rows[i].is_selectable());
+        match forward.or_else(backward) {
+            Some(idx) => {
+                self.diff_cursor = idx;
+                self.scroll_to_cursor();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let cursor = self.diff_cursor as u16;
+        let height = self.diff_height.get().max(1);
+        if cursor < self.diff_scroll {
+            self.diff_scroll = cursor;
+        } else if cursor >= self.diff_scroll + height {
+            self.diff_scroll = cursor + 1 - height;
+        }
+    }
+
+    /// The file diff and selection under the cursor (or the anchored range).
+    fn current_selection(&self, whole_hunk: bool) -> Option<(DiffTarget, FileDiff, DiffSelection)> {
+        let cursor = self.rows.get(self.diff_cursor)?;
+        let target = cursor.target?;
+        let hunk = cursor.row.hunk?;
+        let files = match target {
+            DiffTarget::Staged => &self.staged_diff,
+            DiffTarget::Unstaged => &self.unstaged_diff,
+        };
+        let file = files.get(cursor.row.file?)?.clone();
+
+        let selection = match (whole_hunk, self.diff_anchor, cursor.row.line_index) {
+            (true, _, _) | (false, None, None) => DiffSelection::hunk(hunk),
+            (false, Some(anchor), _) => {
+                let (lo, hi) = (anchor.min(self.diff_cursor), anchor.max(self.diff_cursor));
+                let mut lines = self.rows[lo..=hi]
+                    .iter()
+                    .filter(|r| r.same_hunk(cursor))
+                    .filter_map(|r| r.row.line_index);
+                let first = lines.next()?;
+                let last = lines.next_back().unwrap_or(first);
+                DiffSelection::lines(hunk, first..=last)
+            }
+            (false, None, Some(line)) => DiffSelection::lines(hunk, line..=line),
+        };
+        Some((target, file, selection))
+    }
+
+    /// Stage or unstage the current selection depending on its section.
+    fn toggle_selection(&mut self, repo: &Repository, cfg: &Config, whole_hunk: bool) -> Result<(), crate::errors::GitzError> {
+        let Some((target, file, selection)) = self.current_selection(whole_hunk) else {
+            return Ok(());
+        };
+        let (result, done) = match target {
+            DiffTarget::Unstaged => (repo.stage_selection(&file, &selection), "Staged"),
+            DiffTarget::Staged => (repo.unstage_selection(&file, &selection), "Unstaged"),
+        };
+        self.finish_diff_action(repo, cfg, result, &format!("{} {} in {}", done, describe(&selection), file.path()))
+    }
+
+    /// Ask for confirmation before discarding the current selection.
+    fn request_discard(&mut self, whole_hunk: bool) {
+        match self.current_selection(whole_hunk) {
+            Some((DiffTarget::Unstaged, file, selection)) => {
+                self.status_message = format!("Discard {} in {}? [y/N]", describe(&selection), file.path());
+                self.pending_discard = Some(PendingDiscard::Selection { file, selection });
+            }
+            Some((DiffTarget::Staged, _, _)) => {
+                self.status_message = "Only unstaged changes can be discarded".to_string();
+            }
+            None => {}
+        }
+    }
+
+    /// Reload after a diff action, keeping the cursor near where it was.
+    fn finish_diff_action(
+        &mut self,
+        repo: &Repository,
+        cfg: &Config,
+        result: Result<(), crate::errors::GitzError>,
+        success: &str,
+    ) -> Result<(), crate::errors::GitzError> {
+        let cursor = self.diff_cursor;
+        self.refresh(repo, cfg)?;
+        self.status_message = match result {
+            Ok(()) => success.to_string(),
+            Err(e) => format!("Error: {}", e),
+        };
+        if !self.snap_diff_cursor(cursor) {
+            self.focus = Focus::Files;
+        }
+        Ok(())
+    }
+
+    /// Stage the selected entry (marks conflicts as resolved).
+    fn stage_file(&mut self, repo: &Repository, cfg: &Config) -> Result<(), crate::errors::GitzError> {
+        let Some(entry) = file_list::get_file_at_index(&self.status, self.selected_file_index) else {
+            return Ok(());
+        };
+        if entry.section == StatusSection::Staged {
+            self.status_message = format!("{} is already staged", entry.path);
+            return Ok(());
+        }
+        let result = crate::commands::add::stage_file(repo, &entry.path);
+        self.finish_file_action(repo, cfg, result, format!("Staged {}", entry.path))
+    }
+
+    /// Unstage the selected entry, including the source of a staged rename.
+    fn unstage_file(&mut self, repo: &Repository, cfg: &Config) -> Result<(), crate::errors::GitzError> {
+        let Some(entry) = file_list::get_file_at_index(&self.status, self.selected_file_index) else {
+            return Ok(());
+        };
+        if entry.section != StatusSection::Staged {
+            self.status_message = format!("{} has no staged changes", entry.path);
+            return Ok(());
+        }
+        let mut result = crate::commands::restore::unstage_file(repo, &entry.path);
+        if let Some(from) = entry.change.as_ref().and_then(|c| c.renamed_from.as_deref()) {
+            result = result.and_then(|_| crate::commands::restore::unstage_file(repo, from));
+        }
+        self.finish_file_action(repo, cfg, result, format!("Unstaged {}", entry.path))
+    }
+
+    /// Stage or unstage the selected entry depending on its section.
+    fn toggle_file(&mut self, repo: &Repository, cfg: &Config) -> Result<(), crate::errors::GitzError> {
+        match file_list::get_file_at_index(&self.status, self.selected_file_index) {
+            Some(entry) if entry.section == StatusSection::Staged => self.unstage_file(repo, cfg),
+            Some(_) => self.stage_file(repo, cfg),
+            None => Ok(()),
+        }
+    }
+
+    /// Ask for confirmation before discarding the selected entry's work tree changes.
+    fn request_file_discard(&mut self) {
+        let Some(entry) = file_list::get_file_at_index(&self.status, self.selected_file_index) else {
+            return;
+        };
+        match entry.section {
+            StatusSection::Unstaged => {
+                self.status_message = format!("Discard unstaged changes to {}? [y/N]", entry.path);
+            }
+            StatusSection::Untracked => {
+                self.status_message = format!("Delete untracked {}? [y/N]", entry.path);
+            }
+            StatusSection::Staged | StatusSection::Conflicts => {
+                self.status_message = "Only unstaged or untracked files can be discarded".to_string();
+                return;
+            }
+        }
+        self.pending_discard = Some(PendingDiscard::File { path: entry.path });
+    }
+
+    /// Reload after a file action and report its outcome.
+    fn finish_file_action(
+        &mut self,
+        repo: &Repository,
+        cfg: &Config,
+        result: Result<(), crate::errors::GitzError>,
+        success: String,
+    ) -> Result<(), crate::errors::GitzError> {
+        self.refresh(repo, cfg)?;
+        self.status_message = match result {
+            Ok(()) => success,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (the editor is open).
+    pub fn captures_input(&self) -> bool {
+        self.commit_editor.is_some()
+    }
+
+    /// Open the commit message editor with the draft, or else `commit.template`.
+    fn open_commit_editor(&mut self, repo: &Repository) -> Result<(), crate::errors::GitzError> {
+        if self.status.section(StatusSection::Staged).is_empty() {
+            self.status_message = "Nothing staged to commit".to_string();
+            return Ok(());
+        }
+        let initial = match self.commit_draft.take() {
+            Some(draft) => draft,
+            None => repo.commit_template()?.unwrap_or_default(),
+        };
+        self.commit_editor = Some((CommitMode::New, TextArea::new(&initial)));
+        self.status_message = "Writing commit message".to_string();
+        Ok(())
+    }
+
+    /// Open the editor on HEAD's message to amend it with the staged changes.
+    fn open_amend_editor(&mut self, repo: &Repository) -> Result<(), crate::errors::GitzError> {
+        let Some(head) = repo.recent_commits(1)?.pop() else {
+            self.status_message = "Nothing to amend".to_string();
+            return Ok(());
+        };
+        self.commit_editor = Some((CommitMode::Amend, TextArea::new(&head.message)));
+        self.status_message = format!("Amending {}", head.short_id());
+        Ok(())
+    }
+
+    /// Draw the commit editor popup with the staged files next to it.
+    fn draw_commit_editor(&self, f: &mut ratatui::Frame, mode: CommitMode, editor: &TextArea) {
+        let popup = centered_rect(80, f.area().height.saturating_sub(8), f.area());
+        f.render_widget(Clear, popup);
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(popup);
+        let title = match mode {
+            CommitMode::New => "Commit message (Ctrl+S commit, Esc cancel; # lines are ignored)",
+            CommitMode::Amend => "Amend HEAD (Ctrl+S amend, Esc cancel; # lines are ignored)",
+        };
+        editor.draw(f, halves[0], title);
+
+        let staged = self.status.section(StatusSection::Staged);
+        let mut lines = Vec::new();
+        for file in &staged {
+            if let Some(change) = &file.index {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:<9}", change.kind.label()), Style::default().fg(Color::Green)),
+                    Span::raw(file.path.clone()),
+                ]));
+            }
+        }
+        let summary = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(format!("Staged ({})", staged.len())));
+        f.render_widget(summary, halves[1]);
+    }
+
     /// Handle a key press.
     pub fn handle_key(
         &mut self,
         key: KeyEvent,
-        _repo: &Repository,
-        _cfg: &Config
+        repo: &Repository,
+        cfg: &Config
     ) -> Result<bool, crate::errors::GitzError> {
+        if let Some((mode, mut editor)) = self.commit_editor.take() {
+            match editor.handle_key(key) {
+                InputResult::Pending => self.commit_editor = Some((mode, editor)),
+                InputResult::Cancel if mode == CommitMode::Amend => {
+                    self.status_message = "Amend cancelled".to_string();
+                }
+                InputResult::Cancel => {
+                    let text = editor.text();
+                    self.commit_draft = (!commands::commit::cleanup_message(&text).is_empty()).then_some(text);
+                    self.status_message = match self.commit_draft {
+                        Some(_) => "Commit cancelled, draft kept".to_string(),
+                        None => "Commit cancelled".to_string(),
+                    };
+                }
+                InputResult::Submit(text) => {
+                    let message = commands::commit::cleanup_message(&text);
+                    let result = match mode {
+                        CommitMode::New => commands::commit::commit(repo, &message),
+                        CommitMode::Amend => commands::commit::amend(repo, &message),
+                    };
+                    match result {
+                        Ok(oid) => {
+                            if mode == CommitMode::New {
+                                self.commit_draft = None;
+                            }
+                            self.refresh(repo, cfg)?;
+                            let subject = message.lines().next().unwrap_or("");
+                            let verb = if mode == CommitMode::New { "Committed" } else { "Amended" };
+                            self.status_message = format!("{} {}: {}", verb, &oid.to_string()[..7], subject);
+                        }
+                        Err(e) => {
+                            self.status_message = format!("Error: {}", e);
+                            self.commit_editor = Some((mode, editor));
+                        }
+                    }
+                }
+            }
+            return Ok(false);
+        }
+
+        if let Some(pending) = self.pending_discard.take() {
+            if key.code != crossterm::event::KeyCode::Char('y') {
+                self.status_message = "Discard cancelled".to_string();
+                return Ok(false);
+            }
+            match pending {
+                PendingDiscard::Selection { file, selection } => {
+                    let result = repo.discard_selection(&file, &selection);
+                    let msg = format!("Discarded {} in {}", describe(&selection), file.path());
+                    self.finish_diff_action(repo, cfg, result, &msg)?;
+                }
+                PendingDiscard::File { path } => {
+                    let result = crate::commands::restore::discard_file(repo, &path);
+                    self.finish_file_action(repo, cfg, result, format!("Discarded changes to {}", path))?;
+                }
+            }
+            return Ok(false);
+        }
+
+        if self.focus == Focus::Diff {
+            match key.code {
+                crossterm::event::KeyCode::Down | crossterm::event::KeyCode::Char('j') => self.move_diff_cursor(1),
+                crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') => self.move_diff_cursor(-1),
+                crossterm::event::KeyCode::Char('v') => {
+                    self.diff_anchor = match self.diff_anchor {
+                        Some(_) => None,
+                        None => Some(self.diff_cursor),
+                    };
+                }
+                crossterm::event::KeyCode::Char(' ') => self.toggle_selection(repo, cfg, false)?,
+                crossterm::event::KeyCode::Char('a') => self.toggle_selection(repo, cfg, true)?,
+                crossterm::event::KeyCode::Char('d') => self.request_discard(false),
+                crossterm::event::KeyCode::Char('D') => self.request_discard(true),
+                crossterm::event::KeyCode::Esc
+                | crossterm::event::KeyCode::Left
+                | crossterm::event::KeyCode::Char('h') => {
+                    self.focus = Focus::Files;
+                    self.diff_anchor = None;
+                }
+                _ => self.handle_common_key(key, repo, cfg)?,
+            }
+            return Ok(false);
+        }
+
         match key.code {
-            crossterm::event::KeyCode::Char('s') => {
+            crossterm::event::KeyCode::Char('a') => {
                 // Stage all changes.
-                crate::commands::add::stage_all(_repo)?;
-                self.refresh(_repo)?;
+                crate::commands::add::stage_all(repo)?;
+                self.refresh(repo, cfg)?;
                 self.status_message = "Staged all changes".to_string();
             }
-            crossterm::event::KeyCode::Char('c') => {
-                // Simple commit – in a real app we would open an editor.
-                if self.status.is_clean() {
-                    self.status_message = "Nothing to commit".to_string();
-                } else {
-                    crate::commands::commit::commit(_repo, "quick commit")?;
-                    self.refresh(_repo)?;
-                    self.status_message = "Committed changes".to_string();
+            crossterm::event::KeyCode::Char(' ') => self.toggle_file(repo, cfg)?,
+            crossterm::event::KeyCode::Char('s') => self.stage_file(repo, cfg)?,
+            crossterm::event::KeyCode::Char('u') => self.unstage_file(repo, cfg)?,
+            crossterm::event::KeyCode::Char('d') => self.request_file_discard(),
+            crossterm::event::KeyCode::Char('c') => self.open_commit_editor(repo)?,
+            crossterm::event::KeyCode::Char('A') => self.open_amend_editor(repo)?,
+            crossterm::event::KeyCode::Char('b') => {
+                if let Some(entry) = file_list::get_file_at_index(&self.status, self.selected_file_index) {
+                    let _ = self.event_tx.try_send(AppEvent::OpenBlame { path: entry.path, at: None });
                 }
             }
-            crossterm::event::KeyCode::Char('r') | crossterm::event::KeyCode::F(5) => {
-                // Refresh manually
-                self.refresh(_repo)?;
+            crossterm::event::KeyCode::Char('H') => {
+                if let Some(entry) = file_list::get_file_at_index(&self.status, self.selected_file_index) {
+                    let _ = self.event_tx.try_send(AppEvent::OpenFileHistory(entry.path));
+                }
+            }
+            crossterm::event::KeyCode::Enter
+            | crossterm::event::KeyCode::Right
+            | crossterm::event::KeyCode::Char('l') => {
+                // Move into the diff pane to pick hunks and lines.
+                if self.snap_diff_cursor(0) {
+                    self.focus = Focus::Diff;
+                    self.diff_anchor = None;
+                }
             }
             crossterm::event::KeyCode::Char('q') => {
                 return Ok(true); // Signal to quit
@@ -69,30 +548,57 @@ impl RepoView {
                 let total_files = self.status.total_changes();
                 if total_files > 0 && self.selected_file_index < total_files - 1 {
                     self.selected_file_index += 1;
+                    self.load_diff(repo)?;
                 }
             }
-            crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') => {
+            crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') if self.selected_file_index > 0 => {
                 // Navigate up in file list
-                if self.selected_file_index > 0 {
-                    self.selected_file_index -= 1;
-                }
+                self.selected_file_index -= 1;
+                self.load_diff(repo)?;
             }
             crossterm::event::KeyCode::Home | crossterm::event::KeyCode::Char('g') => {
                 // Go to first file
                 self.selected_file_index = 0;
+                self.load_diff(repo)?;
             }
             crossterm::event::KeyCode::End | crossterm::event::KeyCode::Char('G') => {
                 // Go to last file
                 let total_files = self.status.total_changes();
                 if total_files > 0 {
                     self.selected_file_index = total_files - 1;
+                    self.load_diff(repo)?;
                 }
             }
-            _ => {}
+            _ => self.handle_common_key(key, repo, cfg)?,
         }
         Ok(false) // Continue running
     }
 
+    /// Keys that behave the same whichever pane has focus.
+    fn handle_common_key(&mut self, key: KeyEvent, repo: &Repository, cfg: &Config) -> Result<(), crate::errors::GitzError> {
+        match key.code {
+            crossterm::event::KeyCode::Char('r') | crossterm::event::KeyCode::F(5) => {
+                // Refresh manually
+                self.refresh(repo, cfg)?;
+                self.focus = Focus::Files;
+            }
+            crossterm::event::KeyCode::PageDown => {
+                self.diff_scroll = self.diff_scroll.saturating_add(DIFF_PAGE);
+            }
+            crossterm::event::KeyCode::PageUp => {
+                self.diff_scroll = self.diff_scroll.saturating_sub(DIFF_PAGE);
+            }
+            crossterm::event::KeyCode::Char('J') => {
+                self.diff_scroll = self.diff_scroll.saturating_add(1);
+            }
+            crossterm::event::KeyCode::Char('K') => {
+                self.diff_scroll = self.diff_scroll.saturating_sub(1);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     /// Draw the UI.
     pub fn draw(
         &self,
@@ -117,7 +623,7 @@ impl RepoView {
 
         let top_text = format!(
             "gitz - Repository: {}   Branch: {}   Status: {}",
-            repo.path().display(),
+            repo.root().display(),
             branch_name,
             self.status.summary()
         );
@@ -130,7 +636,7 @@ impl RepoView {
 
         f.render_widget(top_bar, chunks[0]);
 
-        // Main area split into file list and diff placeholder.
+        // Main area split into file list and diff preview.
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
@@ -143,15 +649,20 @@ impl RepoView {
         file_list::draw_file_list_with_selection(f, main_chunks[0], &self.status, Some(self.selected_file_index));
 
         // Diff preview on the right.
-        self.draw_diff_preview(f, main_chunks[1], repo)?;
+        self.draw_diff_preview(f, main_chunks[1])?;
 
         // Bottom status bar with keybindings help.
-        let help_text = format!(
-            "{} | [s]tage [c]ommit [r]efresh [q]uit [j/k]navigate",
-            self.status_message
-        );
+        let keys = match self.focus {
+            Focus::Files => "[space]toggle [s]tage [u]nstage [d]iscard [a]ll [c]ommit [A]mend [b]lame [H]istory [r]efresh [q]uit [Enter]diff [J/K]scroll",
+            Focus::Diff => "[space]stage/unstage line [a]hunk [v]range [d/D]discard line/hunk [Esc]files",
+        };
+        let help_text = format!("{} | {}", self.status_message, keys);
         status_bar::draw_status_bar(f, chunks[2], &help_text);
 
+        if let Some((mode, editor)) = &self.commit_editor {
+            self.draw_commit_editor(f, *mode, editor);
+        }
+
         Ok(())
     }
 
@@ -160,69 +671,61 @@ impl RepoView {
         &self,
         f: &mut ratatui::Frame,
         area: ratatui::layout::Rect,
-        _repo: &Repository,
     ) -> Result<(), crate::errors::GitzError> {
         let diff_block = Block::default()
             .title("Diff Preview")
             .borders(Borders::ALL)
             .style(Style::default().fg(Color::White));
+        self.diff_height.set(area.height.saturating_sub(2));
 
-        let diff_content = if self.status.is_clean() {
-            Paragraph::new("No changes to display")
+        if self.status.is_clean() {
+            let empty = Paragraph::new("No changes to display")
                 .style(Style::default().fg(Color::DarkGray))
-                .block(diff_block)
-        } else if let Some(selected_file) = file_list::get_file_at_index(&self.status, self.selected_file_index) {
-            // TODO: Implement actual diff display with syntax highlighting
-            // For now, show a placeholder with the selected file name
-            Paragraph::new(format!("Diff for: {}\n\nFeature coming soon...\nUse 's' to stage, 'c' to commit", selected_file))
-                .style(Style::default().fg(Color::Yellow))
-                .block(diff_block)
-        } else {
-            Paragraph::new("Select a file to view diff")
+                .block(diff_block);
+            f.render_widget(empty, area);
+            return Ok(());
+        }
+
+        let Some(selected) = file_list::get_file_at_index(&self.status, self.selected_file_index) else {
+            let empty = Paragraph::new("Select a file to view diff")
                 .style(Style::default().fg(Color::DarkGray))
-                .block(diff_block)
+                .block(diff_block);
+            f.render_widget(empty, area);
+            return Ok(());
         };
 
-        f.render_widget(diff_content, area);
+        let mut lines: Vec<Line> = Vec::with_capacity(self.rows.len());
+        let range = self.diff_anchor.map(|a| (a.min(self.diff_cursor), a.max(self.diff_cursor)));
+        for (idx, row) in self.rows.iter().enumerate() {
+            let mut line = row.row.line.clone();
+            if self.focus == Focus::Diff {
+                if idx == self.diff_cursor {
+                    line = line.patch_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+                } else if range.is_some_and(|(lo, hi)| (lo..=hi).contains(&idx) && row.row.line_index.is_some()) {
+                    line = line.patch_style(Style::default().bg(Color::Rgb(40, 40, 60)));
+                }
+            }
+            lines.push(line);
+        }
+        if lines.is_empty() {
+            let note = match selected.conflict {
+                Some(conflict) => format!("Unmerged path ({}) – resolve it in the work tree", conflict.description()),
+                None => "No textual changes".to_string(),
+            };
+            lines.push(Line::from(Span::styled(note, Style::default().fg(Color::DarkGray))));
+        }
+
+        let title = format!("Diff: {} [{}]", selected.path, selected.section.title());
+        diff_view::draw_diff(f, area, &title, lines, self.diff_scroll);
         Ok(())
     }
 }
 
-impl Default for RepoView {
-    fn default() -> Self {
-        Self::new()
+/// Short description of a selection for status messages.
+fn describe(selection: &DiffSelection) -> String {
+    match &selection.lines {
+        None => "hunk".to_string(),
+        Some(r) if r.start() == r.end() => "line".to_string(),
+        Some(r) => format!("{} lines", r.end() - r.start() + 1),
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_new_view_is_clean() {
-        let view = RepoView::new();
-        assert!(view.status.is_clean());
-        assert_eq!(view.selected_file_index, 0);
-    }
-
-    #[test]
-    fn test_navigation() {
-        let mut view = RepoView::new();
-        view.status = RepoStatus {
-            modified: vec!["file1.rs".to_string(), "file2.rs".to_string()],
-            added: vec![],
-            deleted: vec![],
-        };
-
-        // Start at 0
-        assert_eq!(view.selected_file_index, 0);
-
-        // Can't go below 0
-        view.selected_file_index = 0;
-        assert_eq!(view.selected_file_index, 0);
-
-        // Move down
-        view.selected_file_index = 1;
-        assert_eq!(view.selected_file_index, 1);
-    }
-}
\ No newline at end of file
@@ -0,0 +1,285 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::git::{FileDiff, Repository, StashInfo, StashOptions};
+use crate::ui::components::input::{InputResult, TextInput};
+use crate::ui::components::{diff_view, status_bar};
+use crate::utils::relative_time;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+
+/// Lines scrolled by PageUp/PageDown in the diff pane.
+const DIFF_PAGE: u16 = 10;
+
+/// The stashes view – lists stash entries with a diff preview.
+pub struct StashesView {
+    stashes: Vec<StashInfo>,
+    selected_index: usize,
+    preview: Vec<FileDiff>,
+    preview_scroll: u16,
+    status_message: String,
+    /// Options used by the next `s` (toggled with `u` / `i`).
+    include_untracked: bool,
+    keep_index: bool,
+    message_input: Option<TextInput>,
+    /// Index of a stash waiting for drop confirmation.
+    pending_drop: Option<usize>,
+    context_lines: u32,
+    show_line_numbers: bool,
+}
+
+impl StashesView {
+    pub fn new() -> Self {
+        Self {
+            stashes: Vec::new(),
+            selected_index: 0,
+            preview: Vec::new(),
+            preview_scroll: 0,
+            status_message: "Ready".to_string(),
+            include_untracked: false,
+            keep_index: false,
+            message_input: None,
+            pending_drop: None,
+            context_lines: 3,
+            show_line_numbers: true,
+        }
+    }
+
+    /// Refresh the view data from the repository.
+    pub fn refresh(&mut self, repo: &Repository, cfg: &Config) -> Result<(), GitzError> {
+        self.context_lines = cfg.ui.diff_context_lines as u32;
+        self.show_line_numbers = cfg.ui.show_line_numbers;
+        self.stashes = repo.stashes()?;
+        if self.selected_index >= self.stashes.len() {
+            self.selected_index = self.stashes.len().saturating_sub(1);
+        }
+        self.load_preview(repo)?;
+        self.status_message = format!("Refreshed: {} stashes", self.stashes.len());
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (a prompt is open).
+    pub fn captures_input(&self) -> bool {
+        self.message_input.is_some()
+    }
+
+    fn load_preview(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.preview_scroll = 0;
+        self.preview = match self.stashes.get(self.selected_index) {
+            Some(stash) => repo.stash_diff(stash.index, self.context_lines)?,
+            None => Vec::new(),
+        };
+        Ok(())
+    }
+
+    /// Refresh and report the outcome of an operation.
+    fn finish(&mut self, repo: &Repository, cfg: &Config, result: Result<(), GitzError>, success: String) -> Result<(), GitzError> {
+        self.refresh(repo, cfg)?;
+        self.status_message = match result {
+            Ok(()) => success,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &mut Repository,
+        cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if let Some(mut input) = self.message_input.take() {
+            match input.handle_key(key) {
+                InputResult::Pending => self.message_input = Some(input),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(message) => {
+                    let opts = StashOptions {
+                        message: Some(message),
+                        include_untracked: self.include_untracked,
+                        keep_index: self.keep_index,
+                    };
+                    let result = repo.stash_save(&opts).map(|_| ());
+                    self.selected_index = 0;
+                    self.finish(repo, cfg, result, "Saved working directory to stash@{0}".to_string())?;
+                }
+            }
+            return Ok(false);
+        }
+
+        if let Some(index) = self.pending_drop.take() {
+            if key.code == KeyCode::Char('y') {
+                let result = repo.stash_drop(index);
+                self.finish(repo, cfg, result, format!("Dropped stash@{{{}}}", index))?;
+            } else {
+                self.status_message = "Drop cancelled".to_string();
+            }
+            return Ok(false);
+        }
+
+        let selected = self.stashes.get(self.selected_index).map(|s| s.index);
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => {
+                // Refresh manually
+                self.refresh(repo, cfg)?;
+            }
+            KeyCode::Char('q') => {
+                return Ok(true); // Signal to quit
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.selected_index + 1 < self.stashes.len() => {
+                self.selected_index += 1;
+                self.load_preview(repo)?;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_index > 0 => {
+                self.selected_index -= 1;
+                self.load_preview(repo)?;
+            }
+            KeyCode::Char('s') => {
+                let mut title = "Stash message".to_string();
+                if self.include_untracked {
+                    title.push_str(" (+untracked)");
+                }
+                if self.keep_index {
+                    title.push_str(" (keep index)");
+                }
+                self.message_input = Some(TextInput::new(title, ""));
+            }
+            KeyCode::Char('u') => {
+                self.include_untracked = !self.include_untracked;
+                self.status_message = format!("Include untracked: {}", on_off(self.include_untracked));
+            }
+            KeyCode::Char('i') => {
+                self.keep_index = !self.keep_index;
+                self.status_message = format!("Keep index: {}", on_off(self.keep_index));
+            }
+            KeyCode::Char('a') => {
+                if let Some(index) = selected {
+                    let result = repo.stash_apply(index);
+                    self.finish(repo, cfg, result, format!("Applied stash@{{{}}}", index))?;
+                }
+            }
+            KeyCode::Char('p') => {
+                if let Some(index) = selected {
+                    let result = repo.stash_pop(index);
+                    self.finish(repo, cfg, result, format!("Popped stash@{{{}}}", index))?;
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(index) = selected {
+                    self.status_message = format!("Drop stash@{{{}}}? [y/N]", index);
+                    self.pending_drop = Some(index);
+                }
+            }
+            KeyCode::PageDown | KeyCode::Char('J') => {
+                self.preview_scroll = self.preview_scroll.saturating_add(DIFF_PAGE);
+            }
+            KeyCode::PageUp | KeyCode::Char('K') => {
+                self.preview_scroll = self.preview_scroll.saturating_sub(DIFF_PAGE);
+            }
+            _ => {}
+        }
+        Ok(false) // Continue running
+    }
+
+    fn stash_line(stash: &StashInfo) -> Line<'static> {
+        let mut spans = vec![Span::styled(format!("{} ", stash.refname()), Style::default().fg(Color::Yellow))];
+        if let Some(branch) = &stash.branch {
+            spans.push(Span::styled(format!("({}) ", branch), Style::default().fg(Color::Green)));
+        }
+        spans.push(Span::raw(stash.description().to_string()));
+        spans.push(Span::styled(format!("  {}", relative_time(stash.time)), Style::default().fg(Color::DarkGray)));
+        Line::from(spans)
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let top_text = format!(
+            "gitz - Repository: {}   Branch: {}   Stashes: {}   Untracked: {}   Keep index: {}",
+            repo.root().display(),
+            branch_name,
+            self.stashes.len(),
+            on_off(self.include_untracked),
+            on_off(self.keep_index)
+        );
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Stashes"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Percentage(60)
+            ])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.stashes.is_empty() {
+            vec![ListItem::new(Span::styled("No stashes", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.stashes.iter().map(|s| ListItem::new(Self::stash_line(s))).collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Stashes"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut state = ListState::default();
+        if !self.stashes.is_empty() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        let title = self
+            .stashes
+            .get(self.selected_index)
+            .map(|s| format!("Diff: {}", s.refname()))
+            .unwrap_or_else(|| "Diff".to_string());
+        let lines = diff_view::diff_lines(&self.preview, self.show_line_numbers);
+        diff_view::draw_diff(f, main_chunks[1], &title, lines, self.preview_scroll);
+
+        let help_text = format!(
+            "{} | [s]tash [u]ntracked [i]keep-index [a]pply [p]op [d]rop [J/K]scroll [r]efresh [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        if let Some(input) = &self.message_input {
+            input.draw(f, size);
+        }
+
+        Ok(())
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value { "on" } else { "off" }
+}
+
+impl Default for StashesView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
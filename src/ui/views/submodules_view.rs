@@ -0,0 +1,246 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::{AppEvent, TaskOrigin};
+use crate::git::{Repository, SubmoduleInfo};
+use crate::ui::components::status_bar;
+use crossterm::event::{KeyCode, KeyEvent};
+use git2::Oid;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use tokio::sync::mpsc::Sender;
+
+fn short(oid: Option<Oid>) -> String {
+    oid.map_or("-".to_string(), |oid| oid.to_string()[..7].to_string())
+}
+
+/// The submodules view – lists submodules and initialises, updates, syncs
+/// or opens them.
+pub struct SubmodulesView {
+    submodules: Vec<SubmoduleInfo>,
+    selected_index: usize,
+    status_message: String,
+    /// Description of the running update, if any.
+    running: Option<String>,
+    event_tx: Sender<AppEvent>,
+}
+
+impl SubmodulesView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            submodules: Vec::new(),
+            selected_index: 0,
+            status_message: "Ready".to_string(),
+            running: None,
+            event_tx,
+        }
+    }
+
+    /// Refresh the view data from the repository.
+    pub fn refresh(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.submodules = repo.submodules()?;
+        if self.selected_index >= self.submodules.len() {
+            self.selected_index = self.submodules.len().saturating_sub(1);
+        }
+        self.status_message = format!("Refreshed: {} submodules", self.submodules.len());
+        Ok(())
+    }
+
+    /// Called when the background update has finished.
+    pub fn task_finished(&mut self, repo: &Repository, result: Result<String, String>) -> Result<(), GitzError> {
+        self.running = None;
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(summary) => summary,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Report an error from opening a submodule.
+    pub fn set_error(&mut self, error: &GitzError) {
+        self.status_message = format!("Error: {}", error);
+    }
+
+    fn selected(&self) -> Option<&SubmoduleInfo> {
+        self.submodules.get(self.selected_index)
+    }
+
+    /// Refresh and report the outcome of an operation.
+    fn finish(&mut self, repo: &Repository, result: Result<(), GitzError>, success: String) -> Result<(), GitzError> {
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(()) => success,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Update the selected submodule on a blocking thread; it may need to clone.
+    fn start_update(&mut self, repo: &Repository) {
+        if let Some(running) = &self.running {
+            self.status_message = format!("Busy: {}", running);
+            return;
+        }
+        let Some(path) = self.selected().map(|s| s.path.clone()) else {
+            return;
+        };
+        let label = format!("Updating {}", path);
+        self.status_message = format!("{}...", label);
+        self.running = Some(label);
+
+        let repo = repo.clone();
+        let tx = self.event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = repo
+                .submodule_update(&path, &mut |_| {})
+                .map(|_| format!("Updated {}", path))
+                .map_err(|e| e.to_string());
+            let _ = tx.blocking_send(AppEvent::TaskDone { origin: TaskOrigin::Submodules, result });
+        });
+    }
+
+    /// Hand the selected submodule to the app to browse as a repository.
+    fn open(&mut self, repo: &Repository) {
+        let Some(path) = self.selected().map(|s| s.path.clone()) else {
+            return;
+        };
+        match repo.submodule_workdir(&path) {
+            Ok(dir) => {
+                let _ = self.event_tx.try_send(AppEvent::OpenRepository(dir));
+            }
+            Err(e) => self.status_message = format!("Error: {}", e),
+        }
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => self.refresh(repo)?,
+            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Down | KeyCode::Char('j') if self.selected_index + 1 < self.submodules.len() => {
+                self.selected_index += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_index > 0 => {
+                self.selected_index -= 1;
+            }
+            KeyCode::Char('i') => {
+                if let Some(path) = self.selected().map(|s| s.path.clone()) {
+                    let result = repo.submodule_init(&path);
+                    self.finish(repo, result, format!("Initialized {}", path))?;
+                }
+            }
+            KeyCode::Char('s') => {
+                if let Some(path) = self.selected().map(|s| s.path.clone()) {
+                    let result = repo.submodule_sync(&path);
+                    self.finish(repo, result, format!("Synced URL of {}", path))?;
+                }
+            }
+            KeyCode::Char('u') => self.start_update(repo),
+            KeyCode::Enter => self.open(repo),
+            KeyCode::Backspace => {
+                let _ = self.event_tx.try_send(AppEvent::CloseRepository);
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn submodule_line(sub: &SubmoduleInfo) -> Line<'static> {
+        let state_color = match sub.state() {
+            "up to date" => Color::Green,
+            "modified" | "new commits" => Color::Yellow,
+            _ => Color::Red,
+        };
+        Line::from(vec![
+            Span::styled(sub.path.clone(), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("  {}", short(sub.checked_out.or(sub.recorded))), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("  {}", sub.state()), Style::default().fg(state_color)),
+        ])
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let mut top_text = format!(
+            "gitz - Repository: {}   Branch: {}   Submodules: {}",
+            repo.root().display(),
+            branch_name,
+            self.submodules.len()
+        );
+        if let Some(running) = &self.running {
+            top_text.push_str(&format!("   {}...", running));
+        }
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Submodules"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.submodules.is_empty() {
+            vec![ListItem::new(Span::styled("No submodules", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.submodules.iter().map(|s| ListItem::new(Self::submodule_line(s))).collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Submodules"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut state = ListState::default();
+        if !self.submodules.is_empty() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        let mut lines = Vec::new();
+        if let Some(sub) = self.selected() {
+            lines.push(Line::from(Span::styled(sub.name.clone(), Style::default().add_modifier(Modifier::BOLD))));
+            lines.push(Line::from(format!("Path:        {}", sub.path)));
+            lines.push(Line::from(format!("URL:         {}", sub.url.as_deref().unwrap_or("-"))));
+            lines.push(Line::from(format!("Recorded:    {}", short(sub.recorded))));
+            lines.push(Line::from(format!("Checked out: {}", short(sub.checked_out))));
+            lines.push(Line::from(format!("Initialized: {}", if sub.initialized { "yes" } else { "no" })));
+            lines.push(Line::from(format!("Work tree:   {}", if sub.dirty { "modified" } else { "clean" })));
+        }
+        let details = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Details"));
+        f.render_widget(details, main_chunks[1]);
+
+        let help_text = format!(
+            "{} | [j/k]move [i]nit [u]pdate [s]ync URL [Enter]open [Backspace]parent repo [r]efresh [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+#![allow(dead_code)]
+
+use crate::config::Config;
+use crate::errors::GitzError;
+use crate::event::{AppEvent, TaskOrigin};
+use crate::git::{Repository, TagInfo};
+use crate::ui::components::input::{InputResult, TextInput};
+use crate::ui::components::status_bar;
+use crate::utils::relative_time;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use tokio::sync::mpsc::Sender;
+
+/// What the open text prompt is asking for.
+#[derive(Debug, Clone)]
+enum Prompt {
+    Name,
+    Message { name: String },
+    PushRemote { tag: String },
+    DeleteRemote { tag: String },
+}
+
+/// The tags view – lists, creates and deletes tags, locally and on remotes.
+pub struct TagsView {
+    tags: Vec<TagInfo>,
+    selected_index: usize,
+    status_message: String,
+    prompt: Option<(Prompt, TextInput)>,
+    pending_delete: Option<String>,
+    /// Description of the running push, if any.
+    running: Option<String>,
+    event_tx: Sender<AppEvent>,
+}
+
+impl TagsView {
+    pub fn new(event_tx: Sender<AppEvent>) -> Self {
+        Self {
+            tags: Vec::new(),
+            selected_index: 0,
+            status_message: "Ready".to_string(),
+            prompt: None,
+            pending_delete: None,
+            running: None,
+            event_tx,
+        }
+    }
+
+    /// Refresh the view data from the repository.
+    pub fn refresh(&mut self, repo: &Repository) -> Result<(), GitzError> {
+        self.tags = repo.tags()?;
+        if self.selected_index >= self.tags.len() {
+            self.selected_index = self.tags.len().saturating_sub(1);
+        }
+        self.status_message = format!("Refreshed: {} tags", self.tags.len());
+        Ok(())
+    }
+
+    /// Whether key presses should bypass global shortcuts (a prompt is open).
+    pub fn captures_input(&self) -> bool {
+        self.prompt.is_some()
+    }
+
+    /// Called when the background push has finished.
+    pub fn task_finished(&mut self, repo: &Repository, result: Result<String, String>) -> Result<(), GitzError> {
+        self.running = None;
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(summary) => summary,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&TagInfo> {
+        self.tags.get(self.selected_index)
+    }
+
+    /// Refresh and report the outcome of an operation.
+    fn finish(&mut self, repo: &Repository, result: Result<(), GitzError>, success: String) -> Result<(), GitzError> {
+        self.refresh(repo)?;
+        self.status_message = match result {
+            Ok(()) => success,
+            Err(e) => format!("Error: {}", e),
+        };
+        Ok(())
+    }
+
+    /// Remote suggested in the push prompts.
+    fn default_remote(repo: &Repository) -> String {
+        let remotes = repo.remotes().unwrap_or_default();
+        match remotes.iter().find(|r| r.name == "origin").or(remotes.first()) {
+            Some(remote) => remote.name.clone(),
+            None => "origin".to_string(),
+        }
+    }
+
+    /// Push or delete `tag` on `remote` on a blocking thread.
+    fn start(&mut self, repo: &Repository, remote: String, tag: String, delete: bool) {
+        if let Some(running) = &self.running {
+            self.status_message = format!("Busy: {}", running);
+            return;
+        }
+        let label = if delete {
+            format!("Deleting {} on {}", tag, remote)
+        } else {
+            format!("Pushing {} to {}", tag, remote)
+        };
+        self.status_message = format!("{}...", label);
+        self.running = Some(label);
+
+        let repo = repo.clone();
+        let tx = self.event_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut progress = |_| {};
+            let result = if delete {
+                repo.delete_remote_tag(&remote, &tag, &mut progress).map(|_| format!("Deleted {} on {}", tag, remote))
+            } else {
+                repo.push_tag(&remote, &tag, &mut progress).map(|_| format!("Pushed {} to {}", tag, remote))
+            };
+            let result = result.map_err(|e| e.to_string());
+            let _ = tx.blocking_send(AppEvent::TaskDone { origin: TaskOrigin::Tags, result });
+        });
+    }
+
+    fn submit_prompt(&mut self, prompt: Prompt, value: String, repo: &Repository) -> Result<(), GitzError> {
+        let value = value.trim().to_string();
+        match prompt {
+            Prompt::Name => {
+                let title = format!("Message for {} (empty for a lightweight tag)", value);
+                self.prompt = Some((Prompt::Message { name: value }, TextInput::new(title, "")));
+            }
+            Prompt::Message { name } => {
+                let result = match repo.head_oid() {
+                    Some(head) => repo.create_tag(&name, head, Some(&value)),
+                    None => Err(GitzError::InvalidInput("HEAD has no commit to tag".into())),
+                };
+                self.finish(repo, result, format!("Created tag {}", name))?;
+                if let Some(idx) = self.tags.iter().position(|t| t.name == name) {
+                    self.selected_index = idx;
+                }
+            }
+            Prompt::PushRemote { tag } => self.start(repo, value, tag, false),
+            Prompt::DeleteRemote { tag } => self.start(repo, value, tag, true),
+        }
+        Ok(())
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        repo: &Repository,
+        _cfg: &Config
+    ) -> Result<bool, GitzError> {
+        if let Some((prompt, mut input)) = self.prompt.take() {
+            match input.handle_key(key) {
+                InputResult::Pending => self.prompt = Some((prompt, input)),
+                InputResult::Cancel => self.status_message = "Cancelled".to_string(),
+                InputResult::Submit(value) => self.submit_prompt(prompt, value, repo)?,
+            }
+            return Ok(false);
+        }
+
+        if let Some(name) = self.pending_delete.take() {
+            if key.code == KeyCode::Char('y') {
+                let result = repo.delete_tag(&name);
+                self.finish(repo, result, format!("Deleted tag {}", name))?;
+            } else {
+                self.status_message = "Delete cancelled".to_string();
+            }
+            return Ok(false);
+        }
+
+        match key.code {
+            KeyCode::Char('r') | KeyCode::F(5) => self.refresh(repo)?,
+            KeyCode::Char('q') => return Ok(true),
+            KeyCode::Down | KeyCode::Char('j') if self.selected_index + 1 < self.tags.len() => {
+                self.selected_index += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_index > 0 => {
+                self.selected_index -= 1;
+            }
+            KeyCode::Char('n') => {
+                self.prompt = Some((Prompt::Name, TextInput::new("New tag at HEAD", "")));
+            }
+            KeyCode::Char('d') => {
+                if let Some(name) = self.selected().map(|t| t.name.clone()) {
+                    self.status_message = format!("Delete tag {}? [y/N]", name);
+                    self.pending_delete = Some(name);
+                }
+            }
+            KeyCode::Char('P') => {
+                if let Some(tag) = self.selected().map(|t| t.name.clone()) {
+                    let input = TextInput::new(format!("Push {} to remote", tag), Self::default_remote(repo));
+                    self.prompt = Some((Prompt::PushRemote { tag }, input));
+                }
+            }
+            KeyCode::Char('D') => {
+                if let Some(tag) = self.selected().map(|t| t.name.clone()) {
+                    let input = TextInput::new(format!("Delete {} on remote", tag), Self::default_remote(repo));
+                    self.prompt = Some((Prompt::DeleteRemote { tag }, input));
+                }
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn tag_line(tag: &TagInfo) -> Line<'static> {
+        let name_color = if tag.annotated { Color::Yellow } else { Color::LightYellow };
+        Line::from(vec![
+            Span::styled(tag.name.clone(), Style::default().fg(name_color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  {}", tag.short_target()), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("  {}", tag.summary), Style::default().fg(Color::Gray)),
+        ])
+    }
+
+    /// Draw the UI.
+    pub fn draw(
+        &self,
+        f: &mut ratatui::Frame,
+        repo: &Repository
+    ) -> Result<(), GitzError> {
+        let size = f.area();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // top bar (with border)
+                Constraint::Min(0),    // main area
+                Constraint::Length(3), // status bar (with border)
+            ])
+            .split(size);
+
+        let branch_name = repo.current_branch()
+            .unwrap_or_else(|_| "unknown".to_string());
+        let mut top_text = format!(
+            "gitz - Repository: {}   Branch: {}   Tags: {}",
+            repo.root().display(),
+            branch_name,
+            self.tags.len()
+        );
+        if let Some(running) = &self.running {
+            top_text.push_str(&format!("   {}...", running));
+        }
+        let top_bar = Paragraph::new(top_text)
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title("⚡ gitz - Tags"));
+        f.render_widget(top_bar, chunks[0]);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = if self.tags.is_empty() {
+            vec![ListItem::new(Span::styled("No tags", Style::default().fg(Color::DarkGray)))]
+        } else {
+            self.tags.iter().map(|t| ListItem::new(Self::tag_line(t))).collect()
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Tags"))
+            .highlight_style(Style::default().bg(Color::Blue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("» ");
+        let mut state = ListState::default();
+        if !self.tags.is_empty() {
+            state.select(Some(self.selected_index));
+        }
+        f.render_stateful_widget(list, main_chunks[0], &mut state);
+
+        let mut lines = Vec::new();
+        if let Some(tag) = self.selected() {
+            lines.push(Line::from(Span::styled(
+                format!("{} ({})", tag.name, tag.kind()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(format!("Commit: {}", tag.target)));
+            lines.push(Line::from(format!("        {}", tag.summary)));
+            if let (Some(tagger), Some(time)) = (&tag.tagger, tag.time) {
+                lines.push(Line::from(format!("Tagger: {}", tagger)));
+                lines.push(Line::from(format!("Date:   {}", relative_time(time))));
+            }
+            if let Some(message) = &tag.message {
+                lines.push(Line::from(""));
+                lines.extend(message.lines().map(|l| Line::from(format!("    {}", l))));
+            }
+        }
+        let details = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title("Details"));
+        f.render_widget(details, main_chunks[1]);
+
+        let help_text = format!(
+            "{} | [j/k]move [n]ew tag at HEAD [d]elete [P]ush to remote [D]elete on remote [r]efresh [q]uit",
+            self.status_message
+        );
+        status_bar::draw_status_bar(f, chunks[2], &help_text);
+
+        if let Some((_, input)) = &self.prompt {
+            input.draw(f, size);
+        }
+
+        Ok(())
+    }
+}
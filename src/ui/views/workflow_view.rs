@@ -30,15 +30,11 @@ impl WorkflowView {
     /// Handle key events for the workflow view.
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent, _repo: &Repository, _config: &Config) -> Result<(), GitzError> {
         match key.code {
-            crossterm::event::KeyCode::Up => {
-                if self.selected > 0 {
-                    self.selected -= 1;
-                }
+            crossterm::event::KeyCode::Up if self.selected > 0 => {
+                self.selected -= 1;
             }
-            crossterm::event::KeyCode::Down => {
-                if self.selected < self.workflows.len() - 1 {
-                    self.selected += 1;
-                }
+            crossterm::event::KeyCode::Down if self.selected < self.workflows.len() - 1 => {
+                self.selected += 1;
             }
             _ => {}
         }
@@ -45,27 +45,23 @@ impl WorktreesView {
             crossterm::event::KeyCode::Char('q') => {
                 return Ok(true); // Signal to quit
             }
-            crossterm::event::KeyCode::Down | crossterm::event::KeyCode::Char('j') => {
+            crossterm::event::KeyCode::Down | crossterm::event::KeyCode::Char('j')
+                if !self.worktrees.is_empty() && self.selected_index < self.worktrees.len() - 1 =>
+            {
                 // Navigate down in worktrees list
-                if !self.worktrees.is_empty() && self.selected_index < self.worktrees.len() - 1 {
-                    self.selected_index += 1;
-                }
+                self.selected_index += 1;
             }
-            crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') => {
+            crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Char('k') if self.selected_index > 0 => {
                 // Navigate up in worktrees list
-                if self.selected_index > 0 {
-                    self.selected_index -= 1;
-                }
+                self.selected_index -= 1;
             }
             crossterm::event::KeyCode::Home | crossterm::event::KeyCode::Char('g') => {
                 // Go to first worktree
                 self.selected_index = 0;
             }
-            crossterm::event::KeyCode::End | crossterm::event::KeyCode::Char('G') => {
+            crossterm::event::KeyCode::End | crossterm::event::KeyCode::Char('G') if !self.worktrees.is_empty() => {
                 // Go to last worktree
-                if !self.worktrees.is_empty() {
-                    self.selected_index = self.worktrees.len() - 1;
-                }
+                self.selected_index = self.worktrees.len() - 1;
             }
             _ => {}
         }
@@ -96,7 +92,7 @@ impl WorktreesView {
 
         let top_text = format!(
             "gitz - Repository: {}   Branch: {}   Worktrees: {}",
-            repo.path().display(),
+            repo.root().display(),
             branch_name,
             self.worktrees.len()
         );
@@ -1,8 +1,80 @@
 // Utility helpers for the application.
 #![allow(dead_code)]
 
+/// Length of time in its largest whole unit, e.g. `3 hours`.
 pub fn format_duration(secs: u64) -> String {
-    let mins = secs / 60;
-    let secs = secs % 60;
-    format!("{:02}:{:02}", mins, secs)
+    let (value, unit) = match secs {
+        0..=59 => (secs, "second"),
+        60..=3_599 => (secs / 60, "minute"),
+        3_600..=86_399 => (secs / 3_600, "hour"),
+        86_400..=2_591_999 => (secs / 86_400, "day"),
+        2_592_000..=31_535_999 => (secs / 2_592_000, "month"),
+        _ => (secs / 31_536_000, "year"),
+    };
+    format!("{} {}{}", value, unit, if value == 1 { "" } else { "s" })
+}
+
+/// Human-readable byte count, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Age of a unix timestamp relative to `now`, e.g. `3 days ago`.
+pub fn format_relative_time(time: i64, now: i64) -> String {
+    let secs = now - time;
+    if secs < 0 {
+        return "in the future".into();
+    }
+    if secs < 60 {
+        return "just now".into();
+    }
+    format!("{} ago", format_duration(secs as u64))
+}
+
+/// Age of a unix timestamp relative to the current time.
+pub fn relative_time(time: i64) -> String {
+    format_relative_time(time, chrono::Utc::now().timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(1), "1 second");
+        assert_eq!(format_duration(45), "45 seconds");
+        assert_eq!(format_duration(90), "1 minute");
+        assert_eq!(format_duration(3 * 3_600), "3 hours");
+        assert_eq!(format_duration(2 * 86_400), "2 days");
+        assert_eq!(format_duration(400 * 86_400), "1 year");
+    }
+
+    #[test]
+    fn test_format_relative_time() {
+        let now = 1_700_000_000;
+        assert_eq!(format_relative_time(now - 5, now), "just now");
+        assert_eq!(format_relative_time(now - 60, now), "1 minute ago");
+        assert_eq!(format_relative_time(now - 3 * 3_600, now), "3 hours ago");
+        assert_eq!(format_relative_time(now - 2 * 86_400, now), "2 days ago");
+        assert_eq!(format_relative_time(now - 400 * 86_400, now), "1 year ago");
+        assert_eq!(format_relative_time(now + 10, now), "in the future");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
 }